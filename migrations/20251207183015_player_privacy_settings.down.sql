@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+DROP TABLE player_privacy_settings;
+
+ALTER TABLE users DROP COLUMN created_at;
+
+DROP TYPE privacy_level;
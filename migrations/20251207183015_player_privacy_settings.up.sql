@@ -0,0 +1,16 @@
+-- Add up migration script here
+
+CREATE TYPE privacy_level AS ENUM ('public', 'friends_only', 'hidden');
+
+ALTER TABLE users ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT now();
+
+CREATE TABLE player_privacy_settings (
+  user_id UUID PRIMARY KEY REFERENCES player_profiles(user_id) ON DELETE CASCADE,
+  name_visibility privacy_level NOT NULL DEFAULT 'public',
+  member_since_visibility privacy_level NOT NULL DEFAULT 'public',
+  total_bookings_visibility privacy_level NOT NULL DEFAULT 'public',
+  favorite_sport_visibility privacy_level NOT NULL DEFAULT 'public'
+);
+
+INSERT INTO player_privacy_settings (user_id)
+SELECT user_id FROM player_profiles;
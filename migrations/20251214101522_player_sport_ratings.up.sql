@@ -0,0 +1,13 @@
+-- Add up migration script here
+
+CREATE TABLE player_sport_ratings (
+  player_id UUID NOT NULL REFERENCES player_profiles(user_id) ON DELETE CASCADE,
+  sport sport NOT NULL,
+  self_declared_level SMALLINT CHECK (self_declared_level BETWEEN 1 AND 10),
+  system_rating REAL,
+  confidence REAL NOT NULL DEFAULT 0 CHECK (confidence BETWEEN 0 AND 1),
+  PRIMARY KEY (player_id, sport)
+);
+
+-- used by matchmaking to find players of a similar rank in a sport
+CREATE INDEX player_sport_ratings_sport_system_rating_idx ON player_sport_ratings (sport, system_rating);
@@ -0,0 +1,11 @@
+-- Add up migration script here
+
+CREATE TABLE favorite_pitches (
+  player_id UUID NOT NULL REFERENCES player_profiles(user_id) ON DELETE CASCADE,
+  pitch_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  PRIMARY KEY (player_id, pitch_id)
+);
+
+-- used to count the favorites of a pitch
+CREATE INDEX favorite_pitches_pitch_id_idx ON favorite_pitches (pitch_id);
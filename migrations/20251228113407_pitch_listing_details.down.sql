@@ -0,0 +1,10 @@
+-- Add down migration script here
+
+DROP TABLE pitch_reviews;
+
+ALTER TABLE pitches
+  DROP COLUMN amenities,
+  DROP COLUMN image_url,
+  DROP COLUMN price_per_hour_cents;
+
+ALTER TABLE users DROP COLUMN avatar_url;
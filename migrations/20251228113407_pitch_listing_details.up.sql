@@ -0,0 +1,21 @@
+-- Add up migration script here
+
+ALTER TABLE users ADD COLUMN avatar_url TEXT;
+
+ALTER TABLE pitches
+  ADD COLUMN price_per_hour_cents INTEGER NOT NULL DEFAULT 0 CHECK (price_per_hour_cents >= 0),
+  ADD COLUMN image_url TEXT,
+  ADD COLUMN amenities TEXT[] NOT NULL DEFAULT '{}';
+
+-- the default is only used to fill existing rows
+ALTER TABLE pitches ALTER COLUMN price_per_hour_cents DROP DEFAULT;
+
+CREATE TABLE pitch_reviews (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  pitch_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  author_id UUID NOT NULL REFERENCES player_profiles(user_id) ON DELETE CASCADE,
+  rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+  comment TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (pitch_id, author_id)
+);
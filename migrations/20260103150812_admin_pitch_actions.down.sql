@@ -0,0 +1,9 @@
+-- Add down migration script here
+
+DROP TABLE notifications;
+
+DROP TABLE pitch_admin_actions;
+
+DROP TYPE pitch_admin_action;
+
+ALTER TABLE pitches DROP COLUMN is_hidden;
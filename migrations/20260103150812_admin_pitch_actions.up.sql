@@ -0,0 +1,26 @@
+-- Add up migration script here
+
+ALTER TABLE pitches ADD COLUMN is_hidden BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TYPE pitch_admin_action AS ENUM ('create', 'update', 'hide', 'unhide', 'delete');
+
+-- pitch_id has no foreign key so that deletions stay on record
+CREATE TABLE pitch_admin_actions (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  admin_id UUID NOT NULL REFERENCES users(id),
+  pitch_id UUID NOT NULL,
+  owner_id UUID NOT NULL REFERENCES business_profiles(user_id) ON DELETE CASCADE,
+  action pitch_admin_action NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX pitch_admin_actions_pitch_id_idx ON pitch_admin_actions (pitch_id);
+
+CREATE TABLE notifications (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  message TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX notifications_user_id_created_at_idx ON notifications (user_id, created_at);
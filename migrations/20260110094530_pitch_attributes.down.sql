@@ -0,0 +1,35 @@
+-- Add down migration script here
+
+ALTER TABLE pitches ADD COLUMN amenities TEXT[] NOT NULL DEFAULT '{}';
+
+UPDATE
+  pitches
+SET
+  amenities = ARRAY(
+    SELECT
+      amenities.name
+    FROM
+      pitch_amenities
+    JOIN
+      amenities
+    ON
+      amenities.id = pitch_amenities.amenity_id
+    WHERE
+      pitch_amenities.pitch_id = pitches.id
+    ORDER BY
+      amenities.name
+  );
+
+DROP TABLE pitch_amenities;
+
+DROP TABLE amenities;
+
+ALTER TABLE pitches
+  DROP COLUMN surface,
+  DROP COLUMN environment,
+  DROP COLUMN description,
+  DROP COLUMN max_players;
+
+DROP TYPE pitch_environment;
+
+DROP TYPE surface_type;
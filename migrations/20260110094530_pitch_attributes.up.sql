@@ -0,0 +1,55 @@
+-- Add up migration script here
+
+CREATE TYPE surface_type AS ENUM ('grass', 'artificial_turf', 'hardcourt', 'clay', 'wood');
+
+CREATE TYPE pitch_environment AS ENUM ('indoor', 'outdoor');
+
+ALTER TABLE pitches
+  ADD COLUMN surface surface_type,
+  ADD COLUMN environment pitch_environment,
+  ADD COLUMN description TEXT,
+  ADD COLUMN max_players SMALLINT CHECK (max_players > 0);
+
+CREATE TABLE amenities (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  name VARCHAR(64) UNIQUE NOT NULL
+);
+
+INSERT INTO amenities (name)
+VALUES
+  ('lights'),
+  ('water'),
+  ('parking'),
+  ('showers'),
+  ('changing_rooms'),
+  ('equipment_rental'),
+  ('cafe');
+
+CREATE TABLE pitch_amenities (
+  pitch_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  amenity_id UUID NOT NULL REFERENCES amenities(id) ON DELETE CASCADE,
+  PRIMARY KEY (pitch_id, amenity_id)
+);
+
+CREATE INDEX pitch_amenities_amenity_id_idx ON pitch_amenities (amenity_id);
+
+-- move the free-form amenities over to the catalog
+INSERT INTO amenities (name)
+SELECT DISTINCT
+  UNNEST(amenities)
+FROM
+  pitches
+ON CONFLICT DO NOTHING;
+
+INSERT INTO pitch_amenities (pitch_id, amenity_id)
+SELECT
+  pitches.id,
+  amenities.id
+FROM
+  pitches
+JOIN
+  amenities
+ON
+  amenities.name = ANY(pitches.amenities);
+
+ALTER TABLE pitches DROP COLUMN amenities;
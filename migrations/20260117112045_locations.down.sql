@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+ALTER TABLE pitches DROP COLUMN location_id;
+
+DROP TABLE locations;
+
+DROP EXTENSION IF EXISTS earthdistance;
+DROP EXTENSION IF EXISTS cube;
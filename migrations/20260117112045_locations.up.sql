@@ -0,0 +1,25 @@
+-- Add up migration script here
+
+-- earthdistance ships with postgres (contrib), so geo search doesn't need PostGIS.
+-- it models the earth as a sphere, which is precise enough for finding nearby pitches.
+CREATE EXTENSION IF NOT EXISTS cube;
+CREATE EXTENSION IF NOT EXISTS earthdistance;
+
+CREATE TABLE locations (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  address_line_1 VARCHAR(256) NOT NULL,
+  address_line_2 VARCHAR(256),
+  city VARCHAR(128) NOT NULL,
+  postal_code VARCHAR(32),
+  country VARCHAR(128) NOT NULL,
+  latitude DOUBLE PRECISION NOT NULL CHECK (latitude BETWEEN -90 AND 90),
+  longitude DOUBLE PRECISION NOT NULL CHECK (longitude BETWEEN -180 AND 180)
+);
+
+-- used by radius searches (earth_box)
+CREATE INDEX locations_earth_idx ON locations USING gist (ll_to_earth(latitude, longitude));
+
+-- used by bounding box searches
+CREATE INDEX locations_latitude_longitude_idx ON locations (latitude, longitude);
+
+ALTER TABLE pitches ADD COLUMN location_id UUID UNIQUE REFERENCES locations(id) ON DELETE SET NULL;
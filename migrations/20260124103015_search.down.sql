@@ -0,0 +1,9 @@
+-- Add down migration script here
+DROP INDEX locations_city_trgm_idx;
+DROP INDEX business_profiles_display_name_trgm_idx;
+DROP INDEX pitches_display_name_trgm_idx;
+
+ALTER TABLE pitches
+DROP COLUMN search_vector;
+
+DROP EXTENSION IF EXISTS pg_trgm;
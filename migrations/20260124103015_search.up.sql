@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- names weigh more than descriptions in the ranking
+ALTER TABLE pitches
+ADD COLUMN search_vector TSVECTOR NOT NULL GENERATED ALWAYS AS (
+  setweight(to_tsvector('english', display_name), 'A')
+  || setweight(to_tsvector('english', COALESCE(description, '')), 'B')
+) STORED;
+
+CREATE INDEX pitches_search_vector_idx ON pitches USING gin (search_vector);
+
+-- trigram indexes catch typos that full-text search misses
+CREATE INDEX pitches_display_name_trgm_idx ON pitches USING gin (display_name gin_trgm_ops);
+CREATE INDEX business_profiles_display_name_trgm_idx ON business_profiles USING gin (display_name gin_trgm_ops);
+CREATE INDEX locations_city_trgm_idx ON locations USING gin (city gin_trgm_ops);
@@ -0,0 +1,21 @@
+-- Add down migration script here
+
+DROP VIEW bookable_timeslots;
+
+DROP TABLE pitch_closures;
+
+-- enum values can't be dropped, so the type is recreated without them
+DELETE FROM pitch_admin_actions WHERE action IN ('publish', 'archive', 'restore');
+ALTER TYPE pitch_admin_action RENAME TO pitch_admin_action_old;
+CREATE TYPE pitch_admin_action AS ENUM ('create', 'update', 'hide', 'unhide', 'delete');
+ALTER TABLE pitch_admin_actions
+  ALTER COLUMN action TYPE pitch_admin_action USING action::TEXT::pitch_admin_action;
+DROP TYPE pitch_admin_action_old;
+
+ALTER TABLE pitches ADD COLUMN is_hidden BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE pitches SET is_hidden = status <> 'published';
+
+ALTER TABLE pitches DROP COLUMN status;
+
+DROP TYPE pitch_status;
@@ -0,0 +1,58 @@
+-- Add up migration script here
+
+-- draft -> published <-> hidden, and any of them -> archived -> draft.
+-- only published pitches are listed and bookable.
+CREATE TYPE pitch_status AS ENUM ('draft', 'published', 'hidden', 'archived');
+
+ALTER TABLE pitches ADD COLUMN status pitch_status NOT NULL DEFAULT 'published';
+
+UPDATE pitches SET status = 'hidden' WHERE is_hidden;
+
+ALTER TABLE pitches DROP COLUMN is_hidden;
+
+ALTER TYPE pitch_admin_action ADD VALUE 'publish';
+ALTER TYPE pitch_admin_action ADD VALUE 'archive';
+ALTER TYPE pitch_admin_action ADD VALUE 'restore';
+
+-- pending closures wait for the owner to confirm them, since they may cancel bookings.
+-- only confirmed closures make timeslots unbookable.
+CREATE TABLE pitch_closures (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  pitch_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  period TSTZRANGE NOT NULL CHECK (NOT isempty(period)),
+  reason VARCHAR(256) NOT NULL,
+  is_confirmed BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  EXCLUDE USING gist (
+    pitch_id WITH =,
+    period WITH &&
+  )
+);
+
+-- free timeslots of published pitches that no confirmed closure overlaps
+CREATE VIEW bookable_timeslots AS
+SELECT
+  timeslots.*
+FROM
+  timeslots
+JOIN
+  pitches
+ON
+  pitches.id = timeslots.pitch_id
+WHERE
+  NOT timeslots.is_booked
+AND
+  pitches.status = 'published'
+AND
+  NOT EXISTS (
+    SELECT
+      1
+    FROM
+      pitch_closures
+    WHERE
+      pitch_closures.pitch_id = timeslots.pitch_id
+    AND
+      pitch_closures.is_confirmed
+    AND
+      pitch_closures.period && timeslots.time_range
+  );
@@ -0,0 +1,17 @@
+-- Add down migration script here
+
+DROP TRIGGER timeslots_within_opening_hours ON timeslots;
+
+DROP FUNCTION check_timeslot_within_opening_hours();
+
+DROP FUNCTION is_within_opening_hours(UUID, TSTZRANGE);
+
+DROP FUNCTION effective_opening_hours(UUID, DATE, DATE);
+
+DROP TABLE pitch_opening_hour_exceptions;
+
+DROP TABLE pitch_opening_hours;
+
+DROP TYPE weekday;
+
+ALTER TABLE pitches DROP COLUMN timezone;
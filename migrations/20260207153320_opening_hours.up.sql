@@ -0,0 +1,159 @@
+-- Add up migration script here
+
+-- IANA name, e.g. 'Asia/Dubai'. opening hours are declared in the pitch's local time.
+ALTER TABLE pitches ADD COLUMN timezone VARCHAR(64) NOT NULL DEFAULT 'UTC';
+
+-- declared in ISO order, so the position in enum_range() is the ISO day of the week
+CREATE TYPE weekday AS ENUM (
+  'monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday', 'sunday'
+);
+
+-- a day can have several intervals, e.g. 06:00-12:00 and 16:00-24:00.
+-- pitches without any weekly hours are open all day, every day.
+CREATE TABLE pitch_opening_hours (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  pitch_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  weekday weekday NOT NULL,
+  opens_at TIME NOT NULL,
+  closes_at TIME NOT NULL,
+  CHECK (opens_at < closes_at)
+);
+
+CREATE INDEX pitch_opening_hours_pitch_id_idx ON pitch_opening_hours (pitch_id);
+
+-- replace the weekly hours on their date, a row without hours closes the pitch for the day
+CREATE TABLE pitch_opening_hour_exceptions (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  pitch_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  date DATE NOT NULL,
+  opens_at TIME,
+  closes_at TIME,
+  reason VARCHAR(256),
+  CHECK ((opens_at IS NULL) = (closes_at IS NULL)),
+  CHECK (opens_at < closes_at)
+);
+
+CREATE INDEX pitch_opening_hour_exceptions_pitch_id_date_idx
+  ON pitch_opening_hour_exceptions (pitch_id, date);
+
+-- opening intervals of each day between the two dates (in the pitch's local time), days without
+-- any interval are closed
+CREATE FUNCTION effective_opening_hours(target_pitch_id UUID, from_date DATE, to_date DATE)
+RETURNS TABLE (date DATE, opens_at TIMESTAMPTZ, closes_at TIMESTAMPTZ, reason VARCHAR)
+LANGUAGE SQL STABLE AS $$
+  WITH pitch AS (
+    SELECT
+      timezone,
+      EXISTS (
+        SELECT 1 FROM pitch_opening_hours WHERE pitch_opening_hours.pitch_id = pitches.id
+      ) AS has_weekly_hours
+    FROM
+      pitches
+    WHERE
+      id = target_pitch_id
+  ), days AS (
+    SELECT
+      day::DATE AS date
+    FROM
+      generate_series(from_date, to_date, INTERVAL '1 day') day
+  ), local_hours AS (
+    SELECT
+      days.date,
+      days.date + exceptions.opens_at AS opens_at,
+      days.date + exceptions.closes_at AS closes_at,
+      exceptions.reason
+    FROM
+      days
+    JOIN
+      pitch_opening_hour_exceptions exceptions
+    ON
+      exceptions.pitch_id = target_pitch_id
+    AND
+      exceptions.date = days.date
+    UNION ALL
+    SELECT
+      days.date,
+      days.date + weekly.opens_at,
+      days.date + weekly.closes_at,
+      NULL
+    FROM
+      days
+    JOIN
+      pitch_opening_hours weekly
+    ON
+      weekly.pitch_id = target_pitch_id
+    AND
+      array_position(enum_range(NULL::weekday), weekly.weekday) = EXTRACT(ISODOW FROM days.date)
+    WHERE
+      NOT EXISTS (
+        SELECT 1
+        FROM pitch_opening_hour_exceptions exceptions
+        WHERE exceptions.pitch_id = target_pitch_id AND exceptions.date = days.date
+      )
+    UNION ALL
+    SELECT
+      days.date,
+      days.date::TIMESTAMP,
+      (days.date + 1)::TIMESTAMP,
+      NULL
+    FROM
+      days
+    CROSS JOIN
+      pitch
+    WHERE
+      NOT pitch.has_weekly_hours
+    AND
+      NOT EXISTS (
+        SELECT 1
+        FROM pitch_opening_hour_exceptions exceptions
+        WHERE exceptions.pitch_id = target_pitch_id AND exceptions.date = days.date
+      )
+  )
+  SELECT
+    local_hours.date,
+    local_hours.opens_at AT TIME ZONE pitch.timezone,
+    local_hours.closes_at AT TIME ZONE pitch.timezone,
+    local_hours.reason
+  FROM
+    local_hours
+  CROSS JOIN
+    pitch
+$$;
+
+-- whether the time range is covered by the opening hours of the pitch. adjacent intervals are
+-- merged, so a slot can run past midnight when the pitch is open on both sides of it
+CREATE FUNCTION is_within_opening_hours(target_pitch_id UUID, time_range TSTZRANGE)
+RETURNS BOOLEAN
+LANGUAGE SQL STABLE AS $$
+  SELECT
+    COALESCE(range_agg(TSTZRANGE(hours.opens_at, hours.closes_at)) @> time_range, FALSE)
+  FROM
+    pitches
+  CROSS JOIN LATERAL
+    effective_opening_hours(
+      pitches.id,
+      (LOWER(time_range) AT TIME ZONE pitches.timezone)::DATE - 1,
+      (UPPER(time_range) AT TIME ZONE pitches.timezone)::DATE
+    ) hours
+  WHERE
+    pitches.id = target_pitch_id
+  AND
+    hours.opens_at IS NOT NULL
+$$;
+
+-- raised as a check violation, so it can be told apart by its constraint name like any other
+CREATE FUNCTION check_timeslot_within_opening_hours()
+RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+  IF NOT is_within_opening_hours(NEW.pitch_id, NEW.time_range) THEN
+    RAISE EXCEPTION 'timeslot % is outside the opening hours of pitch %', NEW.time_range, NEW.pitch_id
+      USING ERRCODE = 'check_violation', CONSTRAINT = 'timeslots_within_opening_hours';
+  END IF;
+  RETURN NEW;
+END;
+$$;
+
+CREATE TRIGGER timeslots_within_opening_hours
+  BEFORE INSERT OR UPDATE OF pitch_id, time_range ON timeslots
+  FOR EACH ROW EXECUTE FUNCTION check_timeslot_within_opening_hours();
@@ -0,0 +1,19 @@
+-- Add down migration script here
+
+DROP TRIGGER timeslots_occupy_units ON timeslots;
+
+DROP FUNCTION occupy_timeslot_units();
+
+DROP FUNCTION set_timeslot_occupancy(UUID, UUID, TSTZRANGE);
+
+DROP TABLE timeslot_occupancy;
+
+DROP FUNCTION pitch_units(UUID);
+
+DROP TABLE pitch_components;
+
+ALTER TABLE pitches
+  DROP COLUMN other_sports,
+  DROP COLUMN facility_id;
+
+DROP TABLE facilities;
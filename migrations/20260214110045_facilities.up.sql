@@ -0,0 +1,104 @@
+-- Add up migration script here
+
+-- a venue grouping the playable units (pitches) of one business
+CREATE TABLE facilities (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  owner_id UUID NOT NULL REFERENCES business_profiles(user_id) ON DELETE CASCADE,
+  display_name VARCHAR(128) NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  UNIQUE (owner_id, display_name)
+);
+
+ALTER TABLE pitches
+  ADD COLUMN facility_id UUID REFERENCES facilities(id) ON DELETE SET NULL,
+  -- besides `sport`, which stays the main one
+  ADD COLUMN other_sports sport[] NOT NULL DEFAULT '{}';
+
+-- a combined configuration (e.g. a full field) is made of the units it takes up (e.g. its two
+-- halves), all in the same facility
+CREATE TABLE pitch_components (
+  pitch_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  component_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  PRIMARY KEY (pitch_id, component_id),
+  CHECK (pitch_id <> component_id)
+);
+
+CREATE INDEX pitch_components_component_id_idx ON pitch_components (component_id);
+
+-- the units a pitch takes up when it's played on: itself, or the units of its components
+CREATE FUNCTION pitch_units(target_pitch_id UUID)
+RETURNS TABLE (unit_id UUID)
+LANGUAGE SQL STABLE AS $$
+  WITH RECURSIVE parts AS (
+    SELECT
+      target_pitch_id AS pitch_id
+    UNION
+    SELECT
+      pitch_components.component_id
+    FROM
+      parts
+    JOIN
+      pitch_components
+    ON
+      pitch_components.pitch_id = parts.pitch_id
+  )
+  SELECT
+    parts.pitch_id
+  FROM
+    parts
+  WHERE
+    NOT EXISTS (
+      SELECT 1 FROM pitch_components WHERE pitch_components.pitch_id = parts.pitch_id
+    )
+$$;
+
+-- the units each timeslot takes up, so that the timeslots of a combined configuration and of its
+-- components can't overlap, like the timeslots of a single pitch
+CREATE TABLE timeslot_occupancy (
+  timeslot_id UUID NOT NULL REFERENCES timeslots(id) ON DELETE CASCADE,
+  unit_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  time_range TSTZRANGE NOT NULL,
+  PRIMARY KEY (timeslot_id, unit_id),
+  EXCLUDE USING gist (
+    unit_id WITH =,
+    time_range WITH &&
+  )
+);
+
+CREATE FUNCTION set_timeslot_occupancy(
+  target_timeslot_id UUID,
+  target_pitch_id UUID,
+  target_time_range TSTZRANGE
+)
+RETURNS VOID
+LANGUAGE SQL AS $$
+  DELETE FROM
+    timeslot_occupancy
+  WHERE
+    timeslot_id = target_timeslot_id;
+
+  INSERT INTO timeslot_occupancy
+    (timeslot_id, unit_id, time_range)
+  SELECT
+    target_timeslot_id, unit_id, target_time_range
+  FROM
+    pitch_units(target_pitch_id);
+$$;
+
+CREATE FUNCTION occupy_timeslot_units()
+RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+  PERFORM set_timeslot_occupancy(NEW.id, NEW.pitch_id, NEW.time_range);
+  RETURN NEW;
+END;
+$$;
+
+CREATE TRIGGER timeslots_occupy_units
+  AFTER INSERT OR UPDATE OF pitch_id, time_range ON timeslots
+  FOR EACH ROW EXECUTE FUNCTION occupy_timeslot_units();
+
+SELECT
+  set_timeslot_occupancy(id, pitch_id, time_range)
+FROM
+  timeslots;
@@ -0,0 +1,11 @@
+-- Add down migration script here
+
+DROP FUNCTION materialize_timeslot_rule(UUID, TIMESTAMPTZ);
+
+DROP TABLE cancelled_timeslot_occurrences;
+
+ALTER TABLE timeslots
+  DROP COLUMN rule_id,
+  DROP COLUMN occurrence_starts_at;
+
+DROP TABLE timeslot_rules;
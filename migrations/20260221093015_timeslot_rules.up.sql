@@ -0,0 +1,116 @@
+-- Add up migration script here
+
+-- RRULE-like weekly series, times are in the pitch's local time.
+-- e.g. weekdays from 17:00 to 23:00 in 60 minutes slots, until the end of the season.
+CREATE TABLE timeslot_rules (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  pitch_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  weekdays weekday[] NOT NULL CHECK (cardinality(weekdays) > 0),
+  starts_at TIME NOT NULL,
+  ends_at TIME NOT NULL,
+  slot_minutes INTEGER NOT NULL CHECK (slot_minutes > 0),
+  starts_on DATE NOT NULL,
+  -- included, null for a series without an end
+  until DATE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  -- '24:00' - starts_at is still a positive interval
+  CHECK (ends_at - starts_at >= make_interval(mins => slot_minutes)),
+  CHECK (until >= starts_on)
+);
+
+CREATE INDEX timeslot_rules_pitch_id_idx ON timeslot_rules (pitch_id);
+
+-- the occurrence a timeslot was generated for, it stays the same when the timeslot is moved so
+-- that the series doesn't generate it again
+ALTER TABLE timeslots
+  ADD COLUMN rule_id UUID REFERENCES timeslot_rules(id) ON DELETE SET NULL,
+  ADD COLUMN occurrence_starts_at TIMESTAMPTZ,
+  ADD UNIQUE (rule_id, occurrence_starts_at);
+
+-- occurrences whose timeslot was deleted on its own, so that the series doesn't generate them again
+CREATE TABLE cancelled_timeslot_occurrences (
+  rule_id UUID NOT NULL REFERENCES timeslot_rules(id) ON DELETE CASCADE,
+  occurrence_starts_at TIMESTAMPTZ NOT NULL,
+  PRIMARY KEY (rule_id, occurrence_starts_at)
+);
+
+-- Creates the missing timeslots of the rule that start before `horizon`, returns how many.
+--
+-- Idempotent: occurrences that already have a timeslot, or were cancelled, are left alone.
+-- Occurrences overlapping another timeslot, a confirmed closure or outside of the opening hours
+-- are skipped.
+CREATE FUNCTION materialize_timeslot_rule(target_rule_id UUID, horizon TIMESTAMPTZ)
+RETURNS INTEGER
+LANGUAGE plpgsql AS $$
+DECLARE
+  occurrence RECORD;
+  created INTEGER := 0;
+BEGIN
+  FOR occurrence IN
+    SELECT
+      timeslot_rules.pitch_id,
+      slots.local_start AT TIME ZONE pitches.timezone AS starts_at,
+      (slots.local_start + make_interval(mins => timeslot_rules.slot_minutes))
+        AT TIME ZONE pitches.timezone AS ends_at
+    FROM
+      timeslot_rules
+    JOIN
+      pitches
+    ON
+      pitches.id = timeslot_rules.pitch_id
+    CROSS JOIN LATERAL
+      generate_series(
+        GREATEST(timeslot_rules.starts_on, (NOW() AT TIME ZONE pitches.timezone)::DATE),
+        LEAST(timeslot_rules.until, (horizon AT TIME ZONE pitches.timezone)::DATE),
+        INTERVAL '1 day'
+      ) AS days (day)
+    CROSS JOIN LATERAL
+      generate_series(
+        day::DATE + timeslot_rules.starts_at,
+        day::DATE + timeslot_rules.ends_at - make_interval(mins => timeslot_rules.slot_minutes),
+        make_interval(mins => timeslot_rules.slot_minutes)
+      ) AS slots (local_start)
+    WHERE
+      timeslot_rules.id = target_rule_id
+    AND
+      (enum_range(NULL::weekday))[EXTRACT(ISODOW FROM day)::INTEGER] = ANY(timeslot_rules.weekdays)
+    ORDER BY
+      slots.local_start
+  LOOP
+    CONTINUE WHEN occurrence.starts_at <= NOW() OR occurrence.starts_at >= horizon;
+    CONTINUE WHEN EXISTS (
+      SELECT 1 FROM timeslots
+      WHERE rule_id = target_rule_id AND occurrence_starts_at = occurrence.starts_at
+    );
+    CONTINUE WHEN EXISTS (
+      SELECT 1 FROM cancelled_timeslot_occurrences
+      WHERE rule_id = target_rule_id AND occurrence_starts_at = occurrence.starts_at
+    );
+    CONTINUE WHEN EXISTS (
+      SELECT 1 FROM pitch_closures
+      WHERE pitch_id = occurrence.pitch_id
+      AND is_confirmed
+      AND period && TSTZRANGE(occurrence.starts_at, occurrence.ends_at)
+    );
+
+    BEGIN
+      INSERT INTO timeslots
+        (pitch_id, time_range, rule_id, occurrence_starts_at)
+      VALUES
+        (
+          occurrence.pitch_id,
+          TSTZRANGE(occurrence.starts_at, occurrence.ends_at),
+          target_rule_id,
+          occurrence.starts_at
+        );
+      created := created + 1;
+    EXCEPTION
+      -- taken by another timeslot, or outside of the opening hours, or by a concurrent run
+      WHEN exclusion_violation OR check_violation OR unique_violation THEN
+        NULL;
+    END;
+  END LOOP;
+
+  RETURN created;
+END;
+$$;
@@ -0,0 +1,11 @@
+-- Add down migration script here
+
+DROP TRIGGER timeslots_price_currency ON timeslots;
+
+DROP FUNCTION check_timeslot_price_currency();
+
+ALTER TABLE timeslots DROP COLUMN price;
+
+DROP TYPE price;
+
+ALTER TABLE pitches DROP COLUMN currency;
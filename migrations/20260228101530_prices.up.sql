@@ -0,0 +1,33 @@
+-- Add up migration script here
+
+-- the seeded pitches are all in the UAE
+ALTER TABLE pitches ADD COLUMN currency CHAR(3) NOT NULL DEFAULT 'AED';
+ALTER TABLE pitches ALTER COLUMN currency DROP DEFAULT;
+
+-- an amount in the minor unit of an ISO 4217 currency, e.g. fils for AED
+CREATE TYPE price AS (
+  amount_cents BIGINT,
+  currency CHAR(3)
+);
+
+-- overrides the hourly price of the pitch for this one timeslot
+ALTER TABLE timeslots
+  ADD COLUMN price price,
+  ADD CONSTRAINT timeslots_price_check CHECK ((price).amount_cents >= 0);
+
+CREATE FUNCTION check_timeslot_price_currency() RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+BEGIN
+  IF (NEW.price).currency IS DISTINCT FROM (SELECT currency FROM pitches WHERE id = NEW.pitch_id) THEN
+    RAISE EXCEPTION 'the price of a timeslot must be in the currency of its pitch'
+      USING ERRCODE = 'check_violation', CONSTRAINT = 'timeslots_price_currency';
+  END IF;
+  RETURN NEW;
+END;
+$$;
+
+CREATE TRIGGER timeslots_price_currency
+  BEFORE INSERT OR UPDATE OF price, pitch_id ON timeslots
+  FOR EACH ROW
+  WHEN (NEW.price IS NOT NULL)
+  EXECUTE FUNCTION check_timeslot_price_currency();
@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+ALTER TABLE bookings DROP COLUMN price;
+
+DROP FUNCTION pricing_rule_windows(UUID, TSTZRANGE);
+
+DROP TABLE pricing_rules;
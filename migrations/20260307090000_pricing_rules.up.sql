@@ -0,0 +1,96 @@
+-- Add up migration script here
+
+-- adjusts the hourly price of a pitch, e.g. +25% on weeknights from 18:00 or 80.00 before noon.
+-- times and dates are wall clock ones in the pitch's timezone, and when several rules match a
+-- moment the one with the highest priority wins.
+CREATE TABLE pricing_rules (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  pitch_id UUID NOT NULL REFERENCES pitches(id) ON DELETE CASCADE,
+  name VARCHAR(64) NOT NULL CHECK (name <> ''),
+  priority INTEGER NOT NULL,
+  weekdays weekday[] NOT NULL CHECK (cardinality(weekdays) > 0),
+  starts_at TIME NOT NULL,
+  ends_at TIME NOT NULL,
+  starts_on DATE,
+  until DATE,
+  -- minutes from the moment the price is computed (a quote or a booking) to the start of the
+  -- timeslot, e.g. a last minute discount only has a maximum
+  min_lead_minutes INTEGER CHECK (min_lead_minutes >= 0),
+  max_lead_minutes INTEGER CHECK (max_lead_minutes >= 0),
+  -- either replaces the hourly price of the pitch or adjusts it
+  price_per_hour_cents INTEGER CHECK (price_per_hour_cents >= 0),
+  adjustment_percent INTEGER CHECK (adjustment_percent BETWEEN -100 AND 1000),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  UNIQUE (pitch_id, priority),
+  CHECK (starts_at < ends_at),
+  CHECK (starts_on IS NULL OR until IS NULL OR starts_on <= until),
+  CHECK (min_lead_minutes IS NULL OR max_lead_minutes IS NULL OR min_lead_minutes <= max_lead_minutes),
+  CHECK (num_nonnulls(price_per_hour_cents, adjustment_percent) = 1)
+);
+
+-- the parts of `period` where each rule of the pitch applies, lead times aside
+CREATE FUNCTION pricing_rule_windows(pitch_id UUID, period TSTZRANGE)
+RETURNS TABLE (rule_id UUID, starts_at TIMESTAMPTZ, ends_at TIMESTAMPTZ)
+LANGUAGE sql STABLE AS $$
+  SELECT
+    pricing_rules.id,
+    GREATEST(windows.starts_at, LOWER(period)),
+    LEAST(windows.ends_at, UPPER(period))
+  FROM
+    pricing_rules
+  JOIN
+    pitches
+  ON
+    pitches.id = pricing_rules.pitch_id
+  CROSS JOIN LATERAL
+    generate_series(
+      (LOWER(period) AT TIME ZONE pitches.timezone)::DATE,
+      (UPPER(period) AT TIME ZONE pitches.timezone)::DATE,
+      INTERVAL '1 day'
+    ) AS days (day)
+  CROSS JOIN LATERAL (
+    SELECT
+      (day::DATE + pricing_rules.starts_at) AT TIME ZONE pitches.timezone AS starts_at,
+      (day::DATE + pricing_rules.ends_at) AT TIME ZONE pitches.timezone AS ends_at
+  ) AS windows
+  WHERE
+    pricing_rules.pitch_id = pricing_rule_windows.pitch_id
+  AND
+    (enum_range(NULL::weekday))[EXTRACT(ISODOW FROM day)::INTEGER] = ANY(pricing_rules.weekdays)
+  AND
+    (pricing_rules.starts_on IS NULL OR day::DATE >= pricing_rules.starts_on)
+  AND
+    (pricing_rules.until IS NULL OR day::DATE <= pricing_rules.until)
+  AND
+    windows.starts_at < UPPER(period)
+  AND
+    windows.ends_at > LOWER(period)
+$$;
+
+-- what the player paid, the pricing may change after the booking
+ALTER TABLE bookings ADD COLUMN price price;
+
+UPDATE
+  bookings
+SET
+  price = COALESCE(
+    timeslots.price,
+    ROW(
+      ROUND(
+        pitches.price_per_hour_cents
+        * EXTRACT(EPOCH FROM UPPER(timeslots.time_range) - LOWER(timeslots.time_range))
+        / 3600
+      )::BIGINT,
+      pitches.currency
+    )::price
+  )
+FROM
+  timeslots
+JOIN
+  pitches
+ON
+  pitches.id = timeslots.pitch_id
+WHERE
+  timeslots.id = bookings.timeslot_id;
+
+ALTER TABLE bookings ALTER COLUMN price SET NOT NULL;
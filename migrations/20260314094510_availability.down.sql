@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP FUNCTION pitch_availability;
+
+DROP TYPE availability_kind;
@@ -0,0 +1,171 @@
+-- Add up migration script here
+
+CREATE TYPE availability_kind AS ENUM ('free', 'booked', 'held', 'closed');
+
+-- what a pitch looks like between two local dates of `timezone`, cut at its midnights:
+-- - 'closed' outside the opening hours and during confirmed closures,
+-- - 'booked' for its booked timeslots,
+-- - 'held' while a booked timeslot of another pitch takes up one of its units (e.g. a full field
+--   over one of its halves),
+-- - 'free' for its upcoming free timeslots.
+-- an interval only keeps what the kinds before it in that list leave, so intervals never overlap
+CREATE FUNCTION pitch_availability(
+  target_pitch_id UUID,
+  from_date DATE,
+  to_date DATE,
+  timezone TEXT
+)
+RETURNS TABLE (date DATE, kind availability_kind, starts_at TIMESTAMPTZ, ends_at TIMESTAMPTZ)
+LANGUAGE SQL STABLE AS $$
+  WITH period AS (
+    SELECT
+      TSTZRANGE(
+        from_date::TIMESTAMP AT TIME ZONE timezone,
+        (to_date + 1)::TIMESTAMP AT TIME ZONE timezone
+      ) AS time_range
+  ), closed AS (
+    SELECT
+      tstzmultirange(period.time_range)
+      - COALESCE(
+        (
+          SELECT
+            range_agg(TSTZRANGE(hours.opens_at, hours.closes_at))
+          FROM
+            pitches
+          CROSS JOIN LATERAL
+            effective_opening_hours(
+              pitches.id,
+              (LOWER(period.time_range) AT TIME ZONE pitches.timezone)::DATE - 1,
+              (UPPER(period.time_range) AT TIME ZONE pitches.timezone)::DATE
+            ) hours
+          WHERE
+            pitches.id = target_pitch_id
+          AND
+            hours.opens_at IS NOT NULL
+        ),
+        '{}'
+      )
+      + COALESCE(
+        (
+          SELECT
+            range_agg(pitch_closures.period)
+          FROM
+            pitch_closures
+          WHERE
+            pitch_closures.pitch_id = target_pitch_id
+          AND
+            pitch_closures.is_confirmed
+          AND
+            pitch_closures.period && period.time_range
+        ),
+        '{}'
+      ) AS ranges
+    FROM
+      period
+  ), booked AS (
+    SELECT
+      COALESCE(
+        (
+          SELECT
+            range_agg(timeslots.time_range)
+          FROM
+            timeslots
+          WHERE
+            timeslots.pitch_id = target_pitch_id
+          AND
+            timeslots.is_booked
+          AND
+            timeslots.time_range && period.time_range
+        ),
+        '{}'
+      ) - closed.ranges AS ranges
+    FROM
+      period
+    CROSS JOIN
+      closed
+  ), held AS (
+    SELECT
+      COALESCE(
+        (
+          SELECT
+            range_agg(timeslot_occupancy.time_range)
+          FROM
+            timeslot_occupancy
+          JOIN
+            timeslots
+          ON
+            timeslots.id = timeslot_occupancy.timeslot_id
+          WHERE
+            timeslot_occupancy.unit_id IN (SELECT unit_id FROM pitch_units(target_pitch_id))
+          AND
+            timeslots.pitch_id <> target_pitch_id
+          AND
+            timeslots.is_booked
+          AND
+            timeslot_occupancy.time_range && period.time_range
+        ),
+        '{}'
+      ) - closed.ranges - booked.ranges AS ranges
+    FROM
+      period
+    CROSS JOIN
+      closed
+    CROSS JOIN
+      booked
+  ), free AS (
+    SELECT
+      COALESCE(
+        (
+          SELECT
+            range_agg(timeslots.time_range)
+          FROM
+            timeslots
+          WHERE
+            timeslots.pitch_id = target_pitch_id
+          AND
+            NOT timeslots.is_booked
+          AND
+            LOWER(timeslots.time_range) > NOW()
+          AND
+            timeslots.time_range && period.time_range
+        ),
+        '{}'
+      ) - closed.ranges - booked.ranges - held.ranges AS ranges
+    FROM
+      period
+    CROSS JOIN
+      closed
+    CROSS JOIN
+      booked
+    CROSS JOIN
+      held
+  ), intervals AS (
+    SELECT 'closed'::availability_kind AS kind, UNNEST(ranges) AS time_range FROM closed
+    UNION ALL
+    SELECT 'booked', UNNEST(ranges) FROM booked
+    UNION ALL
+    SELECT 'held', UNNEST(ranges) FROM held
+    UNION ALL
+    SELECT 'free', UNNEST(ranges) FROM free
+  ), days AS (
+    SELECT
+      day::DATE AS date,
+      TSTZRANGE(
+        day::TIMESTAMP AT TIME ZONE timezone,
+        (day::DATE + 1)::TIMESTAMP AT TIME ZONE timezone
+      ) AS time_range
+    FROM
+      generate_series(from_date, to_date, INTERVAL '1 day') day
+  )
+  SELECT
+    days.date,
+    intervals.kind,
+    LOWER(intervals.time_range * days.time_range),
+    UPPER(intervals.time_range * days.time_range)
+  FROM
+    days
+  JOIN
+    intervals
+  ON
+    intervals.time_range && days.time_range
+$$;
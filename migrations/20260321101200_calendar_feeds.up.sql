@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+-- secret links to the .ics feed of a user (the bookings of a business' pitches, or a player's own
+-- bookings) or of a single pitch. regenerating the token revokes the previous link
+CREATE TABLE calendar_feeds (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  user_id UUID UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+  pitch_id UUID UNIQUE REFERENCES pitches(id) ON DELETE CASCADE,
+  token VARCHAR(64) NOT NULL UNIQUE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK (num_nonnulls(user_id, pitch_id) = 1)
+);
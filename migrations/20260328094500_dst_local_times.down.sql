@@ -0,0 +1,79 @@
+-- Add down migration script here
+
+CREATE OR REPLACE FUNCTION materialize_timeslot_rule(target_rule_id UUID, horizon TIMESTAMPTZ)
+RETURNS INTEGER
+LANGUAGE plpgsql AS $$
+DECLARE
+  occurrence RECORD;
+  created INTEGER := 0;
+BEGIN
+  FOR occurrence IN
+    SELECT
+      timeslot_rules.pitch_id,
+      slots.local_start AT TIME ZONE pitches.timezone AS starts_at,
+      (slots.local_start + make_interval(mins => timeslot_rules.slot_minutes))
+        AT TIME ZONE pitches.timezone AS ends_at
+    FROM
+      timeslot_rules
+    JOIN
+      pitches
+    ON
+      pitches.id = timeslot_rules.pitch_id
+    CROSS JOIN LATERAL
+      generate_series(
+        GREATEST(timeslot_rules.starts_on, (NOW() AT TIME ZONE pitches.timezone)::DATE),
+        LEAST(timeslot_rules.until, (horizon AT TIME ZONE pitches.timezone)::DATE),
+        INTERVAL '1 day'
+      ) AS days (day)
+    CROSS JOIN LATERAL
+      generate_series(
+        day::DATE + timeslot_rules.starts_at,
+        day::DATE + timeslot_rules.ends_at - make_interval(mins => timeslot_rules.slot_minutes),
+        make_interval(mins => timeslot_rules.slot_minutes)
+      ) AS slots (local_start)
+    WHERE
+      timeslot_rules.id = target_rule_id
+    AND
+      (enum_range(NULL::weekday))[EXTRACT(ISODOW FROM day)::INTEGER] = ANY(timeslot_rules.weekdays)
+    ORDER BY
+      slots.local_start
+  LOOP
+    CONTINUE WHEN occurrence.starts_at <= NOW() OR occurrence.starts_at >= horizon;
+    CONTINUE WHEN EXISTS (
+      SELECT 1 FROM timeslots
+      WHERE rule_id = target_rule_id AND occurrence_starts_at = occurrence.starts_at
+    );
+    CONTINUE WHEN EXISTS (
+      SELECT 1 FROM cancelled_timeslot_occurrences
+      WHERE rule_id = target_rule_id AND occurrence_starts_at = occurrence.starts_at
+    );
+    CONTINUE WHEN EXISTS (
+      SELECT 1 FROM pitch_closures
+      WHERE pitch_id = occurrence.pitch_id
+      AND is_confirmed
+      AND period && TSTZRANGE(occurrence.starts_at, occurrence.ends_at)
+    );
+
+    BEGIN
+      INSERT INTO timeslots
+        (pitch_id, time_range, rule_id, occurrence_starts_at)
+      VALUES
+        (
+          occurrence.pitch_id,
+          TSTZRANGE(occurrence.starts_at, occurrence.ends_at),
+          target_rule_id,
+          occurrence.starts_at
+        );
+      created := created + 1;
+    EXCEPTION
+      -- taken by another timeslot, or outside of the opening hours, or by a concurrent run
+      WHEN exclusion_violation OR check_violation OR unique_violation THEN
+        NULL;
+    END;
+  END LOOP;
+
+  RETURN created;
+END;
+$$;
+
+DROP FUNCTION local_time_to_utc(TIMESTAMP, TEXT);
@@ -0,0 +1,117 @@
+-- Add up migration script here
+
+-- The instant a wall clock time of `timezone` stands for, as in RFC 5545: a time repeated when the
+-- clocks go back is its first occurrence, and a time skipped when they go forward is read with the
+-- offset from before the gap, e.g. 02:30 is 03:30 when the clocks jump from 02:00 to 03:00.
+--
+-- `AT TIME ZONE` alone takes the second occurrence of repeated times.
+CREATE FUNCTION local_time_to_utc(local TIMESTAMP, timezone TEXT)
+RETURNS TIMESTAMPTZ
+LANGUAGE SQL STABLE AS $$
+  SELECT
+    CASE
+      WHEN (earlier AT TIME ZONE timezone) = local THEN LEAST(earlier, later)
+      ELSE later
+    END
+  FROM
+    (SELECT local AT TIME ZONE timezone AS later) AS later
+  CROSS JOIN LATERAL
+    -- with the offset the timezone had a day before
+    (
+      SELECT
+        (
+          local
+          - ((later - INTERVAL '1 day') AT TIME ZONE timezone)
+          + ((later - INTERVAL '1 day') AT TIME ZONE 'UTC')
+        ) AT TIME ZONE 'UTC' AS earlier
+    ) AS earlier
+$$;
+
+-- Same as before, except on the days the clocks change: occurrences starting at a wall clock time
+-- that doesn't exist that day are skipped, repeated times are their first occurrence, and every
+-- timeslot lasts `slot_minutes` even when the clocks change during it.
+CREATE OR REPLACE FUNCTION materialize_timeslot_rule(target_rule_id UUID, horizon TIMESTAMPTZ)
+RETURNS INTEGER
+LANGUAGE plpgsql AS $$
+DECLARE
+  occurrence RECORD;
+  created INTEGER := 0;
+BEGIN
+  FOR occurrence IN
+    SELECT
+      occurrences.pitch_id,
+      occurrences.starts_at,
+      occurrences.starts_at + make_interval(mins => occurrences.slot_minutes) AS ends_at
+    FROM
+      (
+        SELECT
+          timeslot_rules.pitch_id,
+          timeslot_rules.slot_minutes,
+          pitches.timezone,
+          slots.local_start,
+          local_time_to_utc(slots.local_start, pitches.timezone) AS starts_at
+        FROM
+          timeslot_rules
+        JOIN
+          pitches
+        ON
+          pitches.id = timeslot_rules.pitch_id
+        CROSS JOIN LATERAL
+          generate_series(
+            GREATEST(timeslot_rules.starts_on, (NOW() AT TIME ZONE pitches.timezone)::DATE),
+            LEAST(timeslot_rules.until, (horizon AT TIME ZONE pitches.timezone)::DATE),
+            INTERVAL '1 day'
+          ) AS days (day)
+        CROSS JOIN LATERAL
+          generate_series(
+            day::DATE + timeslot_rules.starts_at,
+            day::DATE + timeslot_rules.ends_at - make_interval(mins => timeslot_rules.slot_minutes),
+            make_interval(mins => timeslot_rules.slot_minutes)
+          ) AS slots (local_start)
+        WHERE
+          timeslot_rules.id = target_rule_id
+        AND
+          (enum_range(NULL::weekday))[EXTRACT(ISODOW FROM day)::INTEGER] = ANY(timeslot_rules.weekdays)
+      ) AS occurrences
+    WHERE
+      (occurrences.starts_at AT TIME ZONE occurrences.timezone) = occurrences.local_start
+    ORDER BY
+      occurrences.starts_at
+  LOOP
+    CONTINUE WHEN occurrence.starts_at <= NOW() OR occurrence.starts_at >= horizon;
+    CONTINUE WHEN EXISTS (
+      SELECT 1 FROM timeslots
+      WHERE rule_id = target_rule_id AND occurrence_starts_at = occurrence.starts_at
+    );
+    CONTINUE WHEN EXISTS (
+      SELECT 1 FROM cancelled_timeslot_occurrences
+      WHERE rule_id = target_rule_id AND occurrence_starts_at = occurrence.starts_at
+    );
+    CONTINUE WHEN EXISTS (
+      SELECT 1 FROM pitch_closures
+      WHERE pitch_id = occurrence.pitch_id
+      AND is_confirmed
+      AND period && TSTZRANGE(occurrence.starts_at, occurrence.ends_at)
+    );
+
+    BEGIN
+      INSERT INTO timeslots
+        (pitch_id, time_range, rule_id, occurrence_starts_at)
+      VALUES
+        (
+          occurrence.pitch_id,
+          TSTZRANGE(occurrence.starts_at, occurrence.ends_at),
+          target_rule_id,
+          occurrence.starts_at
+        );
+      created := created + 1;
+    EXCEPTION
+      -- taken by another timeslot, or outside of the opening hours, or by a concurrent run
+      WHEN exclusion_violation OR check_violation OR unique_violation THEN
+        NULL;
+    END;
+  END LOOP;
+
+  RETURN created;
+END;
+$$;
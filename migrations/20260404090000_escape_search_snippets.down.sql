@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+DROP FUNCTION escape_html(TEXT);
@@ -0,0 +1,18 @@
+-- Add up migration script here
+
+-- `text` with the characters that are special in HTML replaced by entities, so that the `<mark>`
+-- tags added by `ts_headline` are the only markup in search snippets.
+CREATE FUNCTION escape_html(text TEXT)
+RETURNS TEXT
+LANGUAGE SQL IMMUTABLE STRICT AS $$
+  SELECT
+    replace(
+      replace(
+        replace(replace(replace(text, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'),
+        '"',
+        '&quot;'
+      ),
+      '''',
+      '&#39;'
+    )
+$$;
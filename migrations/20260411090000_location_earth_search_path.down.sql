@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+DROP INDEX locations_earth_idx;
+
+CREATE INDEX locations_earth_idx ON locations USING gist (ll_to_earth(latitude, longitude));
+
+DROP FUNCTION location_to_earth(DOUBLE PRECISION, DOUBLE PRECISION);
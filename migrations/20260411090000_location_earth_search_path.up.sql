@@ -0,0 +1,16 @@
+-- Add up migration script here
+
+-- `ll_to_earth` resolves `cube` and `earth` through the search_path when it's inlined, so indexing
+-- it directly breaks whenever another search_path is in use, e.g. in autovacuum's analyze or in a
+-- restore. This wrapper pins the search_path to the schema of the extensions.
+CREATE FUNCTION location_to_earth(latitude DOUBLE PRECISION, longitude DOUBLE PRECISION)
+RETURNS earth
+LANGUAGE SQL IMMUTABLE STRICT PARALLEL SAFE
+SET search_path = public AS $$
+  SELECT ll_to_earth(latitude, longitude)
+$$;
+
+DROP INDEX locations_earth_idx;
+
+-- used by radius searches (earth_box), which must use the same expression
+CREATE INDEX locations_earth_idx ON locations USING gist (location_to_earth(latitude, longitude));
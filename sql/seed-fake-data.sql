@@ -43,17 +43,53 @@ VALUES
   ((SELECT id FROM users WHERE username = 'player_1'), 'player_1_first', 'player_1_last', '{football}'),
   ((SELECT id FROM users WHERE username = 'player_2'), 'player_2_first', 'player_2_last', '{basketball, padel}');
 
+INSERT INTO player_privacy_settings (user_id, name_visibility, member_since_visibility, total_bookings_visibility, favorite_sport_visibility)
+VALUES
+  ((SELECT id FROM users WHERE username = 'player_1'), 'public', 'public', 'public', 'public'),
+  ((SELECT id FROM users WHERE username = 'player_2'), 'friends_only', 'public', 'public', 'hidden');
+
+INSERT INTO player_sport_ratings (player_id, sport, self_declared_level, system_rating, confidence)
+VALUES
+  ((SELECT id FROM users WHERE username = 'player_1'), 'football', 6, 5.5, 0.25);
+
 INSERT INTO business_profiles (user_id, display_name)
 VALUES
   ((SELECT id FROM users WHERE username = 'business_1'), 'business_1_display'),
   ((SELECT id FROM users WHERE username = 'business_2'), 'business_2_display');
 
-INSERT INTO pitches (id, owner_id, display_name, sport)
+UPDATE users SET avatar_url = 'https://example.com/avatars/business_1.png' WHERE username = 'business_1';
+
+INSERT INTO locations (id, address_line_1, address_line_2, city, postal_code, country, latitude, longitude)
+VALUES
+  ('00000000-0000-0000-0000-000000000020', 'Al Majaz Waterfront', NULL, 'Sharjah', NULL, 'UAE', 25.3275, 55.3862),
+  ('00000000-0000-0000-0000-000000000021', 'University City Road', 'Gate 3', 'Sharjah', NULL, 'UAE', 25.2866, 55.4799),
+  ('00000000-0000-0000-0000-000000000022', 'Al Wasl Road', NULL, 'Dubai', '00000', 'UAE', 25.1972, 55.2744),
+  ('00000000-0000-0000-0000-000000000023', 'Corniche Road', NULL, 'Abu Dhabi', NULL, 'UAE', 24.4764, 54.3705);
+
+INSERT INTO pitches (id, owner_id, display_name, sport, price_per_hour_cents, currency, image_url, surface, environment, description, max_players, location_id, timezone)
+VALUES
+  ('00000000-0000-0000-0000-000000000006', (SELECT id FROM users WHERE username = 'business_1'), 'football_pitch_1', 'football', 25000, 'AED', 'https://example.com/pitches/football_pitch_1.png', 'artificial_turf', 'outdoor', 'five-a-side pitch next to the lake', 10, '00000000-0000-0000-0000-000000000020', 'Asia/Dubai'),
+  ('00000000-0000-0000-0000-000000000007', (SELECT id FROM users WHERE username = 'business_1'), 'football_pitch_2', 'football', 20000, 'AED', NULL, 'grass', 'outdoor', NULL, 22, '00000000-0000-0000-0000-000000000021', 'Asia/Dubai'),
+  ('00000000-0000-0000-0000-000000000008', (SELECT id FROM users WHERE username = 'business_2'), 'basketball_pitch_1', 'basketball', 12050, 'AED', NULL, 'wood', 'indoor', NULL, 10, '00000000-0000-0000-0000-000000000022', 'Asia/Dubai'),
+  ('00000000-0000-0000-0000-000000000009', (SELECT id FROM users WHERE username = 'business_2'), 'padel_pitch_1', 'padel', 18000, 'AED', NULL, NULL, NULL, NULL, NULL, '00000000-0000-0000-0000-000000000023', 'Asia/Dubai');
+
+INSERT INTO pitch_amenities (pitch_id, amenity_id)
+VALUES
+  ((SELECT id FROM pitches WHERE display_name = 'football_pitch_1'), (SELECT id FROM amenities WHERE name = 'lights')),
+  ((SELECT id FROM pitches WHERE display_name = 'football_pitch_1'), (SELECT id FROM amenities WHERE name = 'water')),
+  ((SELECT id FROM pitches WHERE display_name = 'basketball_pitch_1'), (SELECT id FROM amenities WHERE name = 'parking'));
+
+INSERT INTO pitch_reviews (pitch_id, author_id, rating, comment)
+VALUES
+  ((SELECT id FROM pitches WHERE display_name = 'football_pitch_1'), (SELECT id FROM users WHERE username = 'player_1'), 4, 'good pitch'),
+  ((SELECT id FROM pitches WHERE display_name = 'football_pitch_1'), (SELECT id FROM users WHERE username = 'player_2'), 5, NULL),
+  ((SELECT id FROM pitches WHERE display_name = 'basketball_pitch_1'), (SELECT id FROM users WHERE username = 'player_2'), 2, 'slippery floor');
+
+INSERT INTO favorite_pitches (player_id, pitch_id, created_at)
 VALUES
-  ('00000000-0000-0000-0000-000000000006', (SELECT id FROM users WHERE username = 'business_1'), 'football_pitch_1', 'football'),
-  ('00000000-0000-0000-0000-000000000007', (SELECT id FROM users WHERE username = 'business_1'), 'football_pitch_2', 'football'),
-  ('00000000-0000-0000-0000-000000000008', (SELECT id FROM users WHERE username = 'business_2'), 'basketball_pitch_1', 'basketball'),
-  ('00000000-0000-0000-0000-000000000009', (SELECT id FROM users WHERE username = 'business_2'), 'padel_pitch_1', 'padel');
+  ((SELECT id FROM users WHERE username = 'player_1'), (SELECT id FROM pitches WHERE display_name = 'football_pitch_2'), '2025-03-01 12:00+04'),
+  ((SELECT id FROM users WHERE username = 'player_1'), (SELECT id FROM pitches WHERE display_name = 'football_pitch_1'), '2025-03-02 12:00+04'),
+  ((SELECT id FROM users WHERE username = 'player_2'), (SELECT id FROM pitches WHERE display_name = 'football_pitch_1'), '2025-03-03 12:00+04');
 
 INSERT INTO timeslots (id, pitch_id, time_range, is_booked)
 VALUES
@@ -62,7 +98,7 @@ VALUES
   ('00000000-0000-0000-0000-000000000012', (SELECT id FROM pitches WHERE display_name = 'football_pitch_1'), tstzrange('2025-03-20 17:00+04', '2025-03-20 18:00+04'), TRUE),
   ('00000000-0000-0000-0000-000000000013', (SELECT id FROM pitches WHERE display_name = 'basketball_pitch_1'), tstzrange('2025-03-20 17:00+04', '2025-03-20 18:00+04'), TRUE);
 
-INSERT INTO bookings (id, timeslot_id, booked_by)
+INSERT INTO bookings (id, timeslot_id, booked_by, price)
 VALUES
-  ('00000000-0000-0000-0000-000000000014', (SELECT id from timeslots WHERE is_booked ORDER BY id OFFSET 0 LIMIT 1), (SELECT id FROM users WHERE username = 'player_1')),
-  ('00000000-0000-0000-0000-000000000015', (SELECT id from timeslots WHERE is_booked ORDER BY id OFFSET 1 LIMIT 1), (SELECT id FROM users WHERE username = 'player_2'));
+  ('00000000-0000-0000-0000-000000000014', (SELECT id from timeslots WHERE is_booked ORDER BY id OFFSET 0 LIMIT 1), (SELECT id FROM users WHERE username = 'player_1'), ROW(25000, 'AED')),
+  ('00000000-0000-0000-0000-000000000015', (SELECT id from timeslots WHERE is_booked ORDER BY id OFFSET 1 LIMIT 1), (SELECT id FROM users WHERE username = 'player_2'), ROW(12050, 'AED'));
@@ -2,7 +2,15 @@
 
 DELETE FROM bookings;
 DELETE FROM timeslots;
+DELETE FROM favorite_pitches;
+DELETE FROM pitch_reviews;
+DELETE FROM pitch_amenities;
+DELETE FROM pitch_admin_actions;
 DELETE FROM pitches;
+DELETE FROM locations;
 DELETE FROM business_profiles;
+DELETE FROM player_privacy_settings;
+DELETE FROM player_sport_ratings;
+DELETE FROM notifications;
 DELETE FROM player_profiles;
 DELETE FROM users;
@@ -11,6 +11,22 @@ pub struct Config {
     pub auth_token_exp_duration: Duration,
     pub auth_token_key: Vec<u8>,
     pub password_key: Vec<u8>,
+    pub relevance_weights: RelevanceWeights,
+    /// how far ahead the timeslots of recurring rules are generated
+    pub timeslot_rules_horizon: Duration,
+    /// how often the horizon is rolled forward
+    pub timeslot_rules_refresh_interval: Duration,
+}
+
+/// How much each part of the relevance score of a pitch counts, see the `relevance` module of the
+/// pitch controller
+pub struct RelevanceWeights {
+    pub sport: f64,
+    pub distance: f64,
+    pub price: f64,
+    pub rating: f64,
+    pub availability: f64,
+    pub popularity: f64,
 }
 
 impl Config {
@@ -25,6 +41,21 @@ impl Config {
             auth_token_key: read_env_var("AUTH_TOKEN_KEY").into_bytes(),
             // TODO: consider using base64_url::decode instead of into_bytes
             password_key: read_env_var("PASSWORD_KEY").into_bytes(),
+            relevance_weights: RelevanceWeights {
+                sport: read_env_var_parsed_or("RELEVANCE_WEIGHT_SPORT", 3.0),
+                distance: read_env_var_parsed_or("RELEVANCE_WEIGHT_DISTANCE", 2.0),
+                price: read_env_var_parsed_or("RELEVANCE_WEIGHT_PRICE", 1.0),
+                rating: read_env_var_parsed_or("RELEVANCE_WEIGHT_RATING", 2.0),
+                availability: read_env_var_parsed_or("RELEVANCE_WEIGHT_AVAILABILITY", 1.0),
+                popularity: read_env_var_parsed_or("RELEVANCE_WEIGHT_POPULARITY", 1.0),
+            },
+            timeslot_rules_horizon: Duration::from_secs(
+                read_env_var_parsed_or("TIMESLOT_RULES_HORIZON_DAYS", 28) * 24 * 60 * 60,
+            ),
+            timeslot_rules_refresh_interval: Duration::from_secs(read_env_var_parsed_or(
+                "TIMESLOT_RULES_REFRESH_INTERVAL_SEC",
+                60 * 60,
+            )),
         }
     }
 }
@@ -39,3 +70,11 @@ fn read_env_var_parsed<T: FromStr>(env_var_name: &str) -> T {
         .parse()
         .unwrap_or_else(|_| panic!("failed to parse {env_var_name}"))
 }
+
+/// For optional configs, falls back to `default` when the env var isn't set
+fn read_env_var_parsed_or<T: FromStr>(env_var_name: &str, default: T) -> T {
+    match std::env::var(env_var_name) {
+        Ok(_) => read_env_var_parsed(env_var_name),
+        Err(_) => default,
+    }
+}
@@ -0,0 +1,471 @@
+use crate::{
+    controllers::{
+        PitchActor,
+        availability::{
+            errors::AvailabilityControllerError,
+            models::{AvailabilityDay, AvailabilityInterval, PitchAvailability},
+        },
+    },
+    models::{
+        ModelManager,
+        local_time::with_local_offset,
+        tables::{AvailabilityKind, PitchStatus},
+    },
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub struct AvailabilityController;
+
+#[derive(FromRow)]
+struct AvailabilityRow {
+    date: NaiveDate,
+    kind: AvailabilityKind,
+    starts_at: DateTime<Utc>,
+    local_starts_at: NaiveDateTime,
+    ends_at: DateTime<Utc>,
+    local_ends_at: NaiveDateTime,
+}
+
+impl AvailabilityController {
+    /// Returns the free, booked, held and closed intervals of each day between the two dates,
+    /// included, cut at the midnights of `timezone` (the pitch's own one by default).
+    ///
+    /// Like its timeslots, the pitch is only visible to its owner and admins until it's published.
+    pub async fn get_availability(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        viewer: Option<PitchActor>,
+        from: NaiveDate,
+        to: NaiveDate,
+        timezone: Option<String>,
+    ) -> Result<PitchAvailability, AvailabilityControllerError> {
+        let (owner_id, status, timezone, is_known_timezone): (Uuid, PitchStatus, String, bool) =
+            sqlx::query_as(
+                r#"
+                SELECT
+                    owner_id,
+                    status,
+                    COALESCE($2, timezone),
+                    EXISTS (
+                        SELECT 1 FROM pg_timezone_names WHERE name = COALESCE($2, timezone)
+                    )
+                FROM
+                    pitches
+                WHERE
+                    id = $1
+                "#,
+            )
+            .bind(pitch_id)
+            .bind(timezone)
+            .fetch_optional(model_manager.db())
+            .await
+            .map_err(AvailabilityControllerError::Sqlx)?
+            .ok_or(AvailabilityControllerError::PitchNotFound)?;
+
+        let can_see = match viewer {
+            Some(PitchActor::Admin(_)) => true,
+            Some(PitchActor::Owner(viewer_id)) => viewer_id == owner_id,
+            None => status == PitchStatus::Published,
+        };
+        if !can_see {
+            return Err(AvailabilityControllerError::PitchNotFound);
+        }
+        if !is_known_timezone {
+            return Err(AvailabilityControllerError::UnknownTimezone);
+        }
+
+        let rows: Vec<AvailabilityRow> = sqlx::query_as(
+            r#"
+            SELECT
+                date,
+                kind,
+                starts_at,
+                starts_at AT TIME ZONE $4 AS local_starts_at,
+                ends_at,
+                ends_at AT TIME ZONE $4 AS local_ends_at
+            FROM
+                pitch_availability($1, $2, $3, $4)
+            ORDER BY
+                date, starts_at
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(from)
+        .bind(to)
+        .bind(&timezone)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(AvailabilityControllerError::Sqlx)?;
+
+        let mut days: Vec<AvailabilityDay> = from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| AvailabilityDay {
+                date,
+                intervals: Vec::new(),
+            })
+            .collect();
+
+        for row in rows {
+            let Some(day) = days.iter_mut().find(|day| day.date == row.date) else {
+                continue;
+            };
+            day.intervals.push(AvailabilityInterval {
+                kind: row.kind,
+                starts_at: with_local_offset(row.starts_at, row.local_starts_at),
+                ends_at: with_local_offset(row.ends_at, row.local_ends_at),
+            });
+        }
+
+        let etag = etag(&timezone, &days);
+
+        Ok(PitchAvailability {
+            timezone,
+            days,
+            etag,
+        })
+    }
+}
+
+/// Digest of everything the availability shows, so it only changes when the intervals do
+fn etag(timezone: &str, days: &[AvailabilityDay]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(timezone);
+    for day in days {
+        hasher.update(day.date.to_string());
+        for interval in &day.intervals {
+            hasher.update([interval.kind as u8]);
+            hasher.update(interval.starts_at.to_rfc3339());
+            hasher.update(interval.ends_at.to_rfc3339());
+        }
+    }
+
+    URL_SAFE_NO_PAD.encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            InsertPitchPayload, InsertTimeslotPayload, OpeningHours, OpeningHoursController,
+            PitchActor, TimeslotController, WeeklyOpeningHours,
+            availability::{controller::AvailabilityController, models::AvailabilityDay},
+            test_utils::{self, delete_business, insert_business, pitch_payload},
+        },
+        models::{
+            ModelManager,
+            tables::{AvailabilityKind, PitchStatus, Weekday},
+        },
+    };
+    use anyhow::Context;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use rand::distr::{Alphanumeric, SampleString};
+    use uuid::Uuid;
+
+    /// Returns the id of a new pitch of the business in Dubai, open every day from 08:00 to 22:00
+    async fn insert_pitch(model_manager: &ModelManager, business_id: Uuid) -> anyhow::Result<Uuid> {
+        let pitch = InsertPitchPayload {
+            timezone: "Asia/Dubai".to_string(),
+            ..pitch_payload(
+                &Alphanumeric.sample_string(&mut rand::rng(), 16),
+                PitchStatus::Published,
+            )?
+        };
+        let pitch_id = test_utils::insert_pitch(model_manager, business_id, pitch).await?;
+
+        let weekdays = [
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday,
+        ];
+        let mut weekly = Vec::new();
+        for weekday in weekdays {
+            weekly.push(WeeklyOpeningHours {
+                weekday,
+                opens_at: "08:00".parse()?,
+                closes_at: "22:00".parse()?,
+            });
+        }
+        let opening_hours = OpeningHours {
+            timezone: "Asia/Dubai".to_string(),
+            weekly,
+            exceptions: Vec::new(),
+        };
+        let actor = PitchActor::Owner(business_id);
+        OpeningHoursController::set_opening_hours(model_manager, pitch_id, actor, opening_hours)
+            .await
+            .context("failed while setting opening hours")?;
+
+        Ok(pitch_id)
+    }
+
+    /// Returns the id of a timeslot of the pitch, booked without a booking to keep it short
+    async fn insert_timeslot(
+        model_manager: &ModelManager,
+        business_id: Uuid,
+        pitch_id: Uuid,
+        starts_at: &str,
+        ends_at: &str,
+        is_booked: bool,
+    ) -> anyhow::Result<Uuid> {
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at: starts_at.parse()?,
+            ends_at: ends_at.parse()?,
+            price: None,
+        };
+        let timeslot_id = TimeslotController::create_timeslot(
+            model_manager,
+            PitchActor::Owner(business_id),
+            timeslot,
+        )
+        .await
+        .context("failed while creating timeslot")?;
+        sqlx::query("UPDATE timeslots SET is_booked = $2 WHERE id = $1")
+            .bind(timeslot_id)
+            .bind(is_booked)
+            .execute(model_manager.db())
+            .await
+            .context("failed while booking timeslot")?;
+
+        Ok(timeslot_id)
+    }
+
+    fn intervals(day: &AvailabilityDay) -> Vec<(AvailabilityKind, String, String)> {
+        day.intervals
+            .iter()
+            .map(|interval| {
+                (
+                    interval.kind,
+                    interval.starts_at.to_rfc3339(),
+                    interval.ends_at.to_rfc3339(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_get_availability_ok_merges_timeslots_closures_and_opening_hours()
+    -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let business_id = insert_business(&model_manager).await?;
+        let pitch_id = insert_pitch(&model_manager, business_id).await?;
+        for (starts_at, ends_at, is_booked) in [
+            (
+                "2030-01-07T10:00:00+04:00",
+                "2030-01-07T11:00:00+04:00",
+                false,
+            ),
+            (
+                "2030-01-07T11:00:00+04:00",
+                "2030-01-07T12:00:00+04:00",
+                false,
+            ),
+            (
+                "2030-01-07T14:00:00+04:00",
+                "2030-01-07T15:00:00+04:00",
+                true,
+            ),
+        ] {
+            insert_timeslot(
+                &model_manager,
+                business_id,
+                pitch_id,
+                starts_at,
+                ends_at,
+                is_booked,
+            )
+            .await?;
+        }
+        let closure_starts_at: DateTime<Utc> = "2030-01-08T12:00:00+04:00".parse()?;
+        sqlx::query(
+            r#"
+            INSERT INTO pitch_closures
+                (pitch_id, period, reason, is_confirmed)
+            VALUES
+                ($1, TSTZRANGE($2, $2 + INTERVAL '1 hour'), 'maintenance', TRUE)
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(closure_starts_at)
+        .execute(model_manager.db())
+        .await
+        .context("failed while inserting closure")?;
+        let from = NaiveDate::from_ymd_opt(2030, 1, 7).context("invalid date")?;
+        let to = NaiveDate::from_ymd_opt(2030, 1, 8).context("invalid date")?;
+
+        // exec
+        let availability = AvailabilityController::get_availability(
+            &model_manager,
+            pitch_id,
+            None,
+            from,
+            to,
+            None,
+        )
+        .await
+        .context("failed while getting availability")?;
+
+        // check
+        assert_eq!(availability.timezone, "Asia/Dubai");
+        assert_eq!(availability.days.len(), 2);
+        let closed = |from: &str, to: &str| (AvailabilityKind::Closed, from.into(), to.into());
+        assert_eq!(
+            intervals(&availability.days[0]),
+            [
+                closed("2030-01-07T00:00:00+04:00", "2030-01-07T08:00:00+04:00"),
+                (
+                    AvailabilityKind::Free,
+                    "2030-01-07T10:00:00+04:00".into(),
+                    "2030-01-07T12:00:00+04:00".into()
+                ),
+                (
+                    AvailabilityKind::Booked,
+                    "2030-01-07T14:00:00+04:00".into(),
+                    "2030-01-07T15:00:00+04:00".into()
+                ),
+                closed("2030-01-07T22:00:00+04:00", "2030-01-08T00:00:00+04:00"),
+            ]
+        );
+        assert_eq!(
+            intervals(&availability.days[1]),
+            [
+                closed("2030-01-08T00:00:00+04:00", "2030-01-08T08:00:00+04:00"),
+                closed("2030-01-08T12:00:00+04:00", "2030-01-08T13:00:00+04:00"),
+                closed("2030-01-08T22:00:00+04:00", "2030-01-09T00:00:00+04:00"),
+            ]
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_availability_ok_held_by_combined_pitch_in_another_timezone()
+    -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let business_id = insert_business(&model_manager).await?;
+        let half_id = insert_pitch(&model_manager, business_id).await?;
+        let full_id = insert_pitch(&model_manager, business_id).await?;
+        sqlx::query("INSERT INTO pitch_components (pitch_id, component_id) VALUES ($1, $2)")
+            .bind(full_id)
+            .bind(half_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while combining pitches")?;
+        insert_timeslot(
+            &model_manager,
+            business_id,
+            full_id,
+            "2030-01-07T18:00:00+04:00",
+            "2030-01-07T20:00:00+04:00",
+            true,
+        )
+        .await?;
+        let date = NaiveDate::from_ymd_opt(2030, 1, 7).context("invalid date")?;
+
+        // exec
+        let availability = AvailabilityController::get_availability(
+            &model_manager,
+            half_id,
+            None,
+            date,
+            date,
+            Some("UTC".to_string()),
+        )
+        .await
+        .context("failed while getting availability")?;
+
+        // check
+        assert_eq!(
+            intervals(&availability.days[0]),
+            [
+                (
+                    AvailabilityKind::Closed,
+                    "2030-01-07T00:00:00+00:00".into(),
+                    "2030-01-07T04:00:00+00:00".into()
+                ),
+                (
+                    AvailabilityKind::Held,
+                    "2030-01-07T14:00:00+00:00".into(),
+                    "2030-01-07T16:00:00+00:00".into()
+                ),
+                (
+                    AvailabilityKind::Closed,
+                    "2030-01-07T18:00:00+00:00".into(),
+                    "2030-01-08T00:00:00+00:00".into()
+                ),
+            ]
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_availability_ok_etag_changes_with_bookings() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let business_id = insert_business(&model_manager).await?;
+        let pitch_id = insert_pitch(&model_manager, business_id).await?;
+        let timeslot_id = insert_timeslot(
+            &model_manager,
+            business_id,
+            pitch_id,
+            "2030-01-07T10:00:00+04:00",
+            "2030-01-07T11:00:00+04:00",
+            false,
+        )
+        .await?;
+        let date = NaiveDate::from_ymd_opt(2030, 1, 7).context("invalid date")?;
+        let get_etag = async || -> anyhow::Result<String> {
+            let availability = AvailabilityController::get_availability(
+                &model_manager,
+                pitch_id,
+                None,
+                date,
+                date,
+                None,
+            )
+            .await
+            .context("failed while getting availability")?;
+            Ok(availability.etag)
+        };
+
+        // exec
+        let first_etag = get_etag().await?;
+        let unchanged_etag = get_etag().await?;
+        sqlx::query("UPDATE timeslots SET is_booked = TRUE WHERE id = $1")
+            .bind(timeslot_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while booking timeslot")?;
+        let booked_etag = get_etag().await?;
+
+        // check
+        assert_eq!(first_etag, unchanged_etag);
+        assert_ne!(first_etag, booked_etag);
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum AvailabilityControllerError {
+    PitchNotFound,
+    UnknownTimezone,
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(AvailabilityControllerError);
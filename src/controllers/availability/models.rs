@@ -0,0 +1,26 @@
+use crate::models::tables::AvailabilityKind;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+
+#[cfg_attr(test, derive(Debug))]
+pub struct PitchAvailability {
+    /// IANA name the days are cut in
+    pub timezone: String,
+    pub days: Vec<AvailabilityDay>,
+    /// changes with the intervals, e.g. when a timeslot is booked, moved or deleted
+    pub etag: String,
+}
+
+/// Intervals are ordered and don't overlap, the gaps are open hours without timeslots
+#[cfg_attr(test, derive(Debug))]
+pub struct AvailabilityDay {
+    pub date: NaiveDate,
+    pub intervals: Vec<AvailabilityInterval>,
+}
+
+/// With the offset of the requested timezone at that time
+#[cfg_attr(test, derive(Debug))]
+pub struct AvailabilityInterval {
+    pub kind: AvailabilityKind,
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+}
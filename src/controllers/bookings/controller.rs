@@ -0,0 +1,243 @@
+use crate::{
+    controllers::{
+        PricedPeriod, PricingRuleController,
+        bookings::{errors::BookingControllerError, models::Booking},
+    },
+    models::{ModelManager, price::Price},
+};
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub struct BookingController;
+
+impl BookingController {
+    /// Books an upcoming timeslot of a published pitch for the player.
+    ///
+    /// The timeslot is priced at booking time, from its custom price or the pricing rules of the
+    /// pitch, and the booking keeps that price. Two players racing for a timeslot can't both get
+    /// it, the second one finds it booked.
+    pub async fn book_timeslot(
+        model_manager: &ModelManager,
+        player_id: Uuid,
+        timeslot_id: Uuid,
+    ) -> Result<Booking, BookingControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(BookingControllerError::Sqlx)?;
+
+        let timeslot: Option<BookedTimeslot> = sqlx::query_as(
+            r#"
+            UPDATE
+                timeslots
+            SET
+                is_booked = TRUE
+            WHERE
+                id = $1
+            AND
+                NOT is_booked
+            AND
+                LOWER(time_range) > NOW()
+            AND
+                EXISTS (SELECT 1 FROM bookable_timeslots WHERE bookable_timeslots.id = timeslots.id)
+            RETURNING
+                pitch_id,
+                LOWER(time_range) AS starts_at,
+                UPPER(time_range) AS ends_at,
+                price AS custom_price
+            "#,
+        )
+        .bind(timeslot_id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(BookingControllerError::Sqlx)?;
+
+        let Some(timeslot) = timeslot else {
+            let is_booked: Option<bool> = sqlx::query_scalar(
+                r#"
+                SELECT
+                    timeslots.is_booked
+                FROM
+                    timeslots
+                JOIN
+                    pitches
+                ON
+                    pitches.id = timeslots.pitch_id
+                WHERE
+                    timeslots.id = $1
+                AND
+                    pitches.status = 'published'
+                "#,
+            )
+            .bind(timeslot_id)
+            .fetch_optional(&mut *transaction)
+            .await
+            .map_err(BookingControllerError::Sqlx)?;
+
+            return Err(match is_booked {
+                None => BookingControllerError::TimeslotNotFound,
+                Some(true) => BookingControllerError::TimeslotIsBooked,
+                Some(false) => BookingControllerError::TimeslotNotBookable,
+            });
+        };
+
+        let price = match timeslot.custom_price {
+            Some(price) => price,
+            None => {
+                let period = PricedPeriod {
+                    pitch_id: timeslot.pitch_id,
+                    starts_at: timeslot.starts_at,
+                    ends_at: timeslot.ends_at,
+                };
+                PricingRuleController::quote_periods(&mut transaction, &[period], Utc::now())
+                    .await
+                    .map_err(BookingControllerError::Pricing)?
+                    .pop()
+                    .ok_or(BookingControllerError::TimeslotNotFound)?
+                    .price
+            }
+        };
+
+        let result = sqlx::query_scalar(
+            r#"
+            INSERT INTO bookings
+                (timeslot_id, booked_by, price)
+            VALUES
+                ($1, $2, $3)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(timeslot_id)
+        .bind(player_id)
+        .bind(price)
+        .fetch_one(&mut *transaction)
+        .await;
+
+        let id = match result {
+            Ok(id) => id,
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("bookings_timeslot_id_key") =>
+            {
+                return Err(BookingControllerError::TimeslotIsBooked);
+            }
+            Err(err) => return Err(BookingControllerError::Sqlx(err)),
+        };
+
+        transaction
+            .commit()
+            .await
+            .map_err(BookingControllerError::Sqlx)?;
+
+        Ok(Booking { id, price })
+    }
+}
+
+#[derive(FromRow)]
+struct BookedTimeslot {
+    pitch_id: Uuid,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    custom_price: Option<Price>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            InsertPricingRulePayload, InsertTimeslotPayload, PitchActor, PriceAdjustment,
+            PricingRuleController, TimeslotController,
+            bookings::{controller::BookingController, errors::BookingControllerError},
+            test_utils::{delete_users, insert_business_with_pitch, insert_player},
+        },
+        models::{
+            ModelManager,
+            price::Price,
+            tables::{PitchStatus, Weekday},
+        },
+    };
+    use anyhow::Context;
+    use chrono::{Duration, NaiveTime, Utc};
+
+    #[tokio::test]
+    async fn test_book_timeslot_ok_keeps_the_price_it_was_sold_at() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "booked_pitch", PitchStatus::Published)
+                .await?;
+        let player_id = insert_player(&model_manager).await?;
+        let actor = PitchActor::Owner(business_id);
+        let peak = InsertPricingRulePayload {
+            name: "peak".to_string(),
+            priority: 1,
+            weekdays: vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+                Weekday::Saturday,
+                Weekday::Sunday,
+            ],
+            starts_at: "18:00".parse()?,
+            ends_at: "22:00".parse()?,
+            starts_on: None,
+            until: None,
+            min_lead_minutes: None,
+            max_lead_minutes: None,
+            adjustment: PriceAdjustment::Percent(50),
+        };
+        let peak_id = PricingRuleController::create_rule(&model_manager, pitch_id, actor, peak)
+            .await
+            .context("failed while creating peak rule")?;
+        let starts_at = (Utc::now().date_naive() + Duration::days(1))
+            .and_time(NaiveTime::MIN)
+            .and_utc()
+            + Duration::hours(18);
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::hours(1),
+            price: None,
+        };
+        let timeslot_id = TimeslotController::create_timeslot(&model_manager, actor, timeslot)
+            .await
+            .context("failed while creating timeslot")?;
+
+        // exec
+        let booking = BookingController::book_timeslot(&model_manager, player_id, timeslot_id)
+            .await
+            .context("failed while booking")?;
+        PricingRuleController::delete_rule_by_id(&model_manager, pitch_id, peak_id, actor)
+            .await
+            .context("failed while deleting peak rule")?;
+        let second_booking =
+            BookingController::book_timeslot(&model_manager, player_id, timeslot_id).await;
+
+        // check
+        let aed = "AED".parse()?;
+        assert_eq!(booking.price, Price::new(15000, aed));
+        assert!(
+            matches!(
+                second_booking,
+                Err(BookingControllerError::TimeslotIsBooked)
+            ),
+            "second_booking: {second_booking:?}"
+        );
+        let stored_price: Price = sqlx::query_scalar("SELECT price FROM bookings WHERE id = $1")
+            .bind(booking.id)
+            .fetch_one(model_manager.db())
+            .await
+            .context("failed while fetching booking")?;
+        assert_eq!(stored_price, Price::new(15000, aed));
+
+        // clean
+        delete_users(&model_manager, &[player_id, business_id]).await?;
+
+        Ok(())
+    }
+}
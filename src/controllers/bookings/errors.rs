@@ -0,0 +1,13 @@
+use crate::{controllers::PricingRuleControllerError, errors::error_impl};
+
+#[derive(Debug)]
+pub enum BookingControllerError {
+    TimeslotNotFound,
+    TimeslotIsBooked,
+    /// the timeslot already started or its pitch is closed then
+    TimeslotNotBookable,
+    Pricing(PricingRuleControllerError),
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(BookingControllerError);
@@ -0,0 +1,9 @@
+use crate::models::price::Price;
+use uuid::Uuid;
+
+#[cfg_attr(test, derive(Debug))]
+pub struct Booking {
+    pub id: Uuid,
+    /// what the player pays, kept when the pricing of the pitch changes
+    pub price: Price,
+}
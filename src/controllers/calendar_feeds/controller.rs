@@ -0,0 +1,409 @@
+use crate::{
+    controllers::{
+        PitchActor,
+        calendar_feeds::{
+            errors::CalendarFeedControllerError,
+            models::{Calendar, CalendarEvent, CalendarFeed, FeedScope},
+        },
+    },
+    models::ModelManager,
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Random bytes in a token, enough that it can't be guessed
+const TOKEN_BYTES: usize = 32;
+
+pub struct CalendarFeedController;
+
+#[derive(FromRow)]
+struct FeedOwner {
+    user_id: Option<Uuid>,
+    pitch_id: Option<Uuid>,
+    name: String,
+}
+
+impl CalendarFeedController {
+    pub async fn get_feed(
+        model_manager: &ModelManager,
+        scope: FeedScope,
+    ) -> Result<CalendarFeed, CalendarFeedControllerError> {
+        let (user_id, pitch_id) = Self::check_scope(model_manager, scope).await?;
+
+        sqlx::query_as(
+            r#"
+            SELECT
+                token
+            FROM
+                calendar_feeds
+            WHERE
+                user_id = $1
+            OR
+                pitch_id = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(pitch_id)
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(CalendarFeedControllerError::Sqlx)?
+        .ok_or(CalendarFeedControllerError::FeedNotFound)
+    }
+
+    /// Creates the feed, or gives it a new token, which revokes the link to the previous one
+    pub async fn regenerate_feed(
+        model_manager: &ModelManager,
+        scope: FeedScope,
+    ) -> Result<CalendarFeed, CalendarFeedControllerError> {
+        let (user_id, pitch_id) = Self::check_scope(model_manager, scope).await?;
+        let token = URL_SAFE_NO_PAD.encode(rand::random::<[u8; TOKEN_BYTES]>());
+
+        let query = match scope {
+            FeedScope::User(_) => {
+                r#"
+                INSERT INTO calendar_feeds
+                    (user_id, pitch_id, token)
+                VALUES
+                    ($1, $2, $3)
+                ON CONFLICT (user_id) DO UPDATE SET
+                    token = EXCLUDED.token,
+                    created_at = NOW()
+                RETURNING
+                    token
+                "#
+            }
+            FeedScope::Pitch(..) => {
+                r#"
+                INSERT INTO calendar_feeds
+                    (user_id, pitch_id, token)
+                VALUES
+                    ($1, $2, $3)
+                ON CONFLICT (pitch_id) DO UPDATE SET
+                    token = EXCLUDED.token,
+                    created_at = NOW()
+                RETURNING
+                    token
+                "#
+            }
+        };
+
+        sqlx::query_as(query)
+            .bind(user_id)
+            .bind(pitch_id)
+            .bind(token)
+            .fetch_one(model_manager.db())
+            .await
+            .map_err(CalendarFeedControllerError::Sqlx)
+    }
+
+    /// Revokes the link to the feed
+    pub async fn delete_feed(
+        model_manager: &ModelManager,
+        scope: FeedScope,
+    ) -> Result<(), CalendarFeedControllerError> {
+        let (user_id, pitch_id) = Self::check_scope(model_manager, scope).await?;
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM
+                calendar_feeds
+            WHERE
+                user_id = $1
+            OR
+                pitch_id = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(pitch_id)
+        .execute(model_manager.db())
+        .await
+        .map_err(CalendarFeedControllerError::Sqlx)?;
+
+        if result.rows_affected() == 0 {
+            return Err(CalendarFeedControllerError::FeedNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the bookings of the feed behind the token, from those that ended up to 90 days ago.
+    ///
+    /// A business' feed lists the bookings of all its pitches, a player's feed their own bookings.
+    /// Bookings under a confirmed closure of their pitch are kept, as cancelled events, so that
+    /// subscribed calendars drop them.
+    pub async fn get_calendar(
+        model_manager: &ModelManager,
+        token: &str,
+    ) -> Result<Calendar, CalendarFeedControllerError> {
+        let owner: FeedOwner = sqlx::query_as(
+            r#"
+            SELECT
+                calendar_feeds.user_id,
+                calendar_feeds.pitch_id,
+                COALESCE(
+                    pitches.display_name,
+                    business_profiles.display_name,
+                    player_profiles.first_name || ' ' || player_profiles.last_name
+                ) AS name
+            FROM
+                calendar_feeds
+            LEFT JOIN
+                pitches
+            ON
+                pitches.id = calendar_feeds.pitch_id
+            LEFT JOIN
+                business_profiles
+            ON
+                business_profiles.user_id = calendar_feeds.user_id
+            LEFT JOIN
+                player_profiles
+            ON
+                player_profiles.user_id = calendar_feeds.user_id
+            WHERE
+                calendar_feeds.token = $1
+            "#,
+        )
+        .bind(token)
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(CalendarFeedControllerError::Sqlx)?
+        .ok_or(CalendarFeedControllerError::FeedNotFound)?;
+
+        let events: Vec<CalendarEvent> = sqlx::query_as(
+            r#"
+            SELECT
+                bookings.id AS booking_id,
+                pitches.display_name AS pitch_name,
+                CASE WHEN locations.id IS NOT NULL THEN
+                    CONCAT_WS(', ', locations.address_line_1, locations.city, locations.country)
+                END AS location,
+                LOWER(timeslots.time_range) AS starts_at,
+                UPPER(timeslots.time_range) AS ends_at,
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        pitch_closures
+                    WHERE
+                        pitch_closures.pitch_id = pitches.id
+                    AND
+                        pitch_closures.is_confirmed
+                    AND
+                        pitch_closures.period && timeslots.time_range
+                ) AS is_cancelled
+            FROM
+                bookings
+            JOIN
+                timeslots
+            ON
+                timeslots.id = bookings.timeslot_id
+            JOIN
+                pitches
+            ON
+                pitches.id = timeslots.pitch_id
+            LEFT JOIN
+                locations
+            ON
+                locations.id = pitches.location_id
+            WHERE
+                (pitches.id = $1 OR pitches.owner_id = $2 OR bookings.booked_by = $2)
+            AND
+                UPPER(timeslots.time_range) > NOW() - INTERVAL '90 days'
+            ORDER BY
+                LOWER(timeslots.time_range), bookings.id
+            "#,
+        )
+        .bind(owner.pitch_id)
+        .bind(owner.user_id)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(CalendarFeedControllerError::Sqlx)?;
+
+        Ok(Calendar {
+            name: owner.name,
+            events,
+        })
+    }
+
+    /// Returns the user and the pitch of the feed, after checking the actor owns the pitch
+    async fn check_scope(
+        model_manager: &ModelManager,
+        scope: FeedScope,
+    ) -> Result<(Option<Uuid>, Option<Uuid>), CalendarFeedControllerError> {
+        let (pitch_id, actor) = match scope {
+            FeedScope::User(user_id) => return Ok((Some(user_id), None)),
+            FeedScope::Pitch(pitch_id, actor) => (pitch_id, actor),
+        };
+
+        let owner_id: Uuid = sqlx::query_scalar("SELECT owner_id FROM pitches WHERE id = $1")
+            .bind(pitch_id)
+            .fetch_optional(model_manager.db())
+            .await
+            .map_err(CalendarFeedControllerError::Sqlx)?
+            .ok_or(CalendarFeedControllerError::PitchNotFound)?;
+
+        match actor {
+            PitchActor::Admin(_) => Ok((None, Some(pitch_id))),
+            PitchActor::Owner(actor_id) if actor_id == owner_id => Ok((None, Some(pitch_id))),
+            PitchActor::Owner(_) => Err(CalendarFeedControllerError::PitchNotFound),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            BookingController, InsertTimeslotPayload, PitchActor, TimeslotController,
+            calendar_feeds::{
+                controller::CalendarFeedController, errors::CalendarFeedControllerError,
+                models::FeedScope,
+            },
+            test_utils::{delete_users, insert_business_with_pitch, insert_player},
+        },
+        models::{ModelManager, tables::PitchStatus},
+    };
+    use anyhow::Context;
+    use chrono::{DateTime, Duration, NaiveTime, Utc};
+    use uuid::Uuid;
+
+    /// Returns the id of the booking of a new one hour timeslot by the player
+    async fn book(
+        model_manager: &ModelManager,
+        business_id: Uuid,
+        pitch_id: Uuid,
+        player_id: Uuid,
+        starts_at: DateTime<Utc>,
+    ) -> anyhow::Result<Uuid> {
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::hours(1),
+            price: None,
+        };
+        let timeslot_id = TimeslotController::create_timeslot(
+            model_manager,
+            PitchActor::Owner(business_id),
+            timeslot,
+        )
+        .await
+        .context("failed while creating timeslot")?;
+        let booking = BookingController::book_timeslot(model_manager, player_id, timeslot_id)
+            .await
+            .context("failed while booking")?;
+
+        Ok(booking.id)
+    }
+
+    #[tokio::test]
+    async fn test_get_calendar_ok_cancelled_booking_and_revoked_token() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "subscribed_pitch", PitchStatus::Published)
+                .await?;
+        let player_id = insert_player(&model_manager).await?;
+        let tomorrow = (Utc::now().date_naive() + Duration::days(1))
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+        let kept_id = book(
+            &model_manager,
+            business_id,
+            pitch_id,
+            player_id,
+            tomorrow + Duration::hours(18),
+        )
+        .await?;
+        let cancelled_id = book(
+            &model_manager,
+            business_id,
+            pitch_id,
+            player_id,
+            tomorrow + Duration::hours(20),
+        )
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO pitch_closures
+                (pitch_id, period, reason, is_confirmed)
+            VALUES
+                ($1, TSTZRANGE($2, $2 + INTERVAL '1 hour'), 'flooded', TRUE)
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(tomorrow + Duration::hours(20))
+        .execute(model_manager.db())
+        .await
+        .context("failed while inserting closure")?;
+        let business_scope = FeedScope::User(business_id);
+        let pitch_scope = FeedScope::Pitch(pitch_id, PitchActor::Owner(business_id));
+        let leaked = CalendarFeedController::regenerate_feed(&model_manager, business_scope)
+            .await
+            .context("failed while creating business feed")?;
+
+        // exec
+        let business_feed = CalendarFeedController::regenerate_feed(&model_manager, business_scope)
+            .await
+            .context("failed while regenerating business feed")?;
+        let player_feed =
+            CalendarFeedController::regenerate_feed(&model_manager, FeedScope::User(player_id))
+                .await
+                .context("failed while creating player feed")?;
+        let pitch_feed = CalendarFeedController::regenerate_feed(&model_manager, pitch_scope)
+            .await
+            .context("failed while creating pitch feed")?;
+        let stranger_feed = CalendarFeedController::get_feed(
+            &model_manager,
+            FeedScope::Pitch(pitch_id, PitchActor::Owner(player_id)),
+        )
+        .await;
+        let leaked_calendar =
+            CalendarFeedController::get_calendar(&model_manager, &leaked.token).await;
+        let business_calendar =
+            CalendarFeedController::get_calendar(&model_manager, &business_feed.token)
+                .await
+                .context("failed while getting business calendar")?;
+        let player_calendar =
+            CalendarFeedController::get_calendar(&model_manager, &player_feed.token)
+                .await
+                .context("failed while getting player calendar")?;
+        let pitch_calendar =
+            CalendarFeedController::get_calendar(&model_manager, &pitch_feed.token)
+                .await
+                .context("failed while getting pitch calendar")?;
+
+        // check
+        assert_ne!(leaked.token, business_feed.token);
+        assert!(
+            matches!(
+                leaked_calendar,
+                Err(CalendarFeedControllerError::FeedNotFound)
+            ),
+            "leaked_calendar: {leaked_calendar:?}"
+        );
+        assert!(
+            matches!(
+                stranger_feed,
+                Err(CalendarFeedControllerError::PitchNotFound)
+            ),
+            "stranger_feed: {stranger_feed:?}"
+        );
+        assert_eq!(player_calendar.name, "Layla Haddad");
+        assert_eq!(pitch_calendar.name, "subscribed_pitch");
+        for calendar in [&business_calendar, &player_calendar, &pitch_calendar] {
+            let events: Vec<_> = calendar
+                .events
+                .iter()
+                .map(|event| (event.booking_id, event.is_cancelled))
+                .collect();
+            assert_eq!(events, [(kept_id, false), (cancelled_id, true)]);
+        }
+
+        // clean
+        delete_users(&model_manager, &[player_id, business_id]).await?;
+
+        Ok(())
+    }
+}
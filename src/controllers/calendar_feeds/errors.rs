@@ -0,0 +1,11 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum CalendarFeedControllerError {
+    PitchNotFound,
+    /// the user or the pitch has no feed, or the token was regenerated
+    FeedNotFound,
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(CalendarFeedControllerError);
@@ -0,0 +1,175 @@
+//! Writes calendars in the iCalendar format (RFC 5545) that calendar apps subscribe to.
+//!
+//! Events are identified by their booking, so that apps update them in place when a feed is
+//! fetched again, e.g. to show a cancellation.
+
+use crate::controllers::calendar_feeds::models::{Calendar, CalendarEvent};
+use chrono::{DateTime, Utc};
+
+const PRODUCT_ID: &str = "-//kootshi//bookings//EN";
+const UID_DOMAIN: &str = "kootshi";
+/// longer content lines are folded, RFC 5545 section 3.1
+const MAX_LINE_OCTETS: usize = 75;
+
+impl Calendar {
+    /// Returns the calendar with `now` as the time stamp of its events
+    pub fn to_ics(&self, now: DateTime<Utc>) -> String {
+        let mut ics = String::new();
+        push_line(&mut ics, "BEGIN:VCALENDAR");
+        push_line(&mut ics, "VERSION:2.0");
+        push_line(&mut ics, &format!("PRODID:{PRODUCT_ID}"));
+        push_line(&mut ics, "CALSCALE:GREGORIAN");
+        push_line(&mut ics, "METHOD:PUBLISH");
+        push_line(&mut ics, &format!("X-WR-CALNAME:{}", escape(&self.name)));
+        for event in &self.events {
+            push_event(&mut ics, event, now);
+        }
+        push_line(&mut ics, "END:VCALENDAR");
+
+        ics
+    }
+}
+
+fn push_event(ics: &mut String, event: &CalendarEvent, now: DateTime<Utc>) {
+    // the sequence goes up once, when the booking gets cancelled
+    let (status, sequence) = match event.is_cancelled {
+        true => ("CANCELLED", 1),
+        false => ("CONFIRMED", 0),
+    };
+
+    push_line(ics, "BEGIN:VEVENT");
+    push_line(ics, &format!("UID:{}@{UID_DOMAIN}", event.booking_id));
+    push_line(ics, &format!("DTSTAMP:{}", format_utc(now)));
+    push_line(ics, &format!("DTSTART:{}", format_utc(event.starts_at)));
+    push_line(ics, &format!("DTEND:{}", format_utc(event.ends_at)));
+    let summary = format!("Booking at {}", event.pitch_name);
+    push_line(ics, &format!("SUMMARY:{}", escape(&summary)));
+    if let Some(location) = &event.location {
+        push_line(ics, &format!("LOCATION:{}", escape(location)));
+    }
+    push_line(ics, &format!("STATUS:{status}"));
+    push_line(ics, &format!("SEQUENCE:{sequence}"));
+    push_line(ics, "END:VEVENT");
+}
+
+/// Appends the content line, folded without splitting characters and ended with CRLF
+fn push_line(ics: &mut String, line: &str) {
+    let mut line_octets = 0;
+    for character in line.chars() {
+        if line_octets + character.len_utf8() > MAX_LINE_OCTETS {
+            ics.push_str("\r\n ");
+            line_octets = 1;
+        }
+        ics.push(character);
+        line_octets += character.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+/// Escapes a TEXT value, RFC 5545 section 3.3.11
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+fn format_utc(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn event(is_cancelled: bool) -> CalendarEvent {
+        CalendarEvent {
+            booking_id: Uuid::nil(),
+            pitch_name: "Center Court".to_string(),
+            location: Some("1 Marina Walk, Dubai".to_string()),
+            starts_at: "2030-01-07T18:00:00+04:00".parse().unwrap(),
+            ends_at: "2030-01-07T19:30:00+04:00".parse().unwrap(),
+            is_cancelled,
+        }
+    }
+
+    #[test]
+    fn test_to_ics_ok_event() {
+        let calendar = Calendar {
+            name: "Padel club".to_string(),
+            events: vec![event(false)],
+        };
+
+        let ics = calendar.to_ics("2030-01-01T00:00:00Z".parse().unwrap());
+
+        assert_eq!(
+            ics,
+            [
+                "BEGIN:VCALENDAR",
+                "VERSION:2.0",
+                "PRODID:-//kootshi//bookings//EN",
+                "CALSCALE:GREGORIAN",
+                "METHOD:PUBLISH",
+                "X-WR-CALNAME:Padel club",
+                "BEGIN:VEVENT",
+                "UID:00000000-0000-0000-0000-000000000000@kootshi",
+                "DTSTAMP:20300101T000000Z",
+                "DTSTART:20300107T140000Z",
+                "DTEND:20300107T153000Z",
+                "SUMMARY:Booking at Center Court",
+                "LOCATION:1 Marina Walk\\, Dubai",
+                "STATUS:CONFIRMED",
+                "SEQUENCE:0",
+                "END:VEVENT",
+                "END:VCALENDAR",
+                "",
+            ]
+            .join("\r\n")
+        );
+    }
+
+    #[test]
+    fn test_to_ics_ok_cancelled_event() {
+        let calendar = Calendar {
+            name: "Padel club".to_string(),
+            events: vec![event(true)],
+        };
+
+        let ics = calendar.to_ics("2030-01-01T00:00:00Z".parse().unwrap());
+
+        assert!(ics.contains("\r\nSTATUS:CANCELLED\r\nSEQUENCE:1\r\n"));
+    }
+
+    #[test]
+    fn test_push_line_ok_folds_without_splitting_characters() {
+        let mut ics = String::new();
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+
+        push_line(&mut ics, &line);
+
+        let physical_lines: Vec<_> = ics.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(physical_lines.len(), 2);
+        assert!(
+            physical_lines
+                .iter()
+                .all(|line| line.len() <= MAX_LINE_OCTETS)
+        );
+        assert!(physical_lines[1].starts_with(' '));
+        assert_eq!(physical_lines.concat().replacen(" ", "", 1), line);
+    }
+
+    #[test]
+    fn test_escape_ok() {
+        assert_eq!(escape("a\\b;c,d\r\ne"), "a\\\\b\\;c\\,d\\ne");
+    }
+}
@@ -0,0 +1,4 @@
+pub mod controller;
+pub mod errors;
+pub mod ics;
+pub mod models;
@@ -0,0 +1,38 @@
+use crate::controllers::PitchActor;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Whose bookings a feed lists
+#[derive(Clone, Copy)]
+pub enum FeedScope {
+    /// the bookings of all the pitches of a business, or the own bookings of a player
+    User(Uuid),
+    Pitch(Uuid, PitchActor),
+}
+
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct CalendarFeed {
+    /// secret part of the feed's URL
+    pub token: String,
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub struct Calendar {
+    pub name: String,
+    pub events: Vec<CalendarEvent>,
+}
+
+/// A booking, as a calendar event
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct CalendarEvent {
+    pub booking_id: Uuid,
+    pub pitch_name: String,
+    pub location: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// a confirmed closure of the pitch overlaps the booking
+    pub is_cancelled: bool,
+}
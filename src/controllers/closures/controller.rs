@@ -0,0 +1,564 @@
+use crate::{
+    controllers::{
+        PitchActor,
+        closures::{
+            errors::ClosureControllerError,
+            models::{AffectedBooking, Closure, CreatedClosure, InsertClosurePayload},
+        },
+    },
+    models::{ModelManager, tables::PitchStatus},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgConnection};
+use uuid::Uuid;
+
+pub struct ClosureController;
+
+#[derive(FromRow)]
+struct PendingClosure {
+    /// of the pitch
+    display_name: String,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    reason: String,
+    is_confirmed: bool,
+}
+
+impl ClosureController {
+    /// Returns the closures that haven't ended yet, soonest first.
+    ///
+    /// Pending closures and pitches that aren't published are only shown to the pitch's owner and
+    /// to admins.
+    pub async fn get_closures(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        viewer: Option<PitchActor>,
+    ) -> Result<Vec<Closure>, ClosureControllerError> {
+        let (owner_id, status): (Uuid, PitchStatus) = sqlx::query_as(
+            r#"
+            SELECT
+                owner_id, status
+            FROM
+                pitches
+            WHERE
+                id = $1
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(ClosureControllerError::Sqlx)?
+        .ok_or(ClosureControllerError::PitchNotFound)?;
+
+        let can_manage = match viewer {
+            Some(PitchActor::Admin(_)) => true,
+            Some(PitchActor::Owner(viewer_id)) => viewer_id == owner_id,
+            None => false,
+        };
+        if !can_manage && status != PitchStatus::Published {
+            return Err(ClosureControllerError::PitchNotFound);
+        }
+
+        sqlx::query_as(
+            r#"
+            SELECT
+                pitch_closures.id,
+                LOWER(pitch_closures.period) AS starts_at,
+                UPPER(pitch_closures.period) AS ends_at,
+                LOWER(pitch_closures.period) AT TIME ZONE pitches.timezone AS local_starts_at,
+                UPPER(pitch_closures.period) AT TIME ZONE pitches.timezone AS local_ends_at,
+                pitch_closures.reason,
+                pitch_closures.is_confirmed
+            FROM
+                pitch_closures
+            JOIN
+                pitches
+            ON
+                pitches.id = pitch_closures.pitch_id
+            WHERE
+                pitch_closures.pitch_id = $1
+            AND
+                UPPER(pitch_closures.period) > NOW()
+            AND
+                (pitch_closures.is_confirmed OR $2)
+            ORDER BY
+                LOWER(pitch_closures.period)
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(can_manage)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(ClosureControllerError::Sqlx)
+    }
+
+    /// Closes the pitch for the given period.
+    ///
+    /// The closure is confirmed right away when no booking falls inside it, otherwise it stays
+    /// pending and the affected bookings are returned so the owner can review them before calling
+    /// [`Self::confirm_closure_by_id`].
+    pub async fn create_closure(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        actor: PitchActor,
+        closure: InsertClosurePayload,
+    ) -> Result<CreatedClosure, ClosureControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(ClosureControllerError::Sqlx)?;
+
+        Self::lock_pitch(&mut transaction, pitch_id, actor).await?;
+
+        let affected_bookings = sqlx::query_as::<_, AffectedBooking>(
+            r#"
+            SELECT
+                bookings.id AS booking_id,
+                timeslots.id AS timeslot_id,
+                bookings.booked_by,
+                LOWER(timeslots.time_range) AS starts_at,
+                UPPER(timeslots.time_range) AS ends_at,
+                LOWER(timeslots.time_range) AT TIME ZONE pitches.timezone AS local_starts_at,
+                UPPER(timeslots.time_range) AT TIME ZONE pitches.timezone AS local_ends_at
+            FROM
+                bookings
+            JOIN
+                timeslots
+            ON
+                timeslots.id = bookings.timeslot_id
+            JOIN
+                pitches
+            ON
+                pitches.id = timeslots.pitch_id
+            WHERE
+                timeslots.pitch_id = $1
+            AND
+                timeslots.time_range && TSTZRANGE($2, $3)
+            ORDER BY
+                LOWER(timeslots.time_range), bookings.id
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(closure.starts_at)
+        .bind(closure.ends_at)
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(ClosureControllerError::Sqlx)?;
+        let is_confirmed = affected_bookings.is_empty();
+
+        let result = sqlx::query_scalar(
+            r#"
+            INSERT INTO pitch_closures
+                (pitch_id, period, reason, is_confirmed)
+            VALUES
+                ($1, TSTZRANGE($2, $3), $4, $5)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(closure.starts_at)
+        .bind(closure.ends_at)
+        .bind(closure.reason)
+        .bind(is_confirmed)
+        .fetch_one(&mut *transaction)
+        .await;
+
+        let id = match result {
+            Ok(id) => id,
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("pitch_closures_pitch_id_period_excl") =>
+            {
+                return Err(ClosureControllerError::ClosureOverlaps);
+            }
+            Err(err) => return Err(ClosureControllerError::Sqlx(err)),
+        };
+
+        transaction
+            .commit()
+            .await
+            .map_err(ClosureControllerError::Sqlx)?;
+
+        Ok(CreatedClosure {
+            id,
+            is_confirmed,
+            affected_bookings,
+        })
+    }
+
+    /// Confirms a pending closure and lets the players whose bookings fall inside it know.
+    ///
+    /// Confirming a closure twice is a no-op.
+    pub async fn confirm_closure_by_id(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), ClosureControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(ClosureControllerError::Sqlx)?;
+
+        Self::lock_pitch(&mut transaction, pitch_id, actor).await?;
+
+        let closure = sqlx::query_as::<_, PendingClosure>(
+            r#"
+            SELECT
+                pitches.display_name,
+                LOWER(pitch_closures.period) AS starts_at,
+                UPPER(pitch_closures.period) AS ends_at,
+                pitch_closures.reason,
+                pitch_closures.is_confirmed
+            FROM
+                pitch_closures
+            JOIN
+                pitches
+            ON
+                pitches.id = pitch_closures.pitch_id
+            WHERE
+                pitch_closures.id = $1
+            AND
+                pitch_closures.pitch_id = $2
+            FOR UPDATE OF
+                pitch_closures
+            "#,
+        )
+        .bind(id)
+        .bind(pitch_id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(ClosureControllerError::Sqlx)?
+        .ok_or(ClosureControllerError::ClosureNotFound)?;
+
+        if closure.is_confirmed {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE
+                pitch_closures
+            SET
+                is_confirmed = TRUE
+            WHERE
+                id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(ClosureControllerError::Sqlx)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO notifications
+                (user_id, message)
+            SELECT DISTINCT
+                bookings.booked_by, $4
+            FROM
+                bookings
+            JOIN
+                timeslots
+            ON
+                timeslots.id = bookings.timeslot_id
+            WHERE
+                timeslots.pitch_id = $1
+            AND
+                timeslots.time_range && TSTZRANGE($2, $3)
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(closure.starts_at)
+        .bind(closure.ends_at)
+        .bind(format!(
+            "pitch \"{}\" is closed from {} to {}: {}",
+            closure.display_name,
+            closure.starts_at.to_rfc3339(),
+            closure.ends_at.to_rfc3339(),
+            closure.reason
+        ))
+        .execute(&mut *transaction)
+        .await
+        .map_err(ClosureControllerError::Sqlx)?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(ClosureControllerError::Sqlx)
+    }
+
+    /// Reopens the pitch for the closure's period, or drops a pending closure
+    pub async fn delete_closure_by_id(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), ClosureControllerError> {
+        let rows_affected = sqlx::query(
+            r#"
+            DELETE FROM
+                pitch_closures
+            USING
+                pitches
+            WHERE
+                pitch_closures.id = $1
+            AND
+                pitch_closures.pitch_id = $2
+            AND
+                pitches.id = pitch_closures.pitch_id
+            AND
+                ($3::UUID IS NULL OR pitches.owner_id = $3)
+            "#,
+        )
+        .bind(id)
+        .bind(pitch_id)
+        .bind(actor.owner_id())
+        .execute(model_manager.db())
+        .await
+        .map_err(ClosureControllerError::Sqlx)?
+        .rows_affected();
+
+        if rows_affected == 0 {
+            return Err(ClosureControllerError::ClosureNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Pitches of other businesses are reported as not found when the actor is an owner
+    async fn lock_pitch(
+        connection: &mut PgConnection,
+        pitch_id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), ClosureControllerError> {
+        sqlx::query(
+            r#"
+            SELECT
+                1
+            FROM
+                pitches
+            WHERE
+                id = $1
+            AND
+                ($2::UUID IS NULL OR owner_id = $2)
+            FOR UPDATE
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(actor.owner_id())
+        .fetch_optional(connection)
+        .await
+        .map_err(ClosureControllerError::Sqlx)?
+        .ok_or(ClosureControllerError::PitchNotFound)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            PitchActor,
+            closures::{
+                controller::ClosureController, errors::ClosureControllerError,
+                models::InsertClosurePayload,
+            },
+            test_utils::{delete_business, insert_business_with_pitch},
+        },
+        models::{ModelManager, tables::PitchStatus},
+    };
+    use anyhow::Context;
+    use chrono::{DateTime, Duration, Utc};
+    use uuid::{Uuid, uuid};
+
+    #[tokio::test]
+    async fn test_create_closure_ok_pending_with_bookings() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let pitch_id = uuid!("00000000-0000-0000-0000-000000000006");
+        let business_id = uuid!("00000000-0000-0000-0000-000000000003");
+        let closure = InsertClosurePayload {
+            starts_at: "2025-03-20T00:00:00Z".parse()?,
+            ends_at: "2025-03-21T00:00:00Z".parse()?,
+            reason: "maintenance".to_string(),
+        };
+        let created_closure = ClosureController::create_closure(
+            &model_manager,
+            pitch_id,
+            PitchActor::Owner(business_id),
+            closure,
+        )
+        .await
+        .context("failed while creating closure")?;
+
+        // check
+        assert!(!created_closure.is_confirmed);
+        let booking_ids: Vec<_> = created_closure
+            .affected_bookings
+            .iter()
+            .map(|booking| booking.booking_id)
+            .collect();
+        assert_eq!(booking_ids, [uuid!("00000000-0000-0000-0000-000000000014")]);
+
+        // clean
+        ClosureController::delete_closure_by_id(
+            &model_manager,
+            pitch_id,
+            created_closure.id,
+            PitchActor::Owner(business_id),
+        )
+        .await
+        .context("failed while deleting closure")?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_closure_ok_blocks_timeslots() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "closed_pitch", PitchStatus::Published)
+                .await?;
+        let starts_at = Utc::now() + Duration::days(1);
+        let timeslot_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO timeslots (pitch_id, time_range) VALUES ($1, TSTZRANGE($2, $3)) RETURNING id",
+        )
+        .bind(pitch_id)
+        .bind(starts_at)
+        .bind(starts_at + Duration::hours(1))
+        .fetch_one(model_manager.db())
+        .await
+        .context("failed while inserting timeslot")?;
+
+        // exec
+        let closure = InsertClosurePayload {
+            starts_at,
+            ends_at: starts_at + Duration::days(2),
+            reason: "maintenance".to_string(),
+        };
+        let created_closure = ClosureController::create_closure(
+            &model_manager,
+            pitch_id,
+            PitchActor::Owner(business_id),
+            closure,
+        )
+        .await
+        .context("failed while creating closure")?;
+
+        // check
+        assert!(created_closure.is_confirmed);
+        assert!(created_closure.affected_bookings.is_empty());
+
+        let bookable_timeslot_ids: Vec<Uuid> =
+            sqlx::query_scalar("SELECT id FROM bookable_timeslots WHERE pitch_id = $1")
+                .bind(pitch_id)
+                .fetch_all(model_manager.db())
+                .await
+                .context("failed while fetching bookable timeslots")?;
+        assert!(!bookable_timeslot_ids.contains(&timeslot_id));
+
+        let closures = ClosureController::get_closures(&model_manager, pitch_id, None)
+            .await
+            .context("failed while fetching closures")?;
+        let closure_ids: Vec<_> = closures.iter().map(|closure| closure.id).collect();
+        assert_eq!(closure_ids, [created_closure.id]);
+        assert_eq!(closures[0].reason, "maintenance");
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_closure_err_overlaps() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "closed_pitch", PitchStatus::Published)
+                .await?;
+        let starts_at: DateTime<Utc> = "2030-01-01T00:00:00Z".parse()?;
+        let closure = InsertClosurePayload {
+            starts_at,
+            ends_at: starts_at + Duration::days(2),
+            reason: "maintenance".to_string(),
+        };
+        ClosureController::create_closure(
+            &model_manager,
+            pitch_id,
+            PitchActor::Owner(business_id),
+            closure,
+        )
+        .await
+        .context("failed while creating closure")?;
+
+        // exec
+        let closure = InsertClosurePayload {
+            starts_at: starts_at + Duration::days(1),
+            ends_at: starts_at + Duration::days(3),
+            reason: "renovation".to_string(),
+        };
+        let result = ClosureController::create_closure(
+            &model_manager,
+            pitch_id,
+            PitchActor::Owner(business_id),
+            closure,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(ClosureControllerError::ClosureOverlaps)),
+            "result: {result:?}"
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_closures_err_draft_pitch_of_another_business() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "closed_pitch", PitchStatus::Draft).await?;
+
+        // exec
+        let other_business_id = uuid!("00000000-0000-0000-0000-000000000003");
+        let result = ClosureController::get_closures(
+            &model_manager,
+            pitch_id,
+            Some(PitchActor::Owner(other_business_id)),
+        )
+        .await;
+        let owner_result = ClosureController::get_closures(
+            &model_manager,
+            pitch_id,
+            Some(PitchActor::Owner(business_id)),
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(ClosureControllerError::PitchNotFound)),
+            "result: {result:?}"
+        );
+        assert!(owner_result.is_ok());
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum ClosureControllerError {
+    PitchNotFound,
+    ClosureNotFound,
+    ClosureOverlaps,
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(ClosureControllerError);
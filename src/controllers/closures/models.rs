@@ -0,0 +1,45 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct Closure {
+    pub id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// wall clock times in the pitch's timezone
+    pub local_starts_at: NaiveDateTime,
+    pub local_ends_at: NaiveDateTime,
+    pub reason: String,
+    /// pending closures don't block timeslots yet
+    pub is_confirmed: bool,
+}
+
+pub struct InsertClosurePayload {
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// A booking whose timeslot overlaps a closure
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct AffectedBooking {
+    pub booking_id: Uuid,
+    pub timeslot_id: Uuid,
+    pub booked_by: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// wall clock times in the pitch's timezone
+    pub local_starts_at: NaiveDateTime,
+    pub local_ends_at: NaiveDateTime,
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub struct CreatedClosure {
+    pub id: Uuid,
+    /// `false` when bookings are affected, the owner has to confirm the closure then
+    pub is_confirmed: bool,
+    pub affected_bookings: Vec<AffectedBooking>,
+}
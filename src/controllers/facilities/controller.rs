@@ -0,0 +1,696 @@
+use crate::{
+    controllers::{
+        PitchActor,
+        facilities::{
+            errors::FacilityControllerError,
+            models::{Facility, FacilityUnit},
+        },
+    },
+    models::ModelManager,
+};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+pub struct FacilityController;
+
+impl FacilityController {
+    pub async fn create_facility(
+        model_manager: &ModelManager,
+        actor: PitchActor,
+        owner_id: Uuid,
+        display_name: String,
+    ) -> Result<Uuid, FacilityControllerError> {
+        if actor
+            .owner_id()
+            .is_some_and(|actor_id| actor_id != owner_id)
+        {
+            return Err(FacilityControllerError::NotOwner);
+        }
+
+        let result = sqlx::query_scalar(
+            r#"
+            INSERT INTO facilities
+                (owner_id, display_name)
+            VALUES
+                ($1, $2)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(owner_id)
+        .bind(display_name)
+        .fetch_one(model_manager.db())
+        .await;
+
+        match result {
+            Ok(id) => Ok(id),
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("facilities_owner_id_fkey") =>
+            {
+                Err(FacilityControllerError::OwnerNotFound)
+            }
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("facilities_owner_id_display_name_key") =>
+            {
+                Err(FacilityControllerError::DisplayNameAlreadyExists)
+            }
+            Err(err) => Err(FacilityControllerError::Sqlx(err)),
+        }
+    }
+
+    /// Returns the facility with its units.
+    ///
+    /// Units that aren't published are only shown to the facility's owner and to admins.
+    pub async fn get_facility_by_id(
+        model_manager: &ModelManager,
+        id: Uuid,
+        viewer: Option<PitchActor>,
+    ) -> Result<Facility, FacilityControllerError> {
+        let (owner_id, display_name): (Uuid, String) = sqlx::query_as(
+            r#"
+            SELECT
+                owner_id, display_name
+            FROM
+                facilities
+            WHERE
+                id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(FacilityControllerError::Sqlx)?
+        .ok_or(FacilityControllerError::FacilityNotFound)?;
+
+        let can_manage = match viewer {
+            Some(PitchActor::Admin(_)) => true,
+            Some(PitchActor::Owner(viewer_id)) => viewer_id == owner_id,
+            None => false,
+        };
+
+        let units = sqlx::query_as::<_, FacilityUnit>(
+            r#"
+            SELECT
+                id,
+                display_name,
+                sport,
+                other_sports,
+                status,
+                ARRAY(
+                    SELECT
+                        component_id
+                    FROM
+                        pitch_components
+                    WHERE
+                        pitch_components.pitch_id = pitches.id
+                    ORDER BY
+                        component_id
+                ) AS component_ids
+            FROM
+                pitches
+            WHERE
+                facility_id = $1
+            AND
+                (status = 'published' OR $2)
+            ORDER BY
+                display_name
+            "#,
+        )
+        .bind(id)
+        .bind(can_manage)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(FacilityControllerError::Sqlx)?;
+
+        Ok(Facility {
+            id,
+            owner_id,
+            display_name,
+            units,
+        })
+    }
+
+    /// Deletes the facility, its units are kept as standalone pitches
+    pub async fn delete_facility_by_id(
+        model_manager: &ModelManager,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), FacilityControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(FacilityControllerError::Sqlx)?;
+
+        Self::lock_facility(&mut transaction, id, actor).await?;
+
+        let unit_ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT
+                id
+            FROM
+                pitches
+            WHERE
+                facility_id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(FacilityControllerError::Sqlx)?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM
+                pitch_components
+            WHERE
+                pitch_id = ANY($1)
+            "#,
+        )
+        .bind(&unit_ids)
+        .execute(&mut *transaction)
+        .await
+        .map_err(FacilityControllerError::Sqlx)?;
+
+        sqlx::query("DELETE FROM facilities WHERE id = $1")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(FacilityControllerError::Sqlx)?;
+
+        Self::refresh_timeslot_occupancy(&mut transaction, &unit_ids).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(FacilityControllerError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Adds one of the owner's pitches to the facility, or updates it if it's already a unit.
+    ///
+    /// `component_ids` are the units the pitch takes up, e.g. the two halves of a full field. The
+    /// upcoming timeslots of the pitch and of the combinations it's part of then take up these
+    /// units too, which fails when they overlap timeslots of the components.
+    pub async fn set_unit(
+        model_manager: &ModelManager,
+        facility_id: Uuid,
+        pitch_id: Uuid,
+        actor: PitchActor,
+        mut component_ids: Vec<Uuid>,
+    ) -> Result<(), FacilityControllerError> {
+        component_ids.sort_unstable();
+        component_ids.dedup();
+
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(FacilityControllerError::Sqlx)?;
+
+        let owner_id = Self::lock_facility(&mut transaction, facility_id, actor).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE
+                pitches
+            SET
+                facility_id = $1
+            WHERE
+                id = $2
+            AND
+                owner_id = $3
+            RETURNING
+                id
+            "#,
+        )
+        .bind(facility_id)
+        .bind(pitch_id)
+        .bind(owner_id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(FacilityControllerError::Sqlx)?
+        .ok_or(FacilityControllerError::PitchNotFound)?;
+
+        let component_count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT
+                COUNT(*)
+            FROM
+                pitches
+            WHERE
+                id = ANY($1)
+            AND
+                facility_id = $2
+            AND
+                id <> $3
+            "#,
+        )
+        .bind(&component_ids)
+        .bind(facility_id)
+        .bind(pitch_id)
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(FacilityControllerError::Sqlx)?;
+
+        if component_count != component_ids.len() as i64 {
+            return Err(FacilityControllerError::InvalidComponents);
+        }
+
+        sqlx::query("DELETE FROM pitch_components WHERE pitch_id = $1")
+            .bind(pitch_id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(FacilityControllerError::Sqlx)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO pitch_components
+                (pitch_id, component_id)
+            SELECT
+                $1, UNNEST($2::UUID[])
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(&component_ids)
+        .execute(&mut *transaction)
+        .await
+        .map_err(FacilityControllerError::Sqlx)?;
+
+        let is_own_component: bool = sqlx::query_scalar(
+            r#"
+            WITH RECURSIVE parts AS (
+                SELECT
+                    component_id
+                FROM
+                    pitch_components
+                WHERE
+                    pitch_id = $1
+                UNION
+                SELECT
+                    pitch_components.component_id
+                FROM
+                    parts
+                JOIN
+                    pitch_components
+                ON
+                    pitch_components.pitch_id = parts.component_id
+            )
+            SELECT
+                EXISTS (SELECT 1 FROM parts WHERE component_id = $1)
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(FacilityControllerError::Sqlx)?;
+
+        if is_own_component {
+            return Err(FacilityControllerError::InvalidComponents);
+        }
+
+        let combined_pitch_ids = Self::get_combined_pitch_ids(&mut transaction, pitch_id).await?;
+        Self::refresh_timeslot_occupancy(&mut transaction, &combined_pitch_ids).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(FacilityControllerError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Takes the pitch out of the facility, along with the combinations it was part of
+    pub async fn remove_unit(
+        model_manager: &ModelManager,
+        facility_id: Uuid,
+        pitch_id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), FacilityControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(FacilityControllerError::Sqlx)?;
+
+        Self::lock_facility(&mut transaction, facility_id, actor).await?;
+
+        // collected before the components are gone
+        let combined_pitch_ids = Self::get_combined_pitch_ids(&mut transaction, pitch_id).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE
+                pitches
+            SET
+                facility_id = NULL
+            WHERE
+                id = $1
+            AND
+                facility_id = $2
+            RETURNING
+                id
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(facility_id)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(FacilityControllerError::Sqlx)?
+        .ok_or(FacilityControllerError::PitchNotFound)?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM
+                pitch_components
+            WHERE
+                pitch_id = $1
+            OR
+                component_id = $1
+            "#,
+        )
+        .bind(pitch_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(FacilityControllerError::Sqlx)?;
+
+        Self::refresh_timeslot_occupancy(&mut transaction, &combined_pitch_ids).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(FacilityControllerError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Returns the owner of the facility
+    async fn lock_facility(
+        connection: &mut PgConnection,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<Uuid, FacilityControllerError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT
+                owner_id
+            FROM
+                facilities
+            WHERE
+                id = $1
+            AND
+                ($2::UUID IS NULL OR owner_id = $2)
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .bind(actor.owner_id())
+        .fetch_optional(connection)
+        .await
+        .map_err(FacilityControllerError::Sqlx)?
+        .ok_or(FacilityControllerError::FacilityNotFound)
+    }
+
+    /// Returns the pitch and every combination that takes it up, directly or not
+    async fn get_combined_pitch_ids(
+        connection: &mut PgConnection,
+        pitch_id: Uuid,
+    ) -> Result<Vec<Uuid>, FacilityControllerError> {
+        sqlx::query_scalar(
+            r#"
+            WITH RECURSIVE combined AS (
+                SELECT
+                    $1::UUID AS pitch_id
+                UNION
+                SELECT
+                    pitch_components.pitch_id
+                FROM
+                    combined
+                JOIN
+                    pitch_components
+                ON
+                    pitch_components.component_id = combined.pitch_id
+            )
+            SELECT
+                pitch_id
+            FROM
+                combined
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_all(connection)
+        .await
+        .map_err(FacilityControllerError::Sqlx)
+    }
+
+    /// Recomputes the units taken up by the upcoming timeslots of the pitches, past ones are left
+    /// as they were
+    async fn refresh_timeslot_occupancy(
+        connection: &mut PgConnection,
+        pitch_ids: &[Uuid],
+    ) -> Result<(), FacilityControllerError> {
+        let result = sqlx::query(
+            r#"
+            SELECT
+                set_timeslot_occupancy(id, pitch_id, time_range)
+            FROM
+                timeslots
+            WHERE
+                pitch_id = ANY($1)
+            AND
+                UPPER(time_range) > NOW()
+            "#,
+        )
+        .bind(pitch_ids)
+        .execute(connection)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("timeslot_occupancy_unit_id_time_range_excl") =>
+            {
+                Err(FacilityControllerError::UnitsHaveOverlappingTimeslots)
+            }
+            Err(err) => Err(FacilityControllerError::Sqlx(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            InsertPitchPayload, PitchActor,
+            facilities::{controller::FacilityController, errors::FacilityControllerError},
+            test_utils::{
+                delete_business, insert_business_with_pitch, insert_pitch, pitch_payload,
+            },
+        },
+        models::{
+            ModelManager,
+            tables::{PitchStatus, Sport},
+        },
+    };
+    use anyhow::Context;
+    use uuid::Uuid;
+
+    /// Returns the ids of a new business, of its facility, and of the two halves and the full
+    /// field in it, in that order
+    async fn insert_business_with_facility(
+        model_manager: &ModelManager,
+    ) -> anyhow::Result<(Uuid, Uuid, [Uuid; 3])> {
+        let (business_id, half_a) =
+            insert_business_with_pitch(model_manager, "half_a", PitchStatus::Published).await?;
+        let half_b = insert_pitch(
+            model_manager,
+            business_id,
+            pitch_payload("half_b", PitchStatus::Published)?,
+        )
+        .await?;
+        let full = InsertPitchPayload {
+            other_sports: vec![Sport::Basketball],
+            ..pitch_payload("full", PitchStatus::Published)?
+        };
+        let full = insert_pitch(model_manager, business_id, full).await?;
+        let facility_id = FacilityController::create_facility(
+            model_manager,
+            PitchActor::Owner(business_id),
+            business_id,
+            "sports_park".to_string(),
+        )
+        .await
+        .context("failed while creating facility")?;
+
+        Ok((business_id, facility_id, [half_a, half_b, full]))
+    }
+
+    async fn insert_timeslot(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        starts_at: &str,
+        ends_at: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO timeslots (pitch_id, time_range) VALUES ($1, TSTZRANGE($2::TIMESTAMPTZ, $3::TIMESTAMPTZ))",
+        )
+        .bind(pitch_id)
+        .bind(starts_at)
+        .bind(ends_at)
+        .execute(model_manager.db())
+        .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_unit_ok_combination_blocks_its_components() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, facility_id, [half_a, half_b, full]) =
+            insert_business_with_facility(&model_manager).await?;
+        let actor = PitchActor::Owner(business_id);
+        for pitch_id in [half_a, half_b] {
+            FacilityController::set_unit(&model_manager, facility_id, pitch_id, actor, Vec::new())
+                .await
+                .context("failed while adding half")?;
+        }
+
+        // exec
+        FacilityController::set_unit(
+            &model_manager,
+            facility_id,
+            full,
+            actor,
+            vec![half_a, half_b],
+        )
+        .await
+        .context("failed while adding full field")?;
+
+        // check
+        let facility =
+            FacilityController::get_facility_by_id(&model_manager, facility_id, None).await?;
+        let full_unit = facility
+            .units
+            .iter()
+            .find(|unit| unit.id == full)
+            .context("full field isn't a unit")?;
+        let mut component_ids = vec![half_a, half_b];
+        component_ids.sort_unstable();
+        assert_eq!(full_unit.component_ids, component_ids);
+        assert_eq!(full_unit.other_sports, [Sport::Basketball]);
+
+        let half_a_slot = insert_timeslot(
+            &model_manager,
+            half_a,
+            "2030-01-01T10:00:00Z",
+            "2030-01-01T11:00:00Z",
+        )
+        .await;
+        let full_slot = insert_timeslot(
+            &model_manager,
+            full,
+            "2030-01-01T10:30:00Z",
+            "2030-01-01T11:30:00Z",
+        )
+        .await;
+        let half_b_slot = insert_timeslot(
+            &model_manager,
+            half_b,
+            "2030-01-01T10:30:00Z",
+            "2030-01-01T11:30:00Z",
+        )
+        .await;
+        assert!(half_a_slot.is_ok(), "result: {half_a_slot:?}");
+        assert!(
+            matches!(
+                &full_slot,
+                Err(sqlx::Error::Database(err))
+                    if err.constraint() == Some("timeslot_occupancy_unit_id_time_range_excl")
+            ),
+            "result: {full_slot:?}"
+        );
+        assert!(half_b_slot.is_ok(), "result: {half_b_slot:?}");
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_unit_err_components_have_overlapping_timeslots() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, facility_id, [half_a, _, full]) =
+            insert_business_with_facility(&model_manager).await?;
+        let actor = PitchActor::Owner(business_id);
+        FacilityController::set_unit(&model_manager, facility_id, half_a, actor, Vec::new())
+            .await
+            .context("failed while adding half")?;
+        for pitch_id in [half_a, full] {
+            insert_timeslot(
+                &model_manager,
+                pitch_id,
+                "2030-01-01T10:00:00Z",
+                "2030-01-01T11:00:00Z",
+            )
+            .await
+            .context("failed while inserting timeslot")?;
+        }
+
+        // exec
+        let result =
+            FacilityController::set_unit(&model_manager, facility_id, full, actor, vec![half_a])
+                .await;
+
+        // check
+        assert!(
+            matches!(
+                result,
+                Err(FacilityControllerError::UnitsHaveOverlappingTimeslots)
+            ),
+            "result: {result:?}"
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_unit_err_component_cycle() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, facility_id, [half_a, _, full]) =
+            insert_business_with_facility(&model_manager).await?;
+        let actor = PitchActor::Owner(business_id);
+        FacilityController::set_unit(&model_manager, facility_id, half_a, actor, Vec::new())
+            .await
+            .context("failed while adding half")?;
+        FacilityController::set_unit(&model_manager, facility_id, full, actor, vec![half_a])
+            .await
+            .context("failed while adding full field")?;
+
+        // exec
+        let result =
+            FacilityController::set_unit(&model_manager, facility_id, half_a, actor, vec![full])
+                .await;
+
+        // check
+        assert!(
+            matches!(result, Err(FacilityControllerError::InvalidComponents)),
+            "result: {result:?}"
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum FacilityControllerError {
+    FacilityNotFound,
+    PitchNotFound,
+    OwnerNotFound,
+    NotOwner,
+    DisplayNameAlreadyExists,
+    /// components must be other units of the same facility, without cycles
+    InvalidComponents,
+    /// upcoming timeslots of units that would now take up the same unit overlap
+    UnitsHaveOverlappingTimeslots,
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(FacilityControllerError);
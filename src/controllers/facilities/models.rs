@@ -0,0 +1,24 @@
+use crate::models::tables::{PitchStatus, Sport};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[cfg_attr(test, derive(Debug))]
+pub struct Facility {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub display_name: String,
+    pub units: Vec<FacilityUnit>,
+}
+
+/// A pitch of the facility, either a single court or a combination of other units
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct FacilityUnit {
+    pub id: Uuid,
+    pub display_name: String,
+    pub sport: Sport,
+    pub other_sports: Vec<Sport>,
+    pub status: PitchStatus,
+    /// units that can't be played on while this one is, empty for single courts
+    pub component_ids: Vec<Uuid>,
+}
@@ -0,0 +1,323 @@
+use crate::{
+    controllers::favorites::{errors::FavoriteControllerError, models::FavoritePitch},
+    models::{
+        ModelManager,
+        pagination::{Keyset, PageRequest, Paged},
+    },
+};
+use uuid::Uuid;
+
+pub struct FavoriteController;
+
+impl FavoriteController {
+    /// Adding a pitch that is already a favorite is a no-op
+    pub async fn add_pitch(
+        model_manager: &ModelManager,
+        player_id: Uuid,
+        pitch_id: Uuid,
+    ) -> Result<(), FavoriteControllerError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO favorite_pitches
+                (player_id, pitch_id)
+            VALUES
+                ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(player_id)
+        .bind(pitch_id)
+        .execute(model_manager.db())
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("favorite_pitches_pitch_id_fkey") =>
+            {
+                Err(FavoriteControllerError::PitchNotFound)
+            }
+            Err(err) => Err(FavoriteControllerError::Sqlx(err)),
+        }
+    }
+
+    /// Removing a pitch that is not a favorite is a no-op
+    pub async fn remove_pitch(
+        model_manager: &ModelManager,
+        player_id: Uuid,
+        pitch_id: Uuid,
+    ) -> Result<(), FavoriteControllerError> {
+        sqlx::query(
+            r#"
+            DELETE FROM
+                favorite_pitches
+            WHERE
+                player_id = $1
+            AND
+                pitch_id = $2
+            "#,
+        )
+        .bind(player_id)
+        .bind(pitch_id)
+        .execute(model_manager.db())
+        .await
+        .map_err(FavoriteControllerError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Returns the favorite pitches of a player, most recently added first
+    pub async fn get_favorite_pitches(
+        model_manager: &ModelManager,
+        player_id: Uuid,
+        page: &PageRequest<<FavoritePitch as Keyset>::Key>,
+    ) -> Result<Paged<FavoritePitch>, FavoriteControllerError> {
+        let (after_favorited_at, after_pitch_id) = page.after.unzip();
+
+        let favorites = sqlx::query_as(
+            r#"
+            SELECT
+                pitches.id AS pitch_id,
+                pitches.display_name,
+                pitches.sport,
+                favorite_pitches.created_at AS favorited_at
+            FROM
+                favorite_pitches
+            JOIN
+                pitches
+            ON
+                pitches.id = favorite_pitches.pitch_id
+            WHERE
+                favorite_pitches.player_id = $1
+            AND (
+                $2::TIMESTAMPTZ IS NULL
+            OR
+                favorite_pitches.created_at < $2
+            OR
+                (favorite_pitches.created_at = $2 AND pitches.id > $3)
+            )
+            ORDER BY
+                favorite_pitches.created_at DESC, pitches.id
+            LIMIT $4
+            "#,
+        )
+        .bind(player_id)
+        .bind(after_favorited_at)
+        .bind(after_pitch_id)
+        .bind(page.fetch_limit())
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(FavoriteControllerError::Sqlx)?;
+
+        let total = if page.include_total {
+            let total = sqlx::query_scalar(
+                r#"
+                SELECT
+                    COUNT(*)
+                FROM
+                    favorite_pitches
+                WHERE
+                    player_id = $1
+                "#,
+            )
+            .bind(player_id)
+            .fetch_one(model_manager.db())
+            .await
+            .map_err(FavoriteControllerError::Sqlx)?;
+            Some(total)
+        } else {
+            None
+        };
+
+        Ok(Paged::new(favorites, page.limit, total))
+    }
+
+    /// Counts how many players added the pitch to their favorites.
+    ///
+    /// When `owner_id` is set, pitches owned by someone else are reported as not found.
+    pub async fn count_pitch_favorites(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        owner_id: Option<Uuid>,
+    ) -> Result<i64, FavoriteControllerError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        favorite_pitches
+                    WHERE
+                        favorite_pitches.pitch_id = pitches.id
+                )
+            FROM
+                pitches
+            WHERE
+                pitches.id = $1
+            AND
+                ($2::UUID IS NULL OR pitches.owner_id = $2)
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(owner_id)
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(FavoriteControllerError::Sqlx)?
+        .ok_or(FavoriteControllerError::PitchNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::favorites::{controller::FavoriteController, errors::FavoriteControllerError},
+        models::{
+            ModelManager,
+            pagination::{PageRequest, decode_cursor},
+        },
+    };
+    use anyhow::Context;
+    use uuid::{Uuid, uuid};
+
+    #[tokio::test]
+    async fn test_get_favorite_pitches_ok() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let player_id = uuid!("00000000-0000-0000-0000-000000000001");
+        let favorites = FavoriteController::get_favorite_pitches(
+            &model_manager,
+            player_id,
+            &PageRequest::first(10),
+        )
+        .await
+        .context("failed while fetching favorites")?;
+
+        // check
+        let pitch_ids: Vec<_> = favorites
+            .items
+            .iter()
+            .map(|favorite| favorite.pitch_id)
+            .collect();
+        assert_eq!(
+            pitch_ids,
+            [
+                uuid!("00000000-0000-0000-0000-000000000006"),
+                uuid!("00000000-0000-0000-0000-000000000007"),
+            ]
+        );
+        assert_eq!(favorites.next_cursor, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_favorite_pitches_ok_next_page() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let player_id = uuid!("00000000-0000-0000-0000-000000000001");
+        let first_page = FavoriteController::get_favorite_pitches(
+            &model_manager,
+            player_id,
+            &PageRequest {
+                limit: 1,
+                after: None,
+                include_total: true,
+            },
+        )
+        .await
+        .context("failed while fetching the first page")?;
+
+        // exec
+        let cursor = first_page.next_cursor.context("first page has a cursor")?;
+        let second_page = FavoriteController::get_favorite_pitches(
+            &model_manager,
+            player_id,
+            &PageRequest {
+                limit: 1,
+                after: Some(decode_cursor(&cursor).expect("valid cursor")),
+                include_total: false,
+            },
+        )
+        .await
+        .context("failed while fetching the second page")?;
+
+        // check
+        assert_eq!(first_page.total, Some(2));
+        assert_eq!(
+            first_page.items[0].pitch_id,
+            uuid!("00000000-0000-0000-0000-000000000006")
+        );
+        assert_eq!(
+            second_page
+                .items
+                .iter()
+                .map(|favorite| favorite.pitch_id)
+                .collect::<Vec<_>>(),
+            [uuid!("00000000-0000-0000-0000-000000000007")]
+        );
+        assert_eq!(second_page.next_cursor, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_count_pitch_favorites_ok() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let pitch_id = uuid!("00000000-0000-0000-0000-000000000006");
+        let owner_id = uuid!("00000000-0000-0000-0000-000000000003");
+        let count =
+            FavoriteController::count_pitch_favorites(&model_manager, pitch_id, Some(owner_id))
+                .await
+                .context("failed while counting favorites")?;
+
+        // check
+        assert_eq!(count, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_count_pitch_favorites_err_not_owner() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let pitch_id = uuid!("00000000-0000-0000-0000-000000000006");
+        let other_business_id = uuid!("00000000-0000-0000-0000-000000000004");
+        let result = FavoriteController::count_pitch_favorites(
+            &model_manager,
+            pitch_id,
+            Some(other_business_id),
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(FavoriteControllerError::PitchNotFound)),
+            "result: {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_add_pitch_err_pitch_not_found() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let player_id = uuid!("00000000-0000-0000-0000-000000000002");
+        let result = FavoriteController::add_pitch(&model_manager, player_id, Uuid::new_v4()).await;
+
+        // check
+        assert!(
+            matches!(result, Err(FavoriteControllerError::PitchNotFound)),
+            "result: {result:?}"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use crate::models::{pagination::Keyset, tables::Sport};
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(FromRow)]
+pub struct FavoritePitch {
+    pub pitch_id: Uuid,
+    pub display_name: String,
+    pub sport: Sport,
+    pub favorited_at: DateTime<Utc>,
+}
+
+impl Keyset for FavoritePitch {
+    type Key = (DateTime<Utc>, Uuid);
+
+    fn key(&self) -> Self::Key {
+        (self.favorited_at, self.pitch_id)
+    }
+}
@@ -1,7 +1,69 @@
+mod availability;
+mod bookings;
+mod calendar_feeds;
+mod closures;
+mod facilities;
+mod favorites;
+mod notifications;
+mod opening_hours;
 mod pitches;
+mod players;
+mod pricing_rules;
+mod search;
+#[cfg(test)]
+mod test_utils;
+mod timeslot_rules;
+mod timeslots;
 mod users;
 
-pub use pitches::{controller::PitchController, errors::PitchControllerError};
+pub use availability::{controller::AvailabilityController, errors::AvailabilityControllerError};
+pub use bookings::{controller::BookingController, errors::BookingControllerError};
+pub use calendar_feeds::{
+    controller::CalendarFeedController, errors::CalendarFeedControllerError, models::FeedScope,
+};
+pub use closures::{
+    controller::ClosureController, errors::ClosureControllerError, models::InsertClosurePayload,
+};
+pub use facilities::{controller::FacilityController, errors::FacilityControllerError};
+pub use favorites::{controller::FavoriteController, errors::FavoriteControllerError};
+pub use notifications::{controller::NotificationController, errors::NotificationControllerError};
+pub use opening_hours::{
+    controller::OpeningHoursController,
+    errors::OpeningHoursControllerError,
+    models::{OpeningHours, OpeningHoursException, WeeklyOpeningHours},
+};
+pub use pitches::{
+    controller::PitchController,
+    errors::PitchControllerError,
+    models::{
+        InsertPitchPayload, LocationPayload, PitchActor, PitchFilters, PitchSort,
+        UpdatePitchPayload,
+    },
+};
+pub use players::{
+    controller::PlayerController,
+    errors::PlayerControllerError,
+    models::{ProfileViewer, UpdatePrivacySettingsPayload},
+};
+pub use pricing_rules::{
+    controller::PricingRuleController,
+    errors::PricingRuleControllerError,
+    models::{InsertPricingRulePayload, PriceAdjustment, PricedPeriod},
+};
+pub use search::{controller::SearchController, errors::SearchControllerError};
+pub use timeslot_rules::{
+    controller::TimeslotRuleController, errors::TimeslotRuleControllerError,
+    models::InsertTimeslotRulePayload,
+};
+pub use timeslots::{
+    controller::TimeslotController,
+    errors::TimeslotControllerError,
+    import::ImportFormat,
+    models::{
+        ImportAction, ImportRowError, InsertTimeslotPayload, PricedTimeslot, TimeslotFilters,
+        TimeslotOperation, TimeslotOperationOutcome, UpdateTimeslotPayload,
+    },
+};
 pub use users::{
     controller::UserController,
     errors::UserControllerError,
@@ -0,0 +1,73 @@
+use crate::{
+    controllers::notifications::{errors::NotificationControllerError, models::Notification},
+    models::{
+        ModelManager,
+        pagination::{Keyset, PageRequest, Paged},
+    },
+};
+use uuid::Uuid;
+
+pub struct NotificationController;
+
+impl NotificationController {
+    /// Returns the notifications of a user, most recent first
+    pub async fn get_notifications(
+        model_manager: &ModelManager,
+        user_id: Uuid,
+        page: &PageRequest<<Notification as Keyset>::Key>,
+    ) -> Result<Paged<Notification>, NotificationControllerError> {
+        let (after_created_at, after_id) = page.after.unzip();
+
+        let notifications = sqlx::query_as(
+            r#"
+            SELECT
+                id,
+                message,
+                created_at
+            FROM
+                notifications
+            WHERE
+                user_id = $1
+            AND (
+                $2::TIMESTAMPTZ IS NULL
+            OR
+                created_at < $2
+            OR
+                (created_at = $2 AND id > $3)
+            )
+            ORDER BY
+                created_at DESC, id
+            LIMIT $4
+            "#,
+        )
+        .bind(user_id)
+        .bind(after_created_at)
+        .bind(after_id)
+        .bind(page.fetch_limit())
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(NotificationControllerError::Sqlx)?;
+
+        let total = if page.include_total {
+            let total = sqlx::query_scalar(
+                r#"
+                SELECT
+                    COUNT(*)
+                FROM
+                    notifications
+                WHERE
+                    user_id = $1
+                "#,
+            )
+            .bind(user_id)
+            .fetch_one(model_manager.db())
+            .await
+            .map_err(NotificationControllerError::Sqlx)?;
+            Some(total)
+        } else {
+            None
+        };
+
+        Ok(Paged::new(notifications, page.limit, total))
+    }
+}
@@ -0,0 +1,8 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum NotificationControllerError {
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(NotificationControllerError);
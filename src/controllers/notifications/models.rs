@@ -0,0 +1,20 @@
+use crate::models::pagination::Keyset;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(FromRow)]
+pub struct Notification {
+    pub id: Uuid,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Keyset for Notification {
+    type Key = (DateTime<Utc>, Uuid);
+
+    fn key(&self) -> Self::Key {
+        (self.created_at, self.id)
+    }
+}
@@ -0,0 +1,484 @@
+use crate::{
+    controllers::{
+        PitchActor,
+        opening_hours::{
+            errors::OpeningHoursControllerError,
+            models::{
+                EffectiveDay, EffectiveOpeningHours, OpeningHours, OpeningHoursException,
+                OpeningInterval, WeeklyOpeningHours,
+            },
+        },
+    },
+    models::{ModelManager, local_time::with_local_offset, tables::PitchStatus},
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub struct OpeningHoursController;
+
+#[derive(FromRow)]
+struct EffectiveHoursRow {
+    date: NaiveDate,
+    opens_at: Option<DateTime<Utc>>,
+    local_opens_at: Option<NaiveDateTime>,
+    closes_at: Option<DateTime<Utc>>,
+    local_closes_at: Option<NaiveDateTime>,
+    reason: Option<String>,
+}
+
+impl OpeningHoursController {
+    /// Returns the weekly hours and the exceptions that haven't passed yet.
+    ///
+    /// Pitches that aren't published are only shown to their owner and to admins.
+    pub async fn get_opening_hours(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        viewer: Option<PitchActor>,
+    ) -> Result<OpeningHours, OpeningHoursControllerError> {
+        let timezone = Self::get_visible_pitch_timezone(model_manager, pitch_id, viewer).await?;
+
+        let weekly = sqlx::query_as::<_, WeeklyOpeningHours>(
+            r#"
+            SELECT
+                weekday,
+                TO_CHAR(opens_at, 'HH24:MI') AS opens_at,
+                TO_CHAR(closes_at, 'HH24:MI') AS closes_at
+            FROM
+                pitch_opening_hours
+            WHERE
+                pitch_id = $1
+            ORDER BY
+                weekday, opens_at
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(OpeningHoursControllerError::Sqlx)?;
+
+        let exceptions = sqlx::query_as::<_, OpeningHoursException>(
+            r#"
+            SELECT
+                date,
+                TO_CHAR(opens_at, 'HH24:MI') AS opens_at,
+                TO_CHAR(closes_at, 'HH24:MI') AS closes_at,
+                reason
+            FROM
+                pitch_opening_hour_exceptions
+            WHERE
+                pitch_id = $1
+            AND
+                date >= (NOW() AT TIME ZONE $2)::DATE
+            ORDER BY
+                date
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(&timezone)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(OpeningHoursControllerError::Sqlx)?;
+
+        Ok(OpeningHours {
+            timezone,
+            weekly,
+            exceptions,
+        })
+    }
+
+    /// Replaces the timezone, the weekly hours and all the exceptions of the pitch.
+    ///
+    /// Existing timeslots are kept even if they fall outside the new hours, only timeslots that
+    /// are created or moved afterwards are checked against them.
+    pub async fn set_opening_hours(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        actor: PitchActor,
+        opening_hours: OpeningHours,
+    ) -> Result<(), OpeningHoursControllerError> {
+        let is_known_timezone: bool = sqlx::query_scalar(
+            r#"
+            SELECT
+                EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1)
+            "#,
+        )
+        .bind(&opening_hours.timezone)
+        .fetch_one(model_manager.db())
+        .await
+        .map_err(OpeningHoursControllerError::Sqlx)?;
+
+        if !is_known_timezone {
+            return Err(OpeningHoursControllerError::UnknownTimezone);
+        }
+
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(OpeningHoursControllerError::Sqlx)?;
+
+        sqlx::query(
+            r#"
+            UPDATE
+                pitches
+            SET
+                timezone = $3
+            WHERE
+                id = $1
+            AND
+                ($2::UUID IS NULL OR owner_id = $2)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(actor.owner_id())
+        .bind(&opening_hours.timezone)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(OpeningHoursControllerError::Sqlx)?
+        .ok_or(OpeningHoursControllerError::PitchNotFound)?;
+
+        sqlx::query("DELETE FROM pitch_opening_hours WHERE pitch_id = $1")
+            .bind(pitch_id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(OpeningHoursControllerError::Sqlx)?;
+
+        sqlx::query("DELETE FROM pitch_opening_hour_exceptions WHERE pitch_id = $1")
+            .bind(pitch_id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(OpeningHoursControllerError::Sqlx)?;
+
+        for hours in opening_hours.weekly {
+            sqlx::query(
+                r#"
+                INSERT INTO pitch_opening_hours
+                    (pitch_id, weekday, opens_at, closes_at)
+                VALUES
+                    ($1, $2, $3::TIME, $4::TIME)
+                "#,
+            )
+            .bind(pitch_id)
+            .bind(hours.weekday)
+            .bind(hours.opens_at)
+            .bind(hours.closes_at)
+            .execute(&mut *transaction)
+            .await
+            .map_err(OpeningHoursControllerError::Sqlx)?;
+        }
+
+        for exception in opening_hours.exceptions {
+            sqlx::query(
+                r#"
+                INSERT INTO pitch_opening_hour_exceptions
+                    (pitch_id, date, opens_at, closes_at, reason)
+                VALUES
+                    ($1, $2, $3::TIME, $4::TIME, $5)
+                "#,
+            )
+            .bind(pitch_id)
+            .bind(exception.date)
+            .bind(exception.opens_at)
+            .bind(exception.closes_at)
+            .bind(exception.reason)
+            .execute(&mut *transaction)
+            .await
+            .map_err(OpeningHoursControllerError::Sqlx)?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(OpeningHoursControllerError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Returns the opening intervals of every day from `from` to `to` (both included), after
+    /// applying the exceptions to the weekly hours.
+    ///
+    /// Pitches that aren't published are only shown to their owner and to admins.
+    pub async fn get_effective_opening_hours(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        viewer: Option<PitchActor>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<EffectiveOpeningHours, OpeningHoursControllerError> {
+        let timezone = Self::get_visible_pitch_timezone(model_manager, pitch_id, viewer).await?;
+
+        let rows = sqlx::query_as::<_, EffectiveHoursRow>(
+            r#"
+            SELECT
+                date,
+                opens_at,
+                opens_at AT TIME ZONE $4 AS local_opens_at,
+                closes_at,
+                closes_at AT TIME ZONE $4 AS local_closes_at,
+                reason
+            FROM
+                effective_opening_hours($1, $2, $3)
+            ORDER BY
+                date, opens_at
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(from)
+        .bind(to)
+        .bind(&timezone)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(OpeningHoursControllerError::Sqlx)?;
+
+        let mut days: Vec<EffectiveDay> = from
+            .iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| EffectiveDay {
+                date,
+                intervals: Vec::new(),
+                reason: None,
+            })
+            .collect();
+
+        for row in rows {
+            let Some(day) = days.iter_mut().find(|day| day.date == row.date) else {
+                continue;
+            };
+            day.reason = row.reason.or(day.reason.take());
+
+            if let (Some(opens_at), Some(local_opens_at), Some(closes_at), Some(local_closes_at)) = (
+                row.opens_at,
+                row.local_opens_at,
+                row.closes_at,
+                row.local_closes_at,
+            ) {
+                day.intervals.push(OpeningInterval {
+                    opens_at: with_local_offset(opens_at, local_opens_at),
+                    closes_at: with_local_offset(closes_at, local_closes_at),
+                });
+            }
+        }
+
+        Ok(EffectiveOpeningHours { timezone, days })
+    }
+
+    async fn get_visible_pitch_timezone(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        viewer: Option<PitchActor>,
+    ) -> Result<String, OpeningHoursControllerError> {
+        let (owner_id, status, timezone): (Uuid, PitchStatus, String) = sqlx::query_as(
+            r#"
+            SELECT
+                owner_id, status, timezone
+            FROM
+                pitches
+            WHERE
+                id = $1
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(OpeningHoursControllerError::Sqlx)?
+        .ok_or(OpeningHoursControllerError::PitchNotFound)?;
+
+        let can_manage = match viewer {
+            Some(PitchActor::Admin(_)) => true,
+            Some(PitchActor::Owner(viewer_id)) => viewer_id == owner_id,
+            None => false,
+        };
+        if !can_manage && status != PitchStatus::Published {
+            return Err(OpeningHoursControllerError::PitchNotFound);
+        }
+
+        Ok(timezone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            PitchActor,
+            opening_hours::{
+                controller::OpeningHoursController,
+                errors::OpeningHoursControllerError,
+                models::{OpeningHours, OpeningHoursException, WeeklyOpeningHours},
+            },
+            test_utils::{self, delete_business},
+        },
+        models::{
+            ModelManager,
+            tables::{PitchStatus, Weekday},
+        },
+    };
+    use anyhow::Context;
+    use chrono::{DateTime, NaiveDate};
+    use uuid::Uuid;
+
+    /// Returns the ids of a new business and of its only pitch, open on mondays from 08:00 to
+    /// midnight in Dubai, and closed on 2030-01-07
+    async fn insert_business_with_pitch(
+        model_manager: &ModelManager,
+    ) -> anyhow::Result<(Uuid, Uuid)> {
+        let (business_id, pitch_id) = test_utils::insert_business_with_pitch(
+            model_manager,
+            "opening_hours_pitch",
+            PitchStatus::Published,
+        )
+        .await?;
+
+        let opening_hours = OpeningHours {
+            timezone: "Asia/Dubai".to_string(),
+            weekly: vec![WeeklyOpeningHours {
+                weekday: Weekday::Monday,
+                opens_at: "08:00".parse()?,
+                closes_at: "24:00".parse()?,
+            }],
+            exceptions: vec![OpeningHoursException {
+                date: NaiveDate::from_ymd_opt(2030, 1, 7).context("invalid date")?,
+                opens_at: None,
+                closes_at: None,
+                reason: Some("new year holiday".to_string()),
+            }],
+        };
+        OpeningHoursController::set_opening_hours(
+            model_manager,
+            pitch_id,
+            PitchActor::Owner(business_id),
+            opening_hours,
+        )
+        .await
+        .context("failed while setting opening hours")?;
+
+        Ok((business_id, pitch_id))
+    }
+
+    #[tokio::test]
+    async fn test_get_effective_opening_hours_ok_exception_replaces_weekly_hours()
+    -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) = insert_business_with_pitch(&model_manager).await?;
+
+        // exec
+        let effective_hours = OpeningHoursController::get_effective_opening_hours(
+            &model_manager,
+            pitch_id,
+            None,
+            NaiveDate::from_ymd_opt(2030, 1, 7).context("invalid date")?,
+            NaiveDate::from_ymd_opt(2030, 1, 14).context("invalid date")?,
+        )
+        .await
+        .context("failed while getting effective opening hours")?;
+
+        // check
+        assert_eq!(effective_hours.timezone, "Asia/Dubai");
+        assert_eq!(effective_hours.days.len(), 8);
+        let holiday = &effective_hours.days[0];
+        assert!(holiday.intervals.is_empty());
+        assert_eq!(holiday.reason.as_deref(), Some("new year holiday"));
+        assert!(
+            effective_hours.days[1..7]
+                .iter()
+                .all(|day| day.intervals.is_empty())
+        );
+        let monday = &effective_hours.days[7];
+        assert_eq!(monday.intervals.len(), 1);
+        assert_eq!(
+            monday.intervals[0].opens_at.to_rfc3339(),
+            "2030-01-14T08:00:00+04:00"
+        );
+        assert_eq!(
+            monday.intervals[0].closes_at.to_rfc3339(),
+            "2030-01-15T00:00:00+04:00"
+        );
+        assert!(monday.reason.is_none());
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_opening_hours_err_unknown_timezone() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) = insert_business_with_pitch(&model_manager).await?;
+
+        // exec
+        let opening_hours = OpeningHours {
+            timezone: "Mars/Olympus_Mons".to_string(),
+            weekly: Vec::new(),
+            exceptions: Vec::new(),
+        };
+        let result = OpeningHoursController::set_opening_hours(
+            &model_manager,
+            pitch_id,
+            PitchActor::Owner(business_id),
+            opening_hours,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(OpeningHoursControllerError::UnknownTimezone)),
+            "result: {result:?}"
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_timeslot_err_outside_opening_hours() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) = insert_business_with_pitch(&model_manager).await?;
+        let insert_timeslot = async |starts_at: &str, ends_at: &str| {
+            sqlx::query(
+                "INSERT INTO timeslots (pitch_id, time_range) VALUES ($1, TSTZRANGE($2, $3))",
+            )
+            .bind(pitch_id)
+            .bind(DateTime::parse_from_rfc3339(starts_at).unwrap())
+            .bind(DateTime::parse_from_rfc3339(ends_at).unwrap())
+            .execute(model_manager.db())
+            .await
+        };
+
+        // exec
+        let open = insert_timeslot("2030-01-14T22:00:00+04:00", "2030-01-15T00:00:00+04:00").await;
+        let holiday =
+            insert_timeslot("2030-01-07T10:00:00+04:00", "2030-01-07T11:00:00+04:00").await;
+        let past_midnight =
+            insert_timeslot("2030-01-14T23:00:00+04:00", "2030-01-15T01:00:00+04:00").await;
+
+        // check
+        assert!(open.is_ok(), "result: {open:?}");
+        for result in [holiday, past_midnight] {
+            assert!(
+                matches!(
+                    &result,
+                    Err(sqlx::Error::Database(err))
+                        if err.constraint() == Some("timeslots_within_opening_hours")
+                ),
+                "result: {result:?}"
+            );
+        }
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+}
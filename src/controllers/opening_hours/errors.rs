@@ -0,0 +1,10 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum OpeningHoursControllerError {
+    PitchNotFound,
+    UnknownTimezone,
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(OpeningHoursControllerError);
@@ -0,0 +1,55 @@
+use crate::models::{tables::Weekday, time_of_day::TimeOfDay};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use sqlx::FromRow;
+
+/// Hours repeated every week, a day can have several intervals
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct WeeklyOpeningHours {
+    pub weekday: Weekday,
+    pub opens_at: TimeOfDay,
+    pub closes_at: TimeOfDay,
+}
+
+/// Replaces the weekly hours on its date, e.g. for a holiday
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct OpeningHoursException {
+    pub date: NaiveDate,
+    /// both are `None` when the pitch is closed all day
+    pub opens_at: Option<TimeOfDay>,
+    pub closes_at: Option<TimeOfDay>,
+    pub reason: Option<String>,
+}
+
+/// All the times are wall clock times in `timezone`
+#[cfg_attr(test, derive(Debug))]
+pub struct OpeningHours {
+    /// IANA name, e.g. `Asia/Dubai`
+    pub timezone: String,
+    /// pitches without weekly hours are open all day, every day
+    pub weekly: Vec<WeeklyOpeningHours>,
+    pub exceptions: Vec<OpeningHoursException>,
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub struct EffectiveOpeningHours {
+    pub timezone: String,
+    pub days: Vec<EffectiveDay>,
+}
+
+/// The pitch is closed on days without intervals
+#[cfg_attr(test, derive(Debug))]
+pub struct EffectiveDay {
+    pub date: NaiveDate,
+    pub intervals: Vec<OpeningInterval>,
+    /// set when an exception replaced the weekly hours
+    pub reason: Option<String>,
+}
+
+/// With the offset of the pitch's timezone at that time
+#[cfg_attr(test, derive(Debug))]
+pub struct OpeningInterval {
+    pub opens_at: DateTime<FixedOffset>,
+    pub closes_at: DateTime<FixedOffset>,
+}
@@ -1,45 +1,2007 @@
 use crate::{
-    controllers::pitches::errors::PitchControllerError,
-    models::{ModelManager, tables::Pitch},
+    configs::config,
+    controllers::pitches::{
+        errors::PitchControllerError,
+        models::{
+            ChangedPitch, InsertPitchPayload, LocationPayload, PitchActor, PitchFilters,
+            PitchListItem, PitchSort, UpdatePitchPayload,
+        },
+        relevance::push_relevance_ctes,
+    },
+    models::{
+        ModelManager,
+        pagination::{Keyset, PageRequest, Paged},
+        price::{Currency, Price},
+        tables::{PitchAdminAction, PitchStatus, Sport},
+    },
 };
+use sqlx::{PgConnection, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 pub struct PitchController;
 
 impl PitchController {
+    /// Only published pitches are listed
     pub async fn get_all_pitches(
         model_manager: &ModelManager,
-    ) -> Result<Vec<Pitch>, PitchControllerError> {
-        sqlx::query_as(
-            "
+        viewer_id: Option<Uuid>,
+        filters: &PitchFilters,
+        page: &PageRequest<<PitchListItem as Keyset>::Key>,
+    ) -> Result<Paged<PitchListItem>, PitchControllerError> {
+        Self::get_pitches(model_manager, viewer_id, None, false, filters, page).await
+    }
+
+    pub async fn get_all_pitches_including_unpublished(
+        model_manager: &ModelManager,
+        filters: &PitchFilters,
+        page: &PageRequest<<PitchListItem as Keyset>::Key>,
+    ) -> Result<Paged<PitchListItem>, PitchControllerError> {
+        Self::get_pitches(model_manager, None, None, true, filters, page).await
+    }
+
+    pub async fn get_pitches_by_business_id(
+        business_id: Uuid,
+        model_manager: &ModelManager,
+        filters: &PitchFilters,
+        page: &PageRequest<<PitchListItem as Keyset>::Key>,
+    ) -> Result<Paged<PitchListItem>, PitchControllerError> {
+        // businesses can't have favorites
+        Self::get_pitches(model_manager, None, Some(business_id), true, filters, page).await
+    }
+
+    /// Returns the names of all amenities a pitch can have, sorted
+    pub async fn get_amenities(
+        model_manager: &ModelManager,
+    ) -> Result<Vec<String>, PitchControllerError> {
+        sqlx::query_scalar(
+            r#"
             SELECT
-                id, owner_id, display_name, sport
+                name
             FROM
-                pitches
-            ",
+                amenities
+            ORDER BY
+                name
+            "#,
         )
         .fetch_all(model_manager.db())
         .await
         .map_err(PitchControllerError::Sqlx)
     }
 
-    pub async fn get_pitches_by_business_id(
-        business_id: Uuid,
+    /// Owners can only create pitches for themselves
+    pub async fn create_pitch(
+        model_manager: &ModelManager,
+        actor: PitchActor,
+        owner_id: Uuid,
+        pitch: InsertPitchPayload,
+    ) -> Result<Uuid, PitchControllerError> {
+        if actor
+            .owner_id()
+            .is_some_and(|actor_id| actor_id != owner_id)
+        {
+            return Err(PitchControllerError::NotOwner);
+        }
+
+        Self::check_timezone(model_manager, &pitch.timezone).await?;
+
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(PitchControllerError::Sqlx)?;
+
+        let result = sqlx::query_as::<_, ChangedPitch>(
+            r#"
+            INSERT INTO pitches
+                (
+                    owner_id,
+                    display_name,
+                    sport,
+                    other_sports,
+                    price_per_hour_cents,
+                    currency,
+                    image_url,
+                    surface,
+                    environment,
+                    description,
+                    max_players,
+                    status,
+                    timezone
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING
+                id, owner_id, display_name
+            "#,
+        )
+        .bind(owner_id)
+        .bind(pitch.display_name)
+        .bind(pitch.sport)
+        .bind(other_sports(pitch.other_sports, Some(pitch.sport)))
+        .bind(price_per_hour_cents(pitch.price_per_hour)?)
+        .bind(pitch.price_per_hour.currency())
+        .bind(pitch.image_url)
+        .bind(pitch.surface)
+        .bind(pitch.environment)
+        .bind(pitch.description)
+        .bind(pitch.max_players)
+        .bind(pitch.status)
+        .bind(pitch.timezone)
+        .fetch_one(&mut *transaction)
+        .await;
+
+        let created_pitch = match result {
+            Ok(created_pitch) => created_pitch,
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("pitches_owner_id_fkey") =>
+            {
+                return Err(PitchControllerError::OwnerNotFound);
+            }
+            Err(err) => return Err(Self::map_write_error(err)),
+        };
+
+        Self::set_pitch_amenities(&mut transaction, created_pitch.id, pitch.amenities).await?;
+
+        if let Some(location) = pitch.location {
+            Self::set_pitch_location(&mut transaction, created_pitch.id, location).await?;
+        }
+
+        Self::record_admin_action(
+            &mut transaction,
+            actor,
+            PitchAdminAction::Create,
+            &created_pitch,
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(PitchControllerError::Sqlx)?;
+
+        Ok(created_pitch.id)
+    }
+
+    /// Pitches of other businesses are reported as not found when the actor is an owner.
+    ///
+    /// Changing the timezone keeps the instants of the existing timeslots, the timeslot rules
+    /// generate the next ones at the same wall clock times in the new timezone.
+    pub async fn update_pitch_by_id(
         model_manager: &ModelManager,
-    ) -> Result<Vec<Pitch>, PitchControllerError> {
-        sqlx::query_as(
-            "
+        id: Uuid,
+        actor: PitchActor,
+        new_pitch: UpdatePitchPayload,
+    ) -> Result<(), PitchControllerError> {
+        if let Some(timezone) = &new_pitch.timezone {
+            Self::check_timezone(model_manager, timezone).await?;
+        }
+        if let Some(price_per_hour) = new_pitch.price_per_hour {
+            Self::check_currency(model_manager, id, actor, price_per_hour.currency()).await?;
+        }
+
+        let mut query_builder = QueryBuilder::new("UPDATE pitches SET ");
+        let mut separated_query_builder = query_builder.separated(", ");
+
+        let mut is_updated = false;
+
+        if let Some(display_name) = new_pitch.display_name {
+            is_updated = true;
+            separated_query_builder
+                .push("display_name = ")
+                .push_bind_unseparated(display_name);
+        }
+
+        if let Some(sport) = new_pitch.sport {
+            is_updated = true;
+            separated_query_builder
+                .push("sport = ")
+                .push_bind_unseparated(sport);
+        }
+
+        // the main sport is never listed in the other sports
+        match (new_pitch.other_sports, new_pitch.sport) {
+            (Some(new_other_sports), sport) => {
+                is_updated = true;
+                separated_query_builder
+                    .push("other_sports = array_remove(")
+                    .push_bind_unseparated(other_sports(new_other_sports, sport))
+                    .push_unseparated("::sport[], COALESCE(")
+                    .push_bind_unseparated(sport)
+                    .push_unseparated(", sport))");
+            }
+            (None, Some(sport)) => {
+                separated_query_builder
+                    .push("other_sports = array_remove(other_sports, ")
+                    .push_bind_unseparated(sport)
+                    .push_unseparated(")");
+            }
+            (None, None) => {}
+        }
+
+        if let Some(price_per_hour) = new_pitch.price_per_hour {
+            is_updated = true;
+            separated_query_builder
+                .push("price_per_hour_cents = ")
+                .push_bind_unseparated(price_per_hour_cents(price_per_hour)?);
+        }
+
+        if let Some(image_url) = new_pitch.image_url {
+            is_updated = true;
+            separated_query_builder
+                .push("image_url = ")
+                .push_bind_unseparated(image_url);
+        }
+
+        if let Some(surface) = new_pitch.surface {
+            is_updated = true;
+            separated_query_builder
+                .push("surface = ")
+                .push_bind_unseparated(surface);
+        }
+
+        if let Some(environment) = new_pitch.environment {
+            is_updated = true;
+            separated_query_builder
+                .push("environment = ")
+                .push_bind_unseparated(environment);
+        }
+
+        if let Some(description) = new_pitch.description {
+            is_updated = true;
+            separated_query_builder
+                .push("description = ")
+                .push_bind_unseparated(description);
+        }
+
+        if let Some(max_players) = new_pitch.max_players {
+            is_updated = true;
+            separated_query_builder
+                .push("max_players = ")
+                .push_bind_unseparated(max_players);
+        }
+
+        if let Some(timezone) = new_pitch.timezone {
+            is_updated = true;
+            separated_query_builder
+                .push("timezone = ")
+                .push_bind_unseparated(timezone);
+        }
+
+        // the row is still updated when only the amenities change, or when nothing changes, so that
+        // pitches the business doesn't own are reported as not found
+        if !is_updated {
+            separated_query_builder.push("id = id");
+        }
+
+        query_builder.push(" WHERE id = ").push_bind(id);
+        if let Some(owner_id) = actor.owner_id() {
+            query_builder.push(" AND owner_id = ").push_bind(owner_id);
+        }
+        query_builder.push(" RETURNING id, owner_id, display_name");
+
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(PitchControllerError::Sqlx)?;
+
+        let updated_pitch = query_builder
+            .build_query_as::<ChangedPitch>()
+            .fetch_optional(&mut *transaction)
+            .await
+            .map_err(Self::map_write_error)?
+            .ok_or(PitchControllerError::PitchNotFound)?;
+
+        if let Some(amenities) = new_pitch.amenities {
+            sqlx::query(
+                r#"
+                DELETE FROM
+                    pitch_amenities
+                WHERE
+                    pitch_id = $1
+                "#,
+            )
+            .bind(id)
+            .execute(&mut *transaction)
+            .await
+            .map_err(PitchControllerError::Sqlx)?;
+
+            Self::set_pitch_amenities(&mut transaction, id, amenities).await?;
+        }
+
+        if let Some(location) = new_pitch.location {
+            Self::set_pitch_location(&mut transaction, id, location).await?;
+        }
+
+        Self::record_admin_action(
+            &mut transaction,
+            actor,
+            PitchAdminAction::Update,
+            &updated_pitch,
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(PitchControllerError::Sqlx)
+    }
+
+    /// Moves the pitch through its lifecycle, see [`PitchStatus::can_change_to`].
+    ///
+    /// Pitches of other businesses are reported as not found when the actor is an owner.
+    pub async fn set_pitch_status_by_id(
+        model_manager: &ModelManager,
+        id: Uuid,
+        actor: PitchActor,
+        status: PitchStatus,
+    ) -> Result<(), PitchControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(PitchControllerError::Sqlx)?;
+
+        let current_status: PitchStatus = sqlx::query_scalar(
+            r#"
             SELECT
-                id, owner_id, display_name, sport
+                status
             FROM
                 pitches
             WHERE
-                owner_id = $1
-            ",
+                id = $1
+            AND
+                ($2::UUID IS NULL OR owner_id = $2)
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .bind(actor.owner_id())
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(PitchControllerError::Sqlx)?
+        .ok_or(PitchControllerError::PitchNotFound)?;
+
+        if !current_status.can_change_to(status) {
+            return Err(PitchControllerError::InvalidStatusTransition);
+        }
+
+        let updated_pitch = sqlx::query_as(
+            r#"
+            UPDATE
+                pitches
+            SET
+                status = $2
+            WHERE
+                id = $1
+            RETURNING
+                id, owner_id, display_name
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(PitchControllerError::Sqlx)?;
+
+        let action = match (current_status, status) {
+            (PitchStatus::Hidden, PitchStatus::Published) => PitchAdminAction::Unhide,
+            (_, PitchStatus::Published) => PitchAdminAction::Publish,
+            (_, PitchStatus::Hidden) => PitchAdminAction::Hide,
+            (_, PitchStatus::Archived) => PitchAdminAction::Archive,
+            (_, PitchStatus::Draft) => PitchAdminAction::Restore,
+        };
+        Self::record_admin_action(&mut transaction, actor, action, &updated_pitch).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(PitchControllerError::Sqlx)
+    }
+
+    /// Pitches of other businesses are reported as not found when the actor is an owner
+    pub async fn delete_pitch_by_id(
+        model_manager: &ModelManager,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), PitchControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(PitchControllerError::Sqlx)?;
+
+        // locations aren't shared between pitches, so the pitch's location goes with it
+        let result = sqlx::query_as::<_, ChangedPitch>(
+            r#"
+            WITH deleted_pitch AS (
+                DELETE FROM
+                    pitches
+                WHERE
+                    id = $1
+                AND
+                    ($2::UUID IS NULL OR owner_id = $2)
+                RETURNING
+                    id, owner_id, display_name, location_id
+            ), deleted_location AS (
+                DELETE FROM
+                    locations
+                WHERE
+                    id IN (SELECT location_id FROM deleted_pitch)
+            )
+            SELECT
+                id, owner_id, display_name
+            FROM
+                deleted_pitch
+            "#,
+        )
+        .bind(id)
+        .bind(actor.owner_id())
+        .fetch_optional(&mut *transaction)
+        .await;
+
+        let deleted_pitch = match result {
+            Ok(Some(deleted_pitch)) => deleted_pitch,
+            Ok(None) => return Err(PitchControllerError::PitchNotFound),
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("bookings_timeslot_id_fkey") =>
+            {
+                return Err(PitchControllerError::PitchHasBookings);
+            }
+            Err(err) => return Err(PitchControllerError::Sqlx(err)),
+        };
+
+        Self::record_admin_action(
+            &mut transaction,
+            actor,
+            PitchAdminAction::Delete,
+            &deleted_pitch,
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(PitchControllerError::Sqlx)
+    }
+
+    /// Links the pitch to the given amenities, names missing from the catalog are rejected
+    async fn set_pitch_amenities(
+        connection: &mut PgConnection,
+        pitch_id: Uuid,
+        mut amenities: Vec<String>,
+    ) -> Result<(), PitchControllerError> {
+        amenities.sort_unstable();
+        amenities.dedup();
+
+        let rows_affected = sqlx::query(
+            r#"
+            INSERT INTO pitch_amenities
+                (pitch_id, amenity_id)
+            SELECT
+                $1, id
+            FROM
+                amenities
+            WHERE
+                name = ANY($2)
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(&amenities)
+        .execute(connection)
+        .await
+        .map_err(PitchControllerError::Sqlx)?
+        .rows_affected();
+
+        if rows_affected != amenities.len() as u64 {
+            return Err(PitchControllerError::UnknownAmenity);
+        }
+
+        Ok(())
+    }
+
+    /// Updates the pitch's location in place, or creates one if the pitch has none yet
+    async fn set_pitch_location(
+        connection: &mut PgConnection,
+        pitch_id: Uuid,
+        location: LocationPayload,
+    ) -> Result<(), PitchControllerError> {
+        let rows_affected = sqlx::query(
+            r#"
+            UPDATE
+                locations
+            SET
+                address_line_1 = $2,
+                address_line_2 = $3,
+                city = $4,
+                postal_code = $5,
+                country = $6,
+                latitude = $7,
+                longitude = $8
+            FROM
+                pitches
+            WHERE
+                pitches.id = $1
+            AND
+                locations.id = pitches.location_id
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(&location.address_line_1)
+        .bind(&location.address_line_2)
+        .bind(&location.city)
+        .bind(&location.postal_code)
+        .bind(&location.country)
+        .bind(location.latitude)
+        .bind(location.longitude)
+        .execute(&mut *connection)
+        .await
+        .map_err(Self::map_location_error)?
+        .rows_affected();
+
+        if rows_affected == 1 {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            WITH new_location AS (
+                INSERT INTO locations
+                    (address_line_1, address_line_2, city, postal_code, country, latitude, longitude)
+                VALUES
+                    ($2, $3, $4, $5, $6, $7, $8)
+                RETURNING
+                    id
+            )
+            UPDATE
+                pitches
+            SET
+                location_id = (SELECT id FROM new_location)
+            WHERE
+                id = $1
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(location.address_line_1)
+        .bind(location.address_line_2)
+        .bind(location.city)
+        .bind(location.postal_code)
+        .bind(location.country)
+        .bind(location.latitude)
+        .bind(location.longitude)
+        .execute(connection)
+        .await
+        .map_err(Self::map_location_error)?;
+
+        Ok(())
+    }
+
+    fn map_location_error(err: sqlx::Error) -> PitchControllerError {
+        match err {
+            sqlx::Error::Database(err)
+                if matches!(
+                    err.constraint(),
+                    Some("locations_latitude_check" | "locations_longitude_check")
+                ) =>
+            {
+                PitchControllerError::InvalidCoordinates
+            }
+            err => PitchControllerError::Sqlx(err),
+        }
+    }
+
+    /// Records what an admin did to a pitch and lets the owning business know about it.
+    ///
+    /// No-op for owners.
+    async fn record_admin_action(
+        connection: &mut PgConnection,
+        actor: PitchActor,
+        action: PitchAdminAction,
+        pitch: &ChangedPitch,
+    ) -> Result<(), PitchControllerError> {
+        let PitchActor::Admin(admin_id) = actor else {
+            return Ok(());
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO pitch_admin_actions
+                (admin_id, pitch_id, owner_id, action)
+            VALUES
+                ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(admin_id)
+        .bind(pitch.id)
+        .bind(pitch.owner_id)
+        .bind(action)
+        .execute(&mut *connection)
+        .await
+        .map_err(PitchControllerError::Sqlx)?;
+
+        let verb = match action {
+            PitchAdminAction::Create => "created",
+            PitchAdminAction::Update => "updated",
+            PitchAdminAction::Hide => "hid",
+            PitchAdminAction::Unhide => "unhid",
+            PitchAdminAction::Delete => "deleted",
+            PitchAdminAction::Publish => "published",
+            PitchAdminAction::Archive => "archived",
+            PitchAdminAction::Restore => "restored",
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO notifications
+                (user_id, message)
+            VALUES
+                ($1, $2)
+            "#,
+        )
+        .bind(pitch.owner_id)
+        .bind(format!(
+            "an admin {verb} your pitch \"{}\"",
+            pitch.display_name
+        ))
+        .execute(&mut *connection)
+        .await
+        .map_err(PitchControllerError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// The timezones are the IANA ones the database knows
+    async fn check_timezone(
+        model_manager: &ModelManager,
+        timezone: &str,
+    ) -> Result<(), PitchControllerError> {
+        let is_known_timezone: bool = sqlx::query_scalar(
+            r#"
+            SELECT
+                EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1)
+            "#,
+        )
+        .bind(timezone)
+        .fetch_one(model_manager.db())
+        .await
+        .map_err(PitchControllerError::Sqlx)?;
+
+        if !is_known_timezone {
+            return Err(PitchControllerError::UnknownTimezone);
+        }
+
+        Ok(())
+    }
+
+    /// The currency of a pitch is set when it's created, prices can't be moved to another one.
+    ///
+    /// Missing pitches are left to the update to report.
+    async fn check_currency(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        actor: PitchActor,
+        currency: Currency,
+    ) -> Result<(), PitchControllerError> {
+        let pitch_currency: Option<Currency> = sqlx::query_scalar(
+            r#"
+            SELECT
+                currency
+            FROM
+                pitches
+            WHERE
+                id = $1
+            AND
+                ($2::UUID IS NULL OR owner_id = $2)
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(actor.owner_id())
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(PitchControllerError::Sqlx)?;
+
+        if pitch_currency.is_some_and(|pitch_currency| pitch_currency != currency) {
+            return Err(PitchControllerError::PriceCurrencyMismatch);
+        }
+
+        Ok(())
+    }
+
+    fn map_write_error(err: sqlx::Error) -> PitchControllerError {
+        match err {
+            sqlx::Error::Database(err)
+                if err.constraint() == Some("pitches_owner_id_display_name_key") =>
+            {
+                PitchControllerError::DisplayNameAlreadyExists
+            }
+            sqlx::Error::Database(err)
+                if err.constraint() == Some("pitches_price_per_hour_cents_check") =>
+            {
+                PitchControllerError::NegativePrice
+            }
+            sqlx::Error::Database(err) if err.constraint() == Some("pitches_max_players_check") => {
+                PitchControllerError::InvalidMaxPlayers
+            }
+            err => PitchControllerError::Sqlx(err),
+        }
+    }
+
+    async fn get_pitches(
+        model_manager: &ModelManager,
+        viewer_id: Option<Uuid>,
+        owner_id: Option<Uuid>,
+        include_unpublished: bool,
+        filters: &PitchFilters,
+        page: &PageRequest<<PitchListItem as Keyset>::Key>,
+    ) -> Result<Paged<PitchListItem>, PitchControllerError> {
+        // distances are measured from `near`, or from the middle of `bbox` when only that is set
+        let origin = filters
+            .near
+            .or_else(|| filters.bbox.map(|bbox| bbox.center()));
+
+        // only whitelisted columns end up in the query, every value is bound
+        let (sort_column, is_descending) = match filters.sort {
+            PitchSort::Relevance => ("relevance_score", true),
+            PitchSort::Distance => ("distance_km", false),
+            PitchSort::Price => ("price_per_hour_cents::FLOAT8", false),
+            PitchSort::PriceDesc => ("price_per_hour_cents::FLOAT8", true),
+            PitchSort::Rating => ("rating", false),
+            PitchSort::RatingDesc => ("rating", true),
+        };
+        let (comparison, direction) = if is_descending {
+            (" < ", "DESC")
+        } else {
+            (" > ", "ASC")
+        };
+
+        // the list is built in steps so that later ones can use computed columns like
+        // `distance_km`
+        let mut query_builder = QueryBuilder::new(
+            r#"
+            WITH pitch_list AS (
+            SELECT
+                pitches.id,
+                pitches.owner_id,
+                pitches.display_name,
+                pitches.sport,
+                pitches.other_sports,
+                pitches.facility_id,
+                pitches.price_per_hour_cents,
+                pitches.currency,
+                pitches.image_url,
+                pitches.surface,
+                pitches.environment,
+                pitches.description,
+                pitches.max_players,
+                pitches.status,
+                pitches.timezone,
+                business.display_name AS owner_name,
+                owner.avatar_url AS owner_avatar,
+                pitch_amenity_names.amenities,
+                locations.address_line_1,
+                locations.address_line_2,
+                locations.city,
+                locations.country,
+                locations.latitude,
+                locations.longitude,
+                closure.starts_at AS closure_starts_at,
+                closure.ends_at AS closure_ends_at,
+                closure.starts_at AT TIME ZONE pitches.timezone AS closure_local_starts_at,
+                closure.ends_at AT TIME ZONE pitches.timezone AS closure_local_ends_at,
+                closure.reason AS closure_reason,
+                reviews.rating,
+                COALESCE(reviews.review_count, 0) AS review_count,
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        favorite_pitches
+                    WHERE
+                        favorite_pitches.pitch_id = pitches.id
+                    AND
+                        favorite_pitches.player_id = "#,
+        );
+        query_builder.push_bind(viewer_id).push(
+            r#"
+                ) AS is_favorite,
+            "#,
+        );
+        match origin {
+            Some(origin) => {
+                query_builder
+                    .push("ROUND((earth_distance(location_to_earth(")
+                    .push_bind(origin.latitude)
+                    .push(", ")
+                    .push_bind(origin.longitude)
+                    .push(
+                        "), location_to_earth(locations.latitude, locations.longitude)) / 1000)::NUMERIC, 2)::FLOAT8",
+                    );
+            }
+            None => {
+                query_builder.push("NULL::FLOAT8");
+            }
+        }
+        query_builder.push(" AS distance_km");
+        Self::push_pitch_list_source(&mut query_builder, owner_id, include_unpublished, filters);
+        query_builder.push("),");
+        let relevance_weights =
+            matches!(filters.sort, PitchSort::Relevance).then_some(&config().relevance_weights);
+        push_relevance_ctes(&mut query_builder, viewer_id, relevance_weights);
+        query_builder.push(format!(
+            r#"
+            , sorted AS (
+                SELECT
+                    *,
+                    {sort_column} AS sort_value
+                FROM
+                    ranked
+            )
+            SELECT
+                *
+            FROM
+                sorted
+            WHERE
+                TRUE
+            "#
+        ));
+
+        // nulls are sorted last, so once they run out only the id is left to compare
+        match page.after {
+            Some((_, Some(after_value), after_id)) => {
+                query_builder
+                    .push(format!(" AND (sort_value{comparison}"))
+                    .push_bind(after_value)
+                    .push(" OR (sort_value = ")
+                    .push_bind(after_value)
+                    .push(" AND id > ")
+                    .push_bind(after_id)
+                    .push(") OR sort_value IS NULL)");
+            }
+            Some((_, None, after_id)) => {
+                query_builder
+                    .push(" AND sort_value IS NULL AND id > ")
+                    .push_bind(after_id);
+            }
+            None => {}
+        }
+
+        query_builder
+            .push(format!(
+                r#"
+            ORDER BY
+                sort_value {direction} NULLS LAST, id
+            LIMIT
+            "#
+            ))
+            .push_bind(page.fetch_limit());
+
+        let mut pitches: Vec<PitchListItem> = query_builder
+            .build_query_as()
+            .fetch_all(model_manager.db())
+            .await
+            .map_err(PitchControllerError::Sqlx)?;
+        for pitch in &mut pitches {
+            pitch.sort = filters.sort;
+        }
+
+        let total = if page.include_total {
+            let mut query_builder = QueryBuilder::new("SELECT COUNT(*)");
+            Self::push_pitch_list_source(
+                &mut query_builder,
+                owner_id,
+                include_unpublished,
+                filters,
+            );
+            let total = query_builder
+                .build_query_scalar()
+                .fetch_one(model_manager.db())
+                .await
+                .map_err(PitchControllerError::Sqlx)?;
+            Some(total)
+        } else {
+            None
+        };
+
+        Ok(Paged::new(pitches, page.limit, total))
+    }
+
+    /// Pushes the `FROM` and `WHERE` clauses shared by the pitch list and its count
+    fn push_pitch_list_source<'args>(
+        query_builder: &mut QueryBuilder<'args, Postgres>,
+        owner_id: Option<Uuid>,
+        include_unpublished: bool,
+        filters: &'args PitchFilters,
+    ) {
+        query_builder.push(
+            r#"
+            FROM
+                pitches
+            JOIN
+                business_profiles business
+            ON
+                business.user_id = pitches.owner_id
+            JOIN
+                users owner
+            ON
+                owner.id = pitches.owner_id
+            LEFT JOIN
+                locations
+            ON
+                locations.id = pitches.location_id
+            LEFT JOIN (
+                SELECT
+                    pitch_id,
+                    ROUND(AVG(rating), 2)::FLOAT8 AS rating,
+                    COUNT(*) AS review_count
+                FROM
+                    pitch_reviews
+                GROUP BY
+                    pitch_id
+            ) reviews
+            ON
+                reviews.pitch_id = pitches.id
+            CROSS JOIN LATERAL (
+                SELECT
+                    ARRAY(
+                        SELECT
+                            amenities.name
+                        FROM
+                            pitch_amenities
+                        JOIN
+                            amenities
+                        ON
+                            amenities.id = pitch_amenities.amenity_id
+                        WHERE
+                            pitch_amenities.pitch_id = pitches.id
+                        ORDER BY
+                            amenities.name
+                    )::TEXT[] AS amenities
+            ) pitch_amenity_names
+            LEFT JOIN LATERAL (
+                SELECT
+                    LOWER(period) AS starts_at,
+                    UPPER(period) AS ends_at,
+                    reason
+                FROM
+                    pitch_closures
+                WHERE
+                    pitch_closures.pitch_id = pitches.id
+                AND
+                    pitch_closures.is_confirmed
+                AND
+                    UPPER(pitch_closures.period) > NOW()
+                ORDER BY
+                    LOWER(pitch_closures.period)
+                LIMIT 1
+            ) closure
+            ON
+                TRUE
+            WHERE
+                TRUE
+            "#,
+        );
+
+        if let Some(owner_id) = owner_id {
+            query_builder
+                .push(" AND pitches.owner_id = ")
+                .push_bind(owner_id);
+        }
+
+        if !include_unpublished {
+            query_builder.push(" AND pitches.status = 'published'");
+        }
+
+        if !filters.sports.is_empty() {
+            query_builder
+                .push(" AND (pitches.sport = ANY(")
+                .push_bind(&filters.sports)
+                .push(") OR pitches.other_sports && ")
+                .push_bind(&filters.sports)
+                .push(")");
+        }
+
+        if let Some(min_price_per_hour) = filters.min_price_per_hour {
+            query_builder
+                .push(" AND pitches.currency = ")
+                .push_bind(min_price_per_hour.currency())
+                .push(" AND pitches.price_per_hour_cents >= ")
+                .push_bind(min_price_per_hour.amount_cents());
+        }
+
+        if let Some(max_price_per_hour) = filters.max_price_per_hour {
+            query_builder
+                .push(" AND pitches.currency = ")
+                .push_bind(max_price_per_hour.currency())
+                .push(" AND pitches.price_per_hour_cents <= ")
+                .push_bind(max_price_per_hour.amount_cents());
+        }
+
+        if let Some(surface) = filters.surface {
+            query_builder
+                .push(" AND pitches.surface = ")
+                .push_bind(surface);
+        }
+
+        if let Some(environment) = filters.environment {
+            query_builder
+                .push(" AND pitches.environment = ")
+                .push_bind(environment);
+        }
+
+        if !filters.amenities.is_empty() {
+            query_builder
+                .push(" AND pitch_amenity_names.amenities @> ")
+                .push_bind(&filters.amenities);
+        }
+
+        if let Some(min_capacity) = filters.min_capacity {
+            query_builder
+                .push(" AND pitches.max_players >= ")
+                .push_bind(min_capacity);
+        }
+
+        if let (Some(near), Some(radius_km)) = (filters.near, filters.radius_km) {
+            let radius_meters = radius_km * 1000.0;
+            // earth_box can use the gist index but is a bit larger than the circle, so the exact
+            // distance is checked as well
+            query_builder
+                .push(" AND earth_box(location_to_earth(")
+                .push_bind(near.latitude)
+                .push(", ")
+                .push_bind(near.longitude)
+                .push("), ")
+                .push_bind(radius_meters)
+                .push(") @> location_to_earth(locations.latitude, locations.longitude)")
+                .push(" AND earth_distance(location_to_earth(")
+                .push_bind(near.latitude)
+                .push(", ")
+                .push_bind(near.longitude)
+                .push("), location_to_earth(locations.latitude, locations.longitude)) <= ")
+                .push_bind(radius_meters);
+        }
+
+        if let Some(bbox) = filters.bbox {
+            query_builder
+                .push(" AND locations.latitude BETWEEN ")
+                .push_bind(bbox.south_west.latitude)
+                .push(" AND ")
+                .push_bind(bbox.north_east.latitude)
+                .push(" AND locations.longitude BETWEEN ")
+                .push_bind(bbox.south_west.longitude)
+                .push(" AND ")
+                .push_bind(bbox.north_east.longitude);
+        }
+
+        if let Some(available_between) = filters.available_between {
+            query_builder
+                .push(
+                    r#"
+            AND EXISTS (
+                SELECT
+                    1
+                FROM
+                    bookable_timeslots
+                WHERE
+                    bookable_timeslots.pitch_id = pitches.id
+                AND
+                    bookable_timeslots.time_range <@ TSTZRANGE("#,
+                )
+                .push_bind(available_between.start)
+                .push(", ")
+                .push_bind(available_between.end)
+                .push(
+                    r#")
+            )
+            "#,
+                );
+        }
+    }
+}
+
+/// Hourly prices are stored as 32 bits cents
+fn price_per_hour_cents(price_per_hour: Price) -> Result<i32, PitchControllerError> {
+    i32::try_from(price_per_hour.amount_cents()).map_err(|_| PitchControllerError::PriceOutOfRange)
+}
+
+/// Dedups the other sports of a pitch and leaves its main sport out of them
+fn other_sports(other_sports: Vec<Sport>, sport: Option<Sport>) -> Vec<Sport> {
+    let mut deduped_sports = Vec::with_capacity(other_sports.len());
+    for other_sport in other_sports {
+        if Some(other_sport) != sport && !deduped_sports.contains(&other_sport) {
+            deduped_sports.push(other_sport);
+        }
+    }
+
+    deduped_sports
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            BookingController, InsertTimeslotPayload, InsertUserPayload, TimeslotController,
+            UserController, UserProfile,
+            pitches::{
+                controller::PitchController,
+                errors::PitchControllerError,
+                models::{
+                    InsertPitchPayload, PitchActor, PitchFilters, PitchListItem, PitchSort,
+                    UpdatePitchPayload,
+                },
+            },
+            test_utils::{
+                delete_business, delete_users, insert_business_with_pitch, insert_player,
+            },
+        },
+        models::{
+            ModelManager,
+            geo::GeoPoint,
+            pagination::{PageRequest, decode_cursor},
+            price::Price,
+            tables::{BusinessProfile, PitchAdminAction, PitchEnvironment, PitchStatus, Sport},
+        },
+    };
+    use anyhow::Context;
+    use chrono::{Duration, DurationRound, Utc};
+    use rand::distr::{Alphanumeric, SampleString};
+    use uuid::uuid;
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_favorites_of_viewer() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let viewer_id = uuid!("00000000-0000-0000-0000-000000000002");
+        let pitches = PitchController::get_all_pitches(
+            &model_manager,
+            Some(viewer_id),
+            &PitchFilters::default(),
+            &PageRequest::first(20),
+        )
+        .await
+        .context("failed while fetching pitches")?
+        .items;
+
+        // check
+        let favorites: Vec<_> = pitches
+            .iter()
+            .filter(|item| item.is_favorite)
+            .map(|item| item.pitch.id)
+            .collect();
+        assert_eq!(favorites, [uuid!("00000000-0000-0000-0000-000000000006")]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_pitches_by_business_id_ok() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let business_id = uuid!("00000000-0000-0000-0000-000000000003");
+        let pitches = PitchController::get_pitches_by_business_id(
+            business_id,
+            &model_manager,
+            &PitchFilters::default(),
+            &PageRequest::first(20),
+        )
+        .await
+        .context("failed while fetching pitches")?
+        .items;
+
+        // check
+        assert_eq!(pitches.len(), 2);
+        assert!(
+            pitches
+                .iter()
+                .all(|item| item.pitch.owner_id == business_id)
+        );
+
+        let football_pitch_1 = &pitches[0];
+        assert_eq!(football_pitch_1.pitch.display_name, "football_pitch_1");
+        assert_eq!(football_pitch_1.owner_name, "business_1_display");
+        assert_eq!(football_pitch_1.rating, Some(4.5));
+        assert_eq!(football_pitch_1.review_count, 2);
+        assert_eq!(football_pitch_1.amenities, ["lights", "water"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_pitch_err_display_name_already_exists() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let business_id = uuid!("00000000-0000-0000-0000-000000000003");
+        let pitch = InsertPitchPayload {
+            display_name: "football_pitch_1".to_string(),
+            sport: Sport::Football,
+            other_sports: Vec::new(),
+            price_per_hour: "100 AED".parse()?,
+            image_url: None,
+            surface: None,
+            environment: None,
+            description: None,
+            max_players: None,
+            amenities: Vec::new(),
+            location: None,
+            status: PitchStatus::Published,
+            timezone: "UTC".to_string(),
+        };
+        let result = PitchController::create_pitch(
+            &model_manager,
+            PitchActor::Owner(business_id),
+            business_id,
+            pitch,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(PitchControllerError::DisplayNameAlreadyExists)),
+            "result: {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_pitch_err_unknown_timezone() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let business_id = uuid!("00000000-0000-0000-0000-000000000003");
+        let pitch = InsertPitchPayload {
+            display_name: Alphanumeric.sample_string(&mut rand::rng(), 16),
+            sport: Sport::Football,
+            other_sports: Vec::new(),
+            price_per_hour: "100 AED".parse()?,
+            image_url: None,
+            surface: None,
+            environment: None,
+            description: None,
+            max_players: None,
+            amenities: Vec::new(),
+            location: None,
+            status: PitchStatus::Published,
+            timezone: "GMT+4".to_string(),
+        };
+        let result = PitchController::create_pitch(
+            &model_manager,
+            PitchActor::Owner(business_id),
+            business_id,
+            pitch,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(PitchControllerError::UnknownTimezone)),
+            "result: {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_pitch_by_id_err_not_owner() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let pitch_id = uuid!("00000000-0000-0000-0000-000000000006");
+        let other_business_id = uuid!("00000000-0000-0000-0000-000000000004");
+        let new_pitch = UpdatePitchPayload {
+            display_name: None,
+            sport: None,
+            other_sports: None,
+            price_per_hour: None,
+            image_url: None,
+            surface: None,
+            environment: None,
+            description: None,
+            max_players: None,
+            amenities: None,
+            location: None,
+            timezone: None,
+        };
+        let result = PitchController::update_pitch_by_id(
+            &model_manager,
+            pitch_id,
+            PitchActor::Owner(other_business_id),
+            new_pitch,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(PitchControllerError::PitchNotFound)),
+            "result: {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_pitch_err_not_owner() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let business_id = uuid!("00000000-0000-0000-0000-000000000003");
+        let other_business_id = uuid!("00000000-0000-0000-0000-000000000004");
+        let pitch = InsertPitchPayload {
+            display_name: Alphanumeric.sample_string(&mut rand::rng(), 16),
+            sport: Sport::Football,
+            other_sports: Vec::new(),
+            price_per_hour: "100 AED".parse()?,
+            image_url: None,
+            surface: None,
+            environment: None,
+            description: None,
+            max_players: None,
+            amenities: Vec::new(),
+            location: None,
+            status: PitchStatus::Published,
+            timezone: "UTC".to_string(),
+        };
+        let result = PitchController::create_pitch(
+            &model_manager,
+            PitchActor::Owner(business_id),
+            other_business_id,
+            pitch,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(PitchControllerError::NotOwner)),
+            "result: {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_admin_actions_ok_recorded_and_notified() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let username = Alphanumeric.sample_string(&mut rand::rng(), 16);
+        let password = Alphanumeric.sample_string(&mut rand::rng(), 16);
+        let profile = UserProfile::Business(BusinessProfile {
+            display_name: Alphanumeric.sample_string(&mut rand::rng(), 16),
+        });
+        let user = InsertUserPayload {
+            username: &username,
+            password: &password,
+            profile: &profile,
+        };
+        let business_id = UserController::insert_user(&model_manager, user)
+            .await
+            .context("failed while inserting business")?;
+        let admin_id = uuid!("00000000-0000-0000-0000-000000000005");
+
+        // exec
+        let pitch = InsertPitchPayload {
+            display_name: "admin_pitch".to_string(),
+            sport: Sport::Padel,
+            other_sports: Vec::new(),
+            price_per_hour: "100 AED".parse()?,
+            image_url: None,
+            surface: None,
+            environment: None,
+            description: None,
+            max_players: None,
+            amenities: Vec::new(),
+            location: None,
+            status: PitchStatus::Published,
+            timezone: "UTC".to_string(),
+        };
+        let pitch_id = PitchController::create_pitch(
+            &model_manager,
+            PitchActor::Admin(admin_id),
+            business_id,
+            pitch,
+        )
+        .await
+        .context("failed while creating pitch")?;
+        PitchController::set_pitch_status_by_id(
+            &model_manager,
+            pitch_id,
+            PitchActor::Admin(admin_id),
+            PitchStatus::Hidden,
+        )
+        .await
+        .context("failed while hiding pitch")?;
+        PitchController::delete_pitch_by_id(&model_manager, pitch_id, PitchActor::Admin(admin_id))
+            .await
+            .context("failed while deleting pitch")?;
+
+        // check
+        let actions: Vec<(uuid::Uuid, PitchAdminAction)> = sqlx::query_as(
+            "SELECT admin_id, action FROM pitch_admin_actions WHERE pitch_id = $1 ORDER BY created_at",
+        )
+        .bind(pitch_id)
+        .fetch_all(model_manager.db())
+        .await
+        .context("failed while fetching admin actions")?;
+        assert_eq!(
+            actions,
+            [
+                (admin_id, PitchAdminAction::Create),
+                (admin_id, PitchAdminAction::Hide),
+                (admin_id, PitchAdminAction::Delete),
+            ]
+        );
+
+        let messages: Vec<String> = sqlx::query_scalar(
+            "SELECT message FROM notifications WHERE user_id = $1 ORDER BY created_at",
         )
         .bind(business_id)
         .fetch_all(model_manager.db())
         .await
-        .map_err(PitchControllerError::Sqlx)
+        .context("failed while fetching notifications")?;
+        assert_eq!(
+            messages,
+            [
+                r#"an admin created your pitch "admin_pitch""#,
+                r#"an admin hid your pitch "admin_pitch""#,
+                r#"an admin deleted your pitch "admin_pitch""#,
+            ]
+        );
+
+        // clean
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(business_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while deleting business")?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_filters() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let filters = PitchFilters {
+            surface: None,
+            environment: Some(PitchEnvironment::Outdoor),
+            amenities: vec!["lights".to_string()],
+            min_capacity: Some(10),
+            ..Default::default()
+        };
+        let pitches = PitchController::get_all_pitches(
+            &model_manager,
+            None,
+            &filters,
+            &PageRequest::first(20),
+        )
+        .await
+        .context("failed while fetching pitches")?
+        .items;
+
+        // check
+        let pitch_ids: Vec<_> = pitches.iter().map(|item| item.pitch.id).collect();
+        assert_eq!(pitch_ids, [uuid!("00000000-0000-0000-0000-000000000006")]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_pitch_by_id_err_unknown_amenity() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let pitch_id = uuid!("00000000-0000-0000-0000-000000000007");
+        let business_id = uuid!("00000000-0000-0000-0000-000000000003");
+        let new_pitch = UpdatePitchPayload {
+            display_name: None,
+            sport: None,
+            other_sports: None,
+            price_per_hour: None,
+            image_url: None,
+            surface: None,
+            environment: None,
+            description: None,
+            max_players: None,
+            amenities: Some(vec!["lights".to_string(), "jacuzzi".to_string()]),
+            location: None,
+            timezone: None,
+        };
+        let result = PitchController::update_pitch_by_id(
+            &model_manager,
+            pitch_id,
+            PitchActor::Owner(business_id),
+            new_pitch,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(PitchControllerError::UnknownAmenity)),
+            "result: {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_near_within_radius() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let filters = PitchFilters {
+            // Sharjah city centre
+            near: GeoPoint::new(25.3463, 55.4209),
+            radius_km: Some(20.0),
+            ..Default::default()
+        };
+        let pitches = PitchController::get_all_pitches(
+            &model_manager,
+            None,
+            &filters,
+            &PageRequest::first(20),
+        )
+        .await
+        .context("failed while fetching pitches")?
+        .items;
+
+        // check
+        let pitches: Vec<_> = pitches
+            .iter()
+            .map(|item| (item.pitch.id, item.distance_km))
+            .collect();
+        assert_eq!(
+            pitches,
+            [
+                (uuid!("00000000-0000-0000-0000-000000000006"), Some(4.07)),
+                (uuid!("00000000-0000-0000-0000-000000000007"), Some(8.91)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_pages_by_distance() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let filters = PitchFilters {
+            near: GeoPoint::new(25.3463, 55.4209),
+            radius_km: Some(25.0),
+            ..Default::default()
+        };
+        let first_page = PitchController::get_all_pitches(
+            &model_manager,
+            None,
+            &filters,
+            &PageRequest {
+                limit: 2,
+                after: None,
+                include_total: true,
+            },
+        )
+        .await
+        .context("failed while fetching the first page")?;
+
+        // exec
+        let cursor = first_page.next_cursor.context("first page has a cursor")?;
+        let second_page = PitchController::get_all_pitches(
+            &model_manager,
+            None,
+            &filters,
+            &PageRequest {
+                limit: 2,
+                after: Some(decode_cursor(&cursor).expect("valid cursor")),
+                include_total: false,
+            },
+        )
+        .await
+        .context("failed while fetching the second page")?;
+
+        // check
+        let pitch_ids = |items: &[PitchListItem]| -> Vec<_> {
+            items.iter().map(|item| item.pitch.id).collect()
+        };
+        assert_eq!(first_page.total, Some(3));
+        assert_eq!(
+            pitch_ids(&first_page.items),
+            [
+                uuid!("00000000-0000-0000-0000-000000000006"),
+                uuid!("00000000-0000-0000-0000-000000000007"),
+            ]
+        );
+        assert_eq!(
+            pitch_ids(&second_page.items),
+            [uuid!("00000000-0000-0000-0000-000000000008")]
+        );
+        assert_eq!(second_page.next_cursor, None);
+        assert_eq!(second_page.total, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_sorted_by_rating_desc_pages() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let filters = PitchFilters {
+            near: GeoPoint::new(25.3463, 55.4209),
+            radius_km: Some(25.0),
+            sort: PitchSort::RatingDesc,
+            ..Default::default()
+        };
+        let mut pitch_ids = Vec::new();
+        let mut after = None;
+
+        // exec
+        loop {
+            let page = PitchController::get_all_pitches(
+                &model_manager,
+                None,
+                &filters,
+                &PageRequest {
+                    limit: 1,
+                    after,
+                    include_total: false,
+                },
+            )
+            .await
+            .context("failed while fetching pitches")?;
+            pitch_ids.extend(page.items.iter().map(|item| item.pitch.id));
+            match page.next_cursor {
+                Some(cursor) => after = Some(decode_cursor(&cursor).expect("valid cursor")),
+                None => break,
+            }
+        }
+
+        // check
+        // football_pitch_2 has no reviews so it comes last
+        assert_eq!(
+            pitch_ids,
+            [
+                uuid!("00000000-0000-0000-0000-000000000006"),
+                uuid!("00000000-0000-0000-0000-000000000008"),
+                uuid!("00000000-0000-0000-0000-000000000007"),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_sports_price_and_availability() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let filters = PitchFilters {
+            sports: vec![Sport::Football, Sport::Padel],
+            min_price_per_hour: Some(Price::new(20000, "AED".parse()?)),
+            available_between: "2025-03-30T13:00:00Z,2025-03-30T15:00:00Z".parse().ok(),
+            ..Default::default()
+        };
+        let pitches = PitchController::get_all_pitches(
+            &model_manager,
+            None,
+            &filters,
+            &PageRequest::first(20),
+        )
+        .await
+        .context("failed while fetching pitches")?
+        .items;
+
+        // check
+        let pitch_ids: Vec<_> = pitches.iter().map(|item| item.pitch.id).collect();
+        assert_eq!(pitch_ids, [uuid!("00000000-0000-0000-0000-000000000006")]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_relevance_prefers_player_sports() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        // player_2 prefers basketball and padel, and booked basketball_pitch_1
+        let viewer_id = uuid!("00000000-0000-0000-0000-000000000002");
+        let filters = PitchFilters {
+            near: GeoPoint::new(25.3463, 55.4209),
+            radius_km: Some(25.0),
+            sort: PitchSort::Relevance,
+            ..Default::default()
+        };
+        let pitches = PitchController::get_all_pitches(
+            &model_manager,
+            Some(viewer_id),
+            &filters,
+            &PageRequest::first(20),
+        )
+        .await
+        .context("failed while fetching pitches")?
+        .items;
+
+        // check
+        let pitch_ids: Vec<_> = pitches.iter().map(|item| item.pitch.id).collect();
+        assert_eq!(
+            pitch_ids,
+            [
+                uuid!("00000000-0000-0000-0000-000000000008"),
+                uuid!("00000000-0000-0000-0000-000000000006"),
+                uuid!("00000000-0000-0000-0000-000000000007"),
+            ]
+        );
+        let basketball_pitch = &pitches[0].relevance;
+        assert_eq!(basketball_pitch.sport_score, Some(1.0));
+        assert_eq!(basketball_pitch.price_score, Some(1.0));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_relevance_guest_not_personalized() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let filters = PitchFilters {
+            near: GeoPoint::new(25.3463, 55.4209),
+            radius_km: Some(25.0),
+            sort: PitchSort::Relevance,
+            ..Default::default()
+        };
+        let pitches = PitchController::get_all_pitches(
+            &model_manager,
+            None,
+            &filters,
+            &PageRequest::first(20),
+        )
+        .await
+        .context("failed while fetching pitches")?
+        .items;
+
+        // check
+        assert_eq!(
+            pitches[0].pitch.id,
+            uuid!("00000000-0000-0000-0000-000000000006")
+        );
+        assert!(pitches.iter().all(|item| {
+            item.relevance.sport_score.is_none() && item.relevance.price_score.is_none()
+        }));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_relevance_uses_paid_prices() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "repriced_pitch", PitchStatus::Published)
+                .await?;
+        let player_id = insert_player(&model_manager).await?;
+        // 2 hours at 100 AED per hour
+        let starts_at = Utc::now().duration_trunc(Duration::hours(1))? + Duration::days(1);
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::hours(2),
+            price: None,
+        };
+        let timeslot_id = TimeslotController::create_timeslot(
+            &model_manager,
+            PitchActor::Owner(business_id),
+            timeslot,
+        )
+        .await
+        .context("failed while creating timeslot")?;
+        BookingController::book_timeslot(&model_manager, player_id, timeslot_id)
+            .await
+            .context("failed while booking")?;
+        sqlx::query("UPDATE pitches SET price_per_hour_cents = 20000 WHERE id = $1")
+            .bind(pitch_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while changing price")?;
+
+        // exec
+        let price: Price = "200 AED".parse()?;
+        let filters = PitchFilters {
+            min_price_per_hour: Some(price),
+            max_price_per_hour: Some(price),
+            sort: PitchSort::Relevance,
+            ..Default::default()
+        };
+        let pitches = PitchController::get_all_pitches(
+            &model_manager,
+            Some(player_id),
+            &filters,
+            &PageRequest::first(100),
+        )
+        .await
+        .context("failed while fetching pitches")?
+        .items;
+
+        // check
+        // twice the 100 AED per hour the player paid
+        let pitch = pitches
+            .iter()
+            .find(|item| item.pitch.id == pitch_id)
+            .context("pitch isn't listed")?;
+        assert_eq!(pitch.relevance.price_score, Some(0.5));
+
+        // clean
+        delete_users(&model_manager, &[player_id]).await?;
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_all_pitches_ok_relevance_player_booked_free_pitches() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "free_pitch", PitchStatus::Published)
+                .await?;
+        let (other_business_id, other_pitch_id) =
+            insert_business_with_pitch(&model_manager, "free_pitch", PitchStatus::Published)
+                .await?;
+        let player_id = insert_player(&model_manager).await?;
+        sqlx::query("UPDATE pitches SET price_per_hour_cents = 0 WHERE id = ANY($1)")
+            .bind([pitch_id, other_pitch_id])
+            .execute(model_manager.db())
+            .await
+            .context("failed while making pitches free")?;
+        sqlx::query("UPDATE pitches SET currency = 'USD' WHERE id = $1")
+            .bind(other_pitch_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while changing currency")?;
+        let starts_at = Utc::now().duration_trunc(Duration::hours(1))? + Duration::days(1);
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::hours(1),
+            price: None,
+        };
+        let timeslot_id = TimeslotController::create_timeslot(
+            &model_manager,
+            PitchActor::Owner(business_id),
+            timeslot,
+        )
+        .await
+        .context("failed while creating timeslot")?;
+        BookingController::book_timeslot(&model_manager, player_id, timeslot_id)
+            .await
+            .context("failed while booking")?;
+
+        // exec
+        let mut price_scores = Vec::new();
+        for free in ["0 AED", "0 USD"] {
+            let free: Price = free.parse()?;
+            let filters = PitchFilters {
+                min_price_per_hour: Some(free),
+                max_price_per_hour: Some(free),
+                sort: PitchSort::Relevance,
+                ..Default::default()
+            };
+            let pitches = PitchController::get_all_pitches(
+                &model_manager,
+                Some(player_id),
+                &filters,
+                &PageRequest::first(100),
+            )
+            .await
+            .context("failed while fetching pitches")?
+            .items;
+            price_scores.extend(
+                pitches
+                    .into_iter()
+                    .filter(|item| [pitch_id, other_pitch_id].contains(&item.pitch.id))
+                    .map(|item| item.relevance.price_score),
+            );
+        }
+
+        // check
+        // nothing to compare to in AED, and no history at all in USD
+        assert_eq!(price_scores, [Some(0.5), None]);
+
+        // clean
+        delete_users(&model_manager, &[player_id]).await?;
+        delete_business(&model_manager, business_id).await?;
+        delete_business(&model_manager, other_business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_pitch_status_by_id_err_invalid_transition() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let username = Alphanumeric.sample_string(&mut rand::rng(), 16);
+        let password = Alphanumeric.sample_string(&mut rand::rng(), 16);
+        let profile = UserProfile::Business(BusinessProfile {
+            display_name: Alphanumeric.sample_string(&mut rand::rng(), 16),
+        });
+        let user = InsertUserPayload {
+            username: &username,
+            password: &password,
+            profile: &profile,
+        };
+        let business_id = UserController::insert_user(&model_manager, user)
+            .await
+            .context("failed while inserting business")?;
+        let pitch = InsertPitchPayload {
+            display_name: "draft_pitch".to_string(),
+            sport: Sport::Padel,
+            other_sports: Vec::new(),
+            price_per_hour: "100 AED".parse()?,
+            image_url: None,
+            surface: None,
+            environment: None,
+            description: None,
+            max_players: None,
+            amenities: Vec::new(),
+            location: None,
+            status: PitchStatus::Draft,
+            timezone: "UTC".to_string(),
+        };
+        let actor = PitchActor::Owner(business_id);
+        let pitch_id = PitchController::create_pitch(&model_manager, actor, business_id, pitch)
+            .await
+            .context("failed while creating pitch")?;
+
+        // exec
+        let hide_result = PitchController::set_pitch_status_by_id(
+            &model_manager,
+            pitch_id,
+            actor,
+            PitchStatus::Hidden,
+        )
+        .await;
+        PitchController::set_pitch_status_by_id(
+            &model_manager,
+            pitch_id,
+            actor,
+            PitchStatus::Published,
+        )
+        .await
+        .context("failed while publishing pitch")?;
+        let draft_result = PitchController::set_pitch_status_by_id(
+            &model_manager,
+            pitch_id,
+            actor,
+            PitchStatus::Draft,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(
+                hide_result,
+                Err(PitchControllerError::InvalidStatusTransition)
+            ),
+            "result: {hide_result:?}"
+        );
+        assert!(
+            matches!(
+                draft_result,
+                Err(PitchControllerError::InvalidStatusTransition)
+            ),
+            "result: {draft_result:?}"
+        );
+
+        // clean
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(business_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while deleting business")?;
+
+        Ok(())
     }
 }
@@ -2,6 +2,19 @@ use crate::errors::error_impl;
 
 #[derive(Debug)]
 pub enum PitchControllerError {
+    PitchNotFound,
+    OwnerNotFound,
+    NotOwner,
+    DisplayNameAlreadyExists,
+    NegativePrice,
+    PriceOutOfRange,
+    PriceCurrencyMismatch,
+    InvalidMaxPlayers,
+    UnknownAmenity,
+    InvalidCoordinates,
+    PitchHasBookings,
+    InvalidStatusTransition,
+    UnknownTimezone,
     Sqlx(sqlx::Error),
 }
 
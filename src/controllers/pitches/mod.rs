@@ -1,2 +1,4 @@
 pub mod controller;
 pub mod errors;
+pub mod models;
+pub mod relevance;
@@ -0,0 +1,176 @@
+use crate::{
+    controllers::pitches::relevance::RelevanceExplanation,
+    models::{
+        geo::{BoundingBox, GeoPoint},
+        pagination::Keyset,
+        price::Price,
+        tables::{Pitch, PitchEnvironment, PitchStatus, Sport, SurfaceType},
+        time_window::TimeWindow,
+    },
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Who is writing to a pitch.
+///
+/// Owners can only touch their own pitches, admins can touch any pitch and have their actions
+/// recorded.
+#[derive(Clone, Copy)]
+pub enum PitchActor {
+    Owner(Uuid),
+    Admin(Uuid),
+}
+
+impl PitchActor {
+    pub fn owner_id(&self) -> Option<Uuid> {
+        match self {
+            Self::Owner(owner_id) => Some(*owner_id),
+            Self::Admin(_) => None,
+        }
+    }
+}
+
+#[derive(FromRow)]
+pub struct PitchListItem {
+    #[sqlx(flatten)]
+    pub pitch: Pitch,
+    /// display name of the owning business
+    pub owner_name: String,
+    pub owner_avatar: Option<String>,
+    /// names from the amenities catalog, sorted
+    pub amenities: Vec<String>,
+    /// the location columns are `None` for pitches without a location
+    pub address_line_1: Option<String>,
+    pub address_line_2: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// set when searching around a point
+    pub distance_km: Option<f64>,
+    /// the current or next confirmed closure, all `None` when there is none
+    pub closure_starts_at: Option<DateTime<Utc>>,
+    pub closure_ends_at: Option<DateTime<Utc>>,
+    /// wall clock times of the closure in the pitch's timezone
+    pub closure_local_starts_at: Option<NaiveDateTime>,
+    pub closure_local_ends_at: Option<NaiveDateTime>,
+    pub closure_reason: Option<String>,
+    /// value of the column the list is sorted by, `None` sorts last
+    pub sort_value: Option<f64>,
+    #[sqlx(flatten)]
+    pub relevance: RelevanceExplanation,
+    /// average rating, `None` if the pitch has no reviews yet
+    pub rating: Option<f64>,
+    pub review_count: i64,
+    /// whether the pitch is one of the viewer's favorites, always `false` for guests
+    pub is_favorite: bool,
+    /// the list was sorted this way, set after fetching
+    #[sqlx(skip)]
+    pub sort: PitchSort,
+}
+
+/// The sort is part of the key, `sort_value` means something else for every sort
+impl Keyset for PitchListItem {
+    type Key = (PitchSort, Option<f64>, Uuid);
+
+    fn key(&self) -> Self::Key {
+        (self.sort, self.sort_value, self.pitch.id)
+    }
+}
+
+#[derive(Default)]
+pub struct PitchFilters {
+    /// pitches can be for any of these sports, all sports when empty
+    pub sports: Vec<Sport>,
+    /// pitches must be in the currency of the bounds
+    pub min_price_per_hour: Option<Price>,
+    pub max_price_per_hour: Option<Price>,
+    pub surface: Option<SurfaceType>,
+    pub environment: Option<PitchEnvironment>,
+    /// pitches must have all of these amenities
+    pub amenities: Vec<String>,
+    /// pitches must fit at least this many players
+    pub min_capacity: Option<i16>,
+    /// results are sorted by distance from this point
+    pub near: Option<GeoPoint>,
+    /// only used with `near`
+    pub radius_km: Option<f64>,
+    pub bbox: Option<BoundingBox>,
+    /// pitches must have a free timeslot inside this window
+    pub available_between: Option<TimeWindow>,
+    pub sort: PitchSort,
+}
+
+/// Ties are broken by id, pitches without a value (no location, no reviews) come last
+#[cfg_attr(test, derive(Debug))]
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PitchSort {
+    /// best first, see the `relevance` module
+    Relevance,
+    /// same as sorting by id when there is no point to measure from
+    #[default]
+    Distance,
+    Price,
+    PriceDesc,
+    Rating,
+    RatingDesc,
+}
+
+pub struct LocationPayload {
+    pub address_line_1: String,
+    pub address_line_2: Option<String>,
+    pub city: String,
+    pub postal_code: Option<String>,
+    pub country: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+pub struct InsertPitchPayload {
+    pub display_name: String,
+    pub sport: Sport,
+    pub other_sports: Vec<Sport>,
+    pub price_per_hour: Price,
+    pub image_url: Option<String>,
+    pub surface: Option<SurfaceType>,
+    pub environment: Option<PitchEnvironment>,
+    pub description: Option<String>,
+    pub max_players: Option<i16>,
+    /// names from the amenities catalog
+    pub amenities: Vec<String>,
+    pub location: Option<LocationPayload>,
+    /// either draft or published
+    pub status: PitchStatus,
+    /// IANA name, e.g. "Asia/Dubai"
+    pub timezone: String,
+}
+
+pub struct UpdatePitchPayload {
+    pub display_name: Option<String>,
+    pub sport: Option<Sport>,
+    /// replaces the other sports when set
+    pub other_sports: Option<Vec<Sport>>,
+    /// in the currency of the pitch
+    pub price_per_hour: Option<Price>,
+    pub image_url: Option<String>,
+    pub surface: Option<SurfaceType>,
+    pub environment: Option<PitchEnvironment>,
+    pub description: Option<String>,
+    pub max_players: Option<i16>,
+    /// replaces the current amenities when set
+    pub amenities: Option<Vec<String>>,
+    pub location: Option<LocationPayload>,
+    /// IANA name, the timeslots already created keep their instants
+    pub timezone: Option<String>,
+}
+
+/// The pitch a write was applied to
+#[derive(FromRow)]
+pub struct ChangedPitch {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub display_name: String,
+}
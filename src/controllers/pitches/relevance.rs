@@ -0,0 +1,214 @@
+//! Relevance score of a pitch for whoever is browsing.
+//!
+//! Every part of the score is between 0 and 1, and parts that can't be computed are `NULL` and
+//! count as 0:
+//! - `sport`: whether the pitch can be played for one of the player's preferred sports, players
+//!   only.
+//! - `distance`: how close the pitch is to `near`/`bbox`, 0.5 at 10km.
+//! - `price`: how close the price is to the average hourly price the player paid for bookings in
+//!   the same currency, players only, 0.5 when that average is 0.
+//! - `rating`: the average rating out of 5.
+//! - `availability`: free timeslots in the next days, full at 5 slots.
+//! - `popularity`: reviews, favorites and bookings, 0.5 at 10.
+//!
+//! Guests (and players without a profile or bookings) only get the non-personalized parts, which
+//! makes their ranking a popularity one.
+
+use crate::configs::RelevanceWeights;
+use sqlx::{FromRow, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+/// How far ahead free timeslots count for `availability`
+const AVAILABILITY_WINDOW: &str = "7 days";
+
+/// `price` when the player only booked free pitches, which leaves nothing to compare to
+const NEUTRAL_PRICE_SCORE: f64 = 0.5;
+
+/// The parts of the score, all `None` when the list isn't sorted by relevance
+#[derive(FromRow)]
+pub struct RelevanceExplanation {
+    pub relevance_score: Option<f64>,
+    pub sport_score: Option<f64>,
+    pub distance_score: Option<f64>,
+    pub price_score: Option<f64>,
+    pub rating_score: Option<f64>,
+    pub availability_score: Option<f64>,
+    pub popularity_score: Option<f64>,
+}
+
+/// Pushes the `scored` and `ranked` CTEs, which add the columns of [`RelevanceExplanation`] to
+/// the rows of the `pitch_list` CTE.
+///
+/// The scores are only computed when `weights` is set.
+pub fn push_relevance_ctes<'args>(
+    query_builder: &mut QueryBuilder<'args, Postgres>,
+    viewer_id: Option<Uuid>,
+    weights: Option<&RelevanceWeights>,
+) {
+    let Some(weights) = weights else {
+        query_builder.push(
+            r#"
+            scored AS (
+                SELECT
+                    *,
+                    NULL::FLOAT8 AS sport_score,
+                    NULL::FLOAT8 AS distance_score,
+                    NULL::FLOAT8 AS price_score,
+                    NULL::FLOAT8 AS rating_score,
+                    NULL::FLOAT8 AS availability_score,
+                    NULL::FLOAT8 AS popularity_score
+                FROM
+                    pitch_list
+            ),
+            ranked AS (
+                SELECT
+                    *,
+                    NULL::FLOAT8 AS relevance_score
+                FROM
+                    scored
+            )
+            "#,
+        );
+        return;
+    };
+
+    query_builder.push(format!(
+        r#"
+            scored AS (
+                SELECT
+                    pitch_list.*,
+                    (
+                        pitch_list.sport = ANY(viewer.preferred_sports)
+                    OR
+                        pitch_list.other_sports && viewer.preferred_sports
+                    )::INT::FLOAT8 AS sport_score,
+                    ROUND((1 / (1 + pitch_list.distance_km / 10))::NUMERIC, 3)::FLOAT8
+                        AS distance_score,
+                    CASE
+                        WHEN booking_history.average_price_per_hour_cents IS NOT NULL THEN
+                            COALESCE(
+                                ROUND(
+                                    (
+                                        1 / (
+                                            1 + ABS(
+                                                pitch_list.price_per_hour_cents
+                                                - booking_history.average_price_per_hour_cents
+                                            ) / NULLIF(booking_history.average_price_per_hour_cents, 0)
+                                        )
+                                    )::NUMERIC,
+                                    3
+                                )::FLOAT8,
+                                {NEUTRAL_PRICE_SCORE}
+                            )
+                    END AS price_score,
+                    ROUND((pitch_list.rating / 5)::NUMERIC, 3)::FLOAT8 AS rating_score,
+                    (
+                        LEAST(
+                            (
+                                SELECT
+                                    COUNT(*)
+                                FROM
+                                    bookable_timeslots
+                                WHERE
+                                    bookable_timeslots.pitch_id = pitch_list.id
+                                AND
+                                    LOWER(bookable_timeslots.time_range)
+                                        BETWEEN NOW() AND NOW() + INTERVAL '{AVAILABILITY_WINDOW}'
+                            ),
+                            5
+                        ) / 5.0
+                    )::FLOAT8 AS availability_score,
+                    ROUND((popularity.count / (popularity.count + 10.0))::NUMERIC, 3)::FLOAT8
+                        AS popularity_score
+                FROM
+                    pitch_list
+                CROSS JOIN LATERAL (
+                    SELECT
+                        pitch_list.review_count
+                        + (
+                            SELECT
+                                COUNT(*)
+                            FROM
+                                favorite_pitches
+                            WHERE
+                                favorite_pitches.pitch_id = pitch_list.id
+                        )
+                        + (
+                            SELECT
+                                COUNT(*)
+                            FROM
+                                bookings
+                            JOIN
+                                timeslots
+                            ON
+                                timeslots.id = bookings.timeslot_id
+                            WHERE
+                                timeslots.pitch_id = pitch_list.id
+                        ) AS count
+                ) popularity
+                LEFT JOIN
+                    player_profiles viewer
+                ON
+                    viewer.user_id = "#
+    ));
+    query_builder.push_bind(viewer_id).push(
+        r#"
+                LEFT JOIN (
+                    SELECT
+                        (bookings.price).currency,
+                        AVG(
+                            (bookings.price).amount_cents
+                            * 3600
+                            / EXTRACT(
+                                EPOCH FROM UPPER(timeslots.time_range) - LOWER(timeslots.time_range)
+                            )
+                        )::FLOAT8 AS average_price_per_hour_cents
+                    FROM
+                        bookings
+                    JOIN
+                        timeslots
+                    ON
+                        timeslots.id = bookings.timeslot_id
+                    WHERE
+                        bookings.booked_by = "#,
+    );
+    query_builder.push_bind(viewer_id).push(
+        r#"
+                    GROUP BY
+                        (bookings.price).currency
+                ) booking_history
+                ON
+                    booking_history.currency = pitch_list.currency
+            ),
+            ranked AS (
+                SELECT
+                    *,
+                    ROUND(
+                        (
+                            "#,
+    );
+    let parts = [
+        (weights.sport, "sport_score"),
+        (weights.distance, "distance_score"),
+        (weights.price, "price_score"),
+        (weights.rating, "rating_score"),
+        (weights.availability, "availability_score"),
+        (weights.popularity, "popularity_score"),
+    ];
+    let mut separated = query_builder.separated(" + ");
+    for (weight, column) in parts {
+        separated
+            .push_bind(weight)
+            .push_unseparated(format!(" * COALESCE({column}, 0)"));
+    }
+    query_builder.push(
+        r#"
+                        )::NUMERIC,
+                        3
+                    )::FLOAT8 AS relevance_score
+                FROM
+                    scored
+            )
+            "#,
+    );
+}
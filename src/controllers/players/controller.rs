@@ -0,0 +1,242 @@
+use crate::{
+    controllers::players::{
+        errors::PlayerControllerError,
+        models::{PlayerPublicProfile, UpdatePrivacySettingsPayload},
+    },
+    models::{ModelManager, tables::PlayerPrivacySettings},
+};
+use sqlx::QueryBuilder;
+use uuid::Uuid;
+
+pub struct PlayerController;
+
+impl PlayerController {
+    pub async fn get_public_profile_by_id(
+        model_manager: &ModelManager,
+        id: Uuid,
+    ) -> Result<PlayerPublicProfile, PlayerControllerError> {
+        sqlx::query_as(
+            r#"
+            SELECT
+                player.user_id AS id,
+                player.first_name,
+                player.last_name,
+                users.created_at AS member_since,
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        bookings
+                    WHERE
+                        bookings.booked_by = player.user_id
+                ) AS total_bookings,
+                COALESCE(
+                    (
+                        SELECT
+                            pitches.sport
+                        FROM
+                            bookings
+                        JOIN
+                            timeslots
+                        ON
+                            timeslots.id = bookings.timeslot_id
+                        JOIN
+                            pitches
+                        ON
+                            pitches.id = timeslots.pitch_id
+                        WHERE
+                            bookings.booked_by = player.user_id
+                        GROUP BY
+                            pitches.sport
+                        ORDER BY
+                            COUNT(*) DESC, pitches.sport
+                        LIMIT 1
+                    ),
+                    player.preferred_sports[1]
+                ) AS favorite_sport,
+                settings.name_visibility,
+                settings.member_since_visibility,
+                settings.total_bookings_visibility,
+                settings.favorite_sport_visibility
+            FROM
+                player_profiles player
+            JOIN
+                users
+            ON
+                users.id = player.user_id
+            JOIN
+                player_privacy_settings settings
+            ON
+                settings.user_id = player.user_id
+            WHERE
+                player.user_id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(PlayerControllerError::Sqlx)?
+        .ok_or(PlayerControllerError::PlayerNotFound)
+    }
+
+    pub async fn get_privacy_settings_by_id(
+        model_manager: &ModelManager,
+        id: Uuid,
+    ) -> Result<PlayerPrivacySettings, PlayerControllerError> {
+        sqlx::query_as(
+            r#"
+            SELECT
+                name_visibility,
+                member_since_visibility,
+                total_bookings_visibility,
+                favorite_sport_visibility
+            FROM
+                player_privacy_settings
+            WHERE
+                user_id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(PlayerControllerError::Sqlx)?
+        .ok_or(PlayerControllerError::PlayerNotFound)
+    }
+
+    pub async fn update_privacy_settings_by_id(
+        model_manager: &ModelManager,
+        id: Uuid,
+        new_settings: UpdatePrivacySettingsPayload,
+    ) -> Result<(), PlayerControllerError> {
+        let mut query_builder = QueryBuilder::new("UPDATE player_privacy_settings SET ");
+        let mut separated_query_builder = query_builder.separated(", ");
+
+        let mut is_updated = false;
+
+        let columns = [
+            ("name_visibility = ", new_settings.name_visibility),
+            (
+                "member_since_visibility = ",
+                new_settings.member_since_visibility,
+            ),
+            (
+                "total_bookings_visibility = ",
+                new_settings.total_bookings_visibility,
+            ),
+            (
+                "favorite_sport_visibility = ",
+                new_settings.favorite_sport_visibility,
+            ),
+        ];
+        for (column, privacy_level) in columns {
+            if let Some(privacy_level) = privacy_level {
+                is_updated = true;
+                separated_query_builder
+                    .push(column)
+                    .push_bind_unseparated(privacy_level);
+            }
+        }
+
+        if !is_updated {
+            return Ok(());
+        }
+
+        let rows_affected = query_builder
+            .push(" WHERE user_id = ")
+            .push_bind(id)
+            .build()
+            .execute(model_manager.db())
+            .await
+            .map_err(PlayerControllerError::Sqlx)?
+            .rows_affected();
+
+        if rows_affected != 1 {
+            return Err(PlayerControllerError::PlayerNotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::players::{
+            controller::PlayerController, errors::PlayerControllerError, models::ProfileViewer,
+        },
+        models::{ModelManager, tables::Sport},
+    };
+    use anyhow::Context;
+    use uuid::{Uuid, uuid};
+
+    #[tokio::test]
+    async fn test_get_public_profile_by_id_ok_stranger() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let id = uuid!("00000000-0000-0000-0000-000000000002");
+        let profile = PlayerController::get_public_profile_by_id(&model_manager, id)
+            .await
+            .context("failed while fetching public profile")?
+            .visible_to(ProfileViewer::Stranger);
+
+        // check
+        assert_eq!(profile.id, id);
+        assert_eq!(profile.name, None);
+        assert!(profile.member_since.is_some());
+        assert_eq!(profile.total_bookings, Some(1));
+        assert_eq!(profile.favorite_sport, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_public_profile_by_id_ok_owner() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let id = uuid!("00000000-0000-0000-0000-000000000002");
+        let profile = PlayerController::get_public_profile_by_id(&model_manager, id)
+            .await
+            .context("failed while fetching public profile")?
+            .visible_to(ProfileViewer::Owner);
+
+        // check
+        assert_eq!(profile.id, id);
+        assert_eq!(
+            profile.name.as_deref(),
+            Some("player_2_first player_2_last")
+        );
+        assert!(profile.member_since.is_some());
+        assert_eq!(profile.total_bookings, Some(1));
+        assert_eq!(profile.favorite_sport, Some(Sport::Basketball));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_public_profile_by_id_err_player_not_found() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let business_id = uuid!("00000000-0000-0000-0000-000000000003");
+        let result = PlayerController::get_public_profile_by_id(&model_manager, business_id).await;
+
+        // check
+        assert!(
+            matches!(result, Err(PlayerControllerError::PlayerNotFound)),
+            "result: {:?}",
+            result.map(|_| ())
+        );
+
+        let result =
+            PlayerController::get_public_profile_by_id(&model_manager, Uuid::new_v4()).await;
+        assert!(
+            matches!(result, Err(PlayerControllerError::PlayerNotFound)),
+            "result: {:?}",
+            result.map(|_| ())
+        );
+
+        Ok(())
+    }
+}
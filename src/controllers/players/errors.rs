@@ -0,0 +1,9 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum PlayerControllerError {
+    PlayerNotFound,
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(PlayerControllerError);
@@ -0,0 +1,81 @@
+use crate::models::tables::{PlayerPrivacySettings, PrivacyLevel, Sport};
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Who is looking at a player's public profile
+#[derive(Clone, Copy)]
+pub enum ProfileViewer {
+    /// the player themselves, or an admin
+    Owner,
+    Stranger,
+}
+
+impl PrivacyLevel {
+    pub fn is_visible_to(self, viewer: ProfileViewer) -> bool {
+        match self {
+            PrivacyLevel::Public => true,
+            // TODO: let friends through once friendships are modelled. until then, only the owner
+            // can see friends-only fields
+            PrivacyLevel::FriendsOnly | PrivacyLevel::Hidden => {
+                matches!(viewer, ProfileViewer::Owner)
+            }
+        }
+    }
+}
+
+/// The full public profile of a player, before applying their privacy settings.
+///
+/// The fields are private on purpose: the only way to read them is [`Self::visible_to`], which
+/// makes sure hidden fields never leave this module.
+#[derive(FromRow)]
+pub struct PlayerPublicProfile {
+    id: Uuid,
+    first_name: String,
+    last_name: String,
+    member_since: DateTime<Utc>,
+    total_bookings: i64,
+    favorite_sport: Option<Sport>,
+    #[sqlx(flatten)]
+    privacy_settings: PlayerPrivacySettings,
+}
+
+impl PlayerPublicProfile {
+    pub fn visible_to(self, viewer: ProfileViewer) -> VisiblePlayerProfile {
+        let settings = self.privacy_settings;
+        VisiblePlayerProfile {
+            id: self.id,
+            name: settings
+                .name_visibility
+                .is_visible_to(viewer)
+                .then(|| format!("{} {}", self.first_name, self.last_name)),
+            member_since: settings
+                .member_since_visibility
+                .is_visible_to(viewer)
+                .then_some(self.member_since),
+            total_bookings: settings
+                .total_bookings_visibility
+                .is_visible_to(viewer)
+                .then_some(self.total_bookings),
+            favorite_sport: self
+                .favorite_sport
+                .filter(|_| settings.favorite_sport_visibility.is_visible_to(viewer)),
+        }
+    }
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct VisiblePlayerProfile {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub member_since: Option<DateTime<Utc>>,
+    pub total_bookings: Option<i64>,
+    pub favorite_sport: Option<Sport>,
+}
+
+pub struct UpdatePrivacySettingsPayload {
+    pub name_visibility: Option<PrivacyLevel>,
+    pub member_since_visibility: Option<PrivacyLevel>,
+    pub total_bookings_visibility: Option<PrivacyLevel>,
+    pub favorite_sport_visibility: Option<PrivacyLevel>,
+}
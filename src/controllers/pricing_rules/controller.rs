@@ -0,0 +1,562 @@
+use crate::{
+    controllers::{
+        PitchActor,
+        pricing_rules::{
+            engine,
+            errors::PricingRuleControllerError,
+            models::{
+                InsertPricingRulePayload, PitchPriceQuote, PriceAdjustment, PriceQuote,
+                PriceSegment, PricedPeriod, PricingRule, RuleWindow,
+            },
+        },
+    },
+    models::{ModelManager, local_time::with_local_offset, price::Price, tables::PitchStatus},
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{FromRow, PgConnection};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct PricingRuleController;
+
+impl PricingRuleController {
+    /// Returns the rules of the pitch, highest priority first
+    pub async fn get_rules(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        actor: PitchActor,
+    ) -> Result<Vec<PricingRule>, PricingRuleControllerError> {
+        let mut connection = model_manager
+            .db()
+            .acquire()
+            .await
+            .map_err(PricingRuleControllerError::Sqlx)?;
+
+        Self::check_pitch(&mut connection, pitch_id, actor).await?;
+
+        sqlx::query_as(
+            r#"
+            SELECT
+                id,
+                name,
+                priority,
+                weekdays,
+                TO_CHAR(starts_at, 'HH24:MI') AS starts_at,
+                TO_CHAR(ends_at, 'HH24:MI') AS ends_at,
+                starts_on,
+                until,
+                min_lead_minutes,
+                max_lead_minutes,
+                price_per_hour_cents,
+                adjustment_percent
+            FROM
+                pricing_rules
+            WHERE
+                pitch_id = $1
+            ORDER BY
+                priority DESC
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_all(&mut *connection)
+        .await
+        .map_err(PricingRuleControllerError::Sqlx)
+    }
+
+    /// Adds a rule to the pitch, its priority has to be unique among the rules of the pitch
+    pub async fn create_rule(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        actor: PitchActor,
+        rule: InsertPricingRulePayload,
+    ) -> Result<Uuid, PricingRuleControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(PricingRuleControllerError::Sqlx)?;
+
+        Self::check_pitch(&mut transaction, pitch_id, actor).await?;
+
+        let (price_per_hour_cents, adjustment_percent) = match rule.adjustment {
+            PriceAdjustment::PricePerHourCents(cents) => (Some(cents), None),
+            PriceAdjustment::Percent(percent) => (None, Some(percent)),
+        };
+        let result = sqlx::query_scalar(
+            r#"
+            INSERT INTO pricing_rules
+                (
+                    pitch_id,
+                    name,
+                    priority,
+                    weekdays,
+                    starts_at,
+                    ends_at,
+                    starts_on,
+                    until,
+                    min_lead_minutes,
+                    max_lead_minutes,
+                    price_per_hour_cents,
+                    adjustment_percent
+                )
+            VALUES
+                ($1, $2, $3, $4, $5::TIME, $6::TIME, $7, $8, $9, $10, $11, $12)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(rule.name)
+        .bind(rule.priority)
+        .bind(rule.weekdays)
+        .bind(rule.starts_at)
+        .bind(rule.ends_at)
+        .bind(rule.starts_on)
+        .bind(rule.until)
+        .bind(rule.min_lead_minutes)
+        .bind(rule.max_lead_minutes)
+        .bind(price_per_hour_cents)
+        .bind(adjustment_percent)
+        .fetch_one(&mut *transaction)
+        .await;
+
+        let id = match result {
+            Ok(id) => id,
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("pricing_rules_pitch_id_priority_key") =>
+            {
+                return Err(PricingRuleControllerError::PriorityTaken);
+            }
+            Err(err) => return Err(PricingRuleControllerError::Sqlx(err)),
+        };
+
+        transaction
+            .commit()
+            .await
+            .map_err(PricingRuleControllerError::Sqlx)?;
+
+        Ok(id)
+    }
+
+    /// Booked timeslots keep the price they were sold at
+    pub async fn delete_rule_by_id(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), PricingRuleControllerError> {
+        let mut connection = model_manager
+            .db()
+            .acquire()
+            .await
+            .map_err(PricingRuleControllerError::Sqlx)?;
+
+        Self::check_pitch(&mut connection, pitch_id, actor).await?;
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM
+                pricing_rules
+            WHERE
+                id = $1
+            AND
+                pitch_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(pitch_id)
+        .execute(&mut *connection)
+        .await
+        .map_err(PricingRuleControllerError::Sqlx)?;
+
+        if result.rows_affected() == 0 {
+            return Err(PricingRuleControllerError::RuleNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Prices a period of the pitch as if it was booked now.
+    ///
+    /// Like its timeslots, the pitch is only visible to its owner and admins until it's published.
+    pub async fn get_price_quote(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        viewer: Option<PitchActor>,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+    ) -> Result<PitchPriceQuote, PricingRuleControllerError> {
+        let mut connection = model_manager
+            .db()
+            .acquire()
+            .await
+            .map_err(PricingRuleControllerError::Sqlx)?;
+
+        let (owner_id, status, timezone): (Uuid, PitchStatus, String) = sqlx::query_as(
+            r#"
+            SELECT
+                owner_id, status, timezone
+            FROM
+                pitches
+            WHERE
+                id = $1
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_optional(&mut *connection)
+        .await
+        .map_err(PricingRuleControllerError::Sqlx)?
+        .ok_or(PricingRuleControllerError::PitchNotFound)?;
+
+        let can_see = match viewer {
+            Some(PitchActor::Admin(_)) => true,
+            Some(PitchActor::Owner(viewer_id)) => viewer_id == owner_id,
+            None => status == PitchStatus::Published,
+        };
+        if !can_see {
+            return Err(PricingRuleControllerError::PitchNotFound);
+        }
+
+        let period = PricedPeriod {
+            pitch_id,
+            starts_at,
+            ends_at,
+        };
+        let quote = Self::quote_periods(&mut connection, &[period], Utc::now())
+            .await?
+            .pop()
+            .ok_or(PricingRuleControllerError::PitchNotFound)?;
+
+        let times: Vec<_> = quote
+            .segments
+            .iter()
+            .flat_map(|segment| [segment.starts_at, segment.ends_at])
+            .chain([starts_at, ends_at])
+            .collect();
+        let local_times: HashMap<DateTime<Utc>, NaiveDateTime> = sqlx::query_as(
+            r#"
+            SELECT
+                time, time AT TIME ZONE $2
+            FROM
+                UNNEST($1::TIMESTAMPTZ[]) AS time
+            "#,
+        )
+        .bind(&times)
+        .bind(&timezone)
+        .fetch_all(&mut *connection)
+        .await
+        .map_err(PricingRuleControllerError::Sqlx)?
+        .into_iter()
+        .collect();
+        let to_local = |time: DateTime<Utc>| match local_times.get(&time) {
+            Some(local) => with_local_offset(time, *local),
+            None => time.fixed_offset(),
+        };
+
+        Ok(PitchPriceQuote {
+            starts_at: to_local(starts_at),
+            ends_at: to_local(ends_at),
+            price: quote.price,
+            segments: quote
+                .segments
+                .into_iter()
+                .map(|segment| PriceSegment {
+                    starts_at: to_local(segment.starts_at),
+                    ends_at: to_local(segment.ends_at),
+                    rule: segment.rule,
+                    price_per_hour: segment.price_per_hour,
+                    price: segment.price,
+                })
+                .collect(),
+        })
+    }
+
+    /// Prices the periods as computed at `now`, in their order.
+    ///
+    /// Prices set on timeslots aren't looked at, callers only pass the periods that need pricing.
+    pub async fn quote_periods(
+        connection: &mut PgConnection,
+        periods: &[PricedPeriod],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<PriceQuote>, PricingRuleControllerError> {
+        if periods.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pitch_ids: Vec<_> = periods.iter().map(|period| period.pitch_id).collect();
+        let starts_ats: Vec<_> = periods.iter().map(|period| period.starts_at).collect();
+        let ends_ats: Vec<_> = periods.iter().map(|period| period.ends_at).collect();
+
+        let prices_per_hour: HashMap<Uuid, Price> = sqlx::query_as(
+            r#"
+            SELECT
+                id, ROW(price_per_hour_cents, currency)::price
+            FROM
+                pitches
+            WHERE
+                id = ANY($1)
+            "#,
+        )
+        .bind(&pitch_ids)
+        .fetch_all(&mut *connection)
+        .await
+        .map_err(PricingRuleControllerError::Sqlx)?
+        .into_iter()
+        .collect();
+
+        let windows: Vec<PeriodRuleWindow> = sqlx::query_as(
+            r#"
+            SELECT
+                periods.index,
+                pricing_rules.id AS rule_id,
+                pricing_rules.name AS rule_name,
+                pricing_rules.priority,
+                pricing_rules.min_lead_minutes,
+                pricing_rules.max_lead_minutes,
+                pricing_rules.price_per_hour_cents,
+                pricing_rules.adjustment_percent,
+                windows.starts_at,
+                windows.ends_at
+            FROM
+                UNNEST($1::UUID[], $2::TIMESTAMPTZ[], $3::TIMESTAMPTZ[])
+                    WITH ORDINALITY AS periods (pitch_id, starts_at, ends_at, index)
+            CROSS JOIN LATERAL
+                pricing_rule_windows(periods.pitch_id, TSTZRANGE(periods.starts_at, periods.ends_at))
+                    AS windows
+            JOIN
+                pricing_rules
+            ON
+                pricing_rules.id = windows.rule_id
+            "#,
+        )
+        .bind(&pitch_ids)
+        .bind(&starts_ats)
+        .bind(&ends_ats)
+        .fetch_all(&mut *connection)
+        .await
+        .map_err(PricingRuleControllerError::Sqlx)?;
+
+        let mut windows_by_period: HashMap<i64, Vec<RuleWindow>> = HashMap::new();
+        for window in windows {
+            windows_by_period
+                .entry(window.index)
+                .or_default()
+                .push(window.window);
+        }
+
+        periods
+            .iter()
+            .zip(1..)
+            .map(|(period, index)| {
+                let price_per_hour = prices_per_hour
+                    .get(&period.pitch_id)
+                    .ok_or(PricingRuleControllerError::PitchNotFound)?;
+                let windows = windows_by_period.get(&index).map_or(&[][..], Vec::as_slice);
+                engine::quote(
+                    *price_per_hour,
+                    period.starts_at,
+                    period.ends_at,
+                    now,
+                    windows,
+                )
+                .map_err(|_| PricingRuleControllerError::PriceOutOfRange)
+            })
+            .collect()
+    }
+
+    async fn check_pitch(
+        connection: &mut PgConnection,
+        pitch_id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), PricingRuleControllerError> {
+        sqlx::query(
+            r#"
+            SELECT
+                1
+            FROM
+                pitches
+            WHERE
+                id = $1
+            AND
+                ($2::UUID IS NULL OR owner_id = $2)
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(actor.owner_id())
+        .fetch_optional(connection)
+        .await
+        .map_err(PricingRuleControllerError::Sqlx)?
+        .ok_or(PricingRuleControllerError::PitchNotFound)?;
+
+        Ok(())
+    }
+}
+
+/// A rule window tagged with the 1-based position of its period
+#[derive(FromRow)]
+struct PeriodRuleWindow {
+    index: i64,
+    #[sqlx(flatten)]
+    window: RuleWindow,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            PitchActor,
+            pricing_rules::{
+                controller::PricingRuleController,
+                errors::PricingRuleControllerError,
+                models::{InsertPricingRulePayload, PriceAdjustment},
+            },
+            test_utils::{delete_business, insert_business_with_pitch},
+        },
+        models::{
+            ModelManager,
+            price::Price,
+            tables::{PitchStatus, Weekday},
+        },
+    };
+    use anyhow::Context;
+    use chrono::{DateTime, Duration, NaiveTime, Utc};
+
+    /// Every day between `starts_at` and `ends_at`, without dates or lead times
+    fn daily_rule(
+        name: &str,
+        priority: i32,
+        starts_at: &str,
+        ends_at: &str,
+        adjustment: PriceAdjustment,
+    ) -> anyhow::Result<InsertPricingRulePayload> {
+        Ok(InsertPricingRulePayload {
+            name: name.to_string(),
+            priority,
+            weekdays: vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+                Weekday::Saturday,
+                Weekday::Sunday,
+            ],
+            starts_at: starts_at.parse()?,
+            ends_at: ends_at.parse()?,
+            starts_on: None,
+            until: None,
+            min_lead_minutes: None,
+            max_lead_minutes: None,
+            adjustment,
+        })
+    }
+
+    /// `hour` o'clock tomorrow, in UTC
+    fn tomorrow_at(hour: i64) -> DateTime<Utc> {
+        (Utc::now().date_naive() + Duration::days(1))
+            .and_time(NaiveTime::MIN)
+            .and_utc()
+            + Duration::hours(hour)
+    }
+
+    #[tokio::test]
+    async fn test_get_price_quote_ok_breaks_down_rules() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "priced_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        let peak = daily_rule("peak", 1, "18:00", "22:00", PriceAdjustment::Percent(50))?;
+        let peak_id = PricingRuleController::create_rule(&model_manager, pitch_id, actor, peak)
+            .await
+            .context("failed while creating peak rule")?;
+        let happy_hour = daily_rule(
+            "happy_hour",
+            2,
+            "18:00",
+            "19:00",
+            PriceAdjustment::PricePerHourCents(8000),
+        )?;
+        let happy_hour_id =
+            PricingRuleController::create_rule(&model_manager, pitch_id, actor, happy_hour)
+                .await
+                .context("failed while creating happy hour rule")?;
+
+        // exec
+        let quote = PricingRuleController::get_price_quote(
+            &model_manager,
+            pitch_id,
+            None,
+            tomorrow_at(17),
+            tomorrow_at(20),
+        )
+        .await
+        .context("failed while quoting")?;
+
+        // check
+        let aed = "AED".parse()?;
+        assert_eq!(quote.price, Price::new(33000, aed));
+        let segments: Vec<_> = quote
+            .segments
+            .iter()
+            .map(|segment| {
+                (
+                    segment.starts_at.to_utc(),
+                    segment.rule.as_ref().map(|rule| rule.id),
+                    segment.price.amount_cents(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            segments,
+            [
+                (tomorrow_at(17), None, 10000),
+                (tomorrow_at(18), Some(happy_hour_id), 8000),
+                (tomorrow_at(19), Some(peak_id), 15000),
+            ]
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_rule_err_priority_taken() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "priced_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        let peak = daily_rule("peak", 1, "18:00", "22:00", PriceAdjustment::Percent(50))?;
+        PricingRuleController::create_rule(&model_manager, pitch_id, actor, peak)
+            .await
+            .context("failed while creating peak rule")?;
+        let morning = daily_rule(
+            "morning",
+            1,
+            "08:00",
+            "10:00",
+            PriceAdjustment::Percent(-20),
+        )?;
+
+        // exec
+        let result =
+            PricingRuleController::create_rule(&model_manager, pitch_id, actor, morning).await;
+
+        // check
+        assert!(
+            matches!(result, Err(PricingRuleControllerError::PriorityTaken)),
+            "result: {result:?}"
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+}
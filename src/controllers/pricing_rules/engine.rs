@@ -0,0 +1,232 @@
+//! Prices a period of a pitch from its hourly price and the windows of its pricing rules.
+//!
+//! The period is cut wherever a rule window starts or ends. Each part gets the hourly price of
+//! the highest priority rule covering it, or the pitch's own price, and is charged pro rata.
+
+use crate::{
+    controllers::pricing_rules::models::{
+        AppliedPricingRule, PriceQuote, PriceSegment, RuleWindow,
+    },
+    models::price::{Price, PriceError},
+};
+use chrono::{DateTime, Utc};
+
+const SECONDS_PER_HOUR: i64 = 60 * 60;
+
+/// Prices `[starts_at, ends_at)` as computed at `now`, which decides which lead times match
+pub fn quote(
+    price_per_hour: Price,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    windows: &[RuleWindow],
+) -> Result<PriceQuote, PriceError> {
+    let lead_minutes = (starts_at - now).num_minutes();
+    let windows: Vec<_> = windows
+        .iter()
+        .filter(|window| window.matches_lead(lead_minutes))
+        .collect();
+
+    let mut boundaries = vec![starts_at, ends_at];
+    for window in &windows {
+        boundaries.push(window.starts_at.clamp(starts_at, ends_at));
+        boundaries.push(window.ends_at.clamp(starts_at, ends_at));
+    }
+    boundaries.sort();
+    boundaries.dedup();
+
+    // consecutive parts under the same rule are merged before pricing, to round only once
+    let mut parts: Vec<(DateTime<Utc>, DateTime<Utc>, Option<&RuleWindow>)> = Vec::new();
+    for pair in boundaries.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        let rule = windows
+            .iter()
+            .filter(|window| window.starts_at <= from && to <= window.ends_at)
+            .max_by_key(|window| window.priority)
+            .copied();
+        match parts.last_mut() {
+            Some((_, last_to, last_rule))
+                if last_rule.map(|window| window.rule_id) == rule.map(|window| window.rule_id) =>
+            {
+                *last_to = to;
+            }
+            _ => parts.push((from, to, rule)),
+        }
+    }
+
+    let mut price = Price::zero(price_per_hour.currency());
+    let mut segments = Vec::with_capacity(parts.len());
+    for (from, to, rule) in parts {
+        let segment_price_per_hour = match rule {
+            Some(window) => window.price_per_hour(price_per_hour)?,
+            None => price_per_hour,
+        };
+        let segment_price = segment_price_per_hour
+            .checked_mul_ratio((to - from).num_seconds(), SECONDS_PER_HOUR)?;
+        price = price.checked_add(segment_price)?;
+        segments.push(PriceSegment {
+            starts_at: from,
+            ends_at: to,
+            rule: rule.map(|window| AppliedPricingRule {
+                id: window.rule_id,
+                name: window.rule_name.clone(),
+            }),
+            price_per_hour: segment_price_per_hour,
+            price: segment_price,
+        });
+    }
+
+    Ok(PriceQuote { price, segments })
+}
+
+impl RuleWindow {
+    fn matches_lead(&self, lead_minutes: i64) -> bool {
+        self.min_lead_minutes
+            .is_none_or(|min_lead_minutes| lead_minutes >= i64::from(min_lead_minutes))
+            && self
+                .max_lead_minutes
+                .is_none_or(|max_lead_minutes| lead_minutes <= i64::from(max_lead_minutes))
+    }
+
+    fn price_per_hour(&self, pitch_price_per_hour: Price) -> Result<Price, PriceError> {
+        match (self.price_per_hour_cents, self.adjustment_percent) {
+            (Some(cents), _) => Ok(Price::new(cents.into(), pitch_price_per_hour.currency())),
+            (None, Some(percent)) => {
+                pitch_price_per_hour.checked_mul_ratio(100 + i64::from(percent), 100)
+            }
+            (None, None) => Ok(pitch_price_per_hour),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    fn at(hour: i64) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH + Duration::days(30) + Duration::hours(hour)
+    }
+
+    fn window(priority: i32, from: i64, to: i64, adjustment_percent: i32) -> RuleWindow {
+        RuleWindow {
+            rule_id: Uuid::new_v4(),
+            rule_name: format!("rule_{priority}"),
+            priority,
+            min_lead_minutes: None,
+            max_lead_minutes: None,
+            price_per_hour_cents: None,
+            adjustment_percent: Some(adjustment_percent),
+            starts_at: at(from),
+            ends_at: at(to),
+        }
+    }
+
+    #[test]
+    fn test_quote_ok_splits_at_rule_windows() {
+        let aed = "AED".parse().unwrap();
+        let peak = window(1, 18, 22, 50);
+
+        let quote = quote(Price::new(10_000, aed), at(17), at(19), at(0), &[peak]).unwrap();
+
+        assert_eq!(quote.price, Price::new(25_000, aed));
+        let segments: Vec<_> = quote
+            .segments
+            .iter()
+            .map(|segment| {
+                (
+                    segment.starts_at,
+                    segment.ends_at,
+                    segment.price.amount_cents(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            segments,
+            [(at(17), at(18), 10_000), (at(18), at(19), 15_000)]
+        );
+        assert_eq!(quote.segments[1].rule.as_ref().unwrap().name, "rule_1");
+    }
+
+    #[test]
+    fn test_quote_ok_highest_priority_wins() {
+        let aed = "AED".parse().unwrap();
+        let peak = window(1, 17, 22, 50);
+        let happy_hour = RuleWindow {
+            price_per_hour_cents: Some(8_000),
+            adjustment_percent: None,
+            ..window(2, 18, 19, 0)
+        };
+
+        let quote = quote(
+            Price::new(10_000, aed),
+            at(17),
+            at(20),
+            at(0),
+            &[peak, happy_hour],
+        )
+        .unwrap();
+
+        let prices: Vec<_> = quote
+            .segments
+            .iter()
+            .map(|segment| segment.price_per_hour.amount_cents())
+            .collect();
+        assert_eq!(prices, [15_000, 8_000, 15_000]);
+        assert_eq!(quote.price, Price::new(38_000, aed));
+    }
+
+    #[test]
+    fn test_quote_ok_lead_time() {
+        let aed = "AED".parse().unwrap();
+        let last_minute = RuleWindow {
+            max_lead_minutes: Some(120),
+            ..window(1, 0, 48, -20)
+        };
+
+        let early = quote(Price::new(10_000, aed), at(20), at(21), at(0), &[]).unwrap();
+        let late = quote(
+            Price::new(10_000, aed),
+            at(20),
+            at(21),
+            at(19),
+            std::slice::from_ref(&last_minute),
+        )
+        .unwrap();
+        let too_early = quote(
+            Price::new(10_000, aed),
+            at(20),
+            at(21),
+            at(0),
+            &[last_minute],
+        )
+        .unwrap();
+
+        assert_eq!(early.price, Price::new(10_000, aed));
+        assert_eq!(late.price, Price::new(8_000, aed));
+        assert_eq!(too_early.price, Price::new(10_000, aed));
+        assert!(too_early.segments[0].rule.is_none());
+    }
+
+    #[test]
+    fn test_quote_ok_merges_consecutive_windows_of_a_rule() {
+        let aed = "AED".parse().unwrap();
+        let mut first_day = window(1, 0, 24, 10);
+        let mut second_day = window(1, 24, 48, 10);
+        second_day.rule_id = first_day.rule_id;
+        first_day.rule_name = second_day.rule_name.clone();
+
+        let quote = quote(
+            Price::new(1_000, aed),
+            at(23),
+            at(25),
+            at(0),
+            &[first_day, second_day],
+        )
+        .unwrap();
+
+        assert_eq!(quote.segments.len(), 1);
+        assert_eq!(quote.price, Price::new(2_200, aed));
+    }
+}
@@ -0,0 +1,13 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum PricingRuleControllerError {
+    PitchNotFound,
+    RuleNotFound,
+    /// another rule of the pitch has the same priority
+    PriorityTaken,
+    PriceOutOfRange,
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(PricingRuleControllerError);
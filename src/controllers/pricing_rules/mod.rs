@@ -0,0 +1,4 @@
+pub mod controller;
+pub mod engine;
+pub mod errors;
+pub mod models;
@@ -0,0 +1,103 @@
+use crate::models::{price::Price, tables::Weekday, time_of_day::TimeOfDay};
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Adjusts the hourly price of a pitch, the times and dates are wall clock ones in the pitch's
+/// timezone
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct PricingRule {
+    pub id: Uuid,
+    pub name: String,
+    /// the highest one wins when several rules match
+    pub priority: i32,
+    pub weekdays: Vec<Weekday>,
+    pub starts_at: TimeOfDay,
+    pub ends_at: TimeOfDay,
+    pub starts_on: Option<NaiveDate>,
+    /// included
+    pub until: Option<NaiveDate>,
+    /// minutes between the price computation and the start of the timeslot
+    pub min_lead_minutes: Option<i32>,
+    pub max_lead_minutes: Option<i32>,
+    /// exactly one of `price_per_hour_cents` and `adjustment_percent` is set
+    pub price_per_hour_cents: Option<i32>,
+    pub adjustment_percent: Option<i32>,
+}
+
+pub enum PriceAdjustment {
+    /// replaces the hourly price of the pitch, in its currency
+    PricePerHourCents(i32),
+    /// `25` for 25% more, `-10` for a 10% discount
+    Percent(i32),
+}
+
+pub struct InsertPricingRulePayload {
+    pub name: String,
+    pub priority: i32,
+    pub weekdays: Vec<Weekday>,
+    pub starts_at: TimeOfDay,
+    pub ends_at: TimeOfDay,
+    pub starts_on: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub min_lead_minutes: Option<i32>,
+    pub max_lead_minutes: Option<i32>,
+    pub adjustment: PriceAdjustment,
+}
+
+/// A period of a pitch to price
+#[derive(Clone, Copy)]
+pub struct PricedPeriod {
+    pub pitch_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// Where a rule applies within a period, lead times aside
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct RuleWindow {
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub priority: i32,
+    pub min_lead_minutes: Option<i32>,
+    pub max_lead_minutes: Option<i32>,
+    pub price_per_hour_cents: Option<i32>,
+    pub adjustment_percent: Option<i32>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct PriceQuote {
+    pub price: Price,
+    /// consecutive parts of the period, each at a single hourly price
+    pub segments: Vec<PriceSegment>,
+}
+
+/// Quote of a single period, with the offsets of the pitch's timezone
+#[cfg_attr(test, derive(Debug))]
+pub struct PitchPriceQuote {
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+    pub price: Price,
+    pub segments: Vec<PriceSegment<FixedOffset>>,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct PriceSegment<Tz: TimeZone = Utc> {
+    pub starts_at: DateTime<Tz>,
+    pub ends_at: DateTime<Tz>,
+    /// `None` where no rule applies and the pitch's own price is used
+    pub rule: Option<AppliedPricingRule>,
+    pub price_per_hour: Price,
+    pub price: Price,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Clone)]
+pub struct AppliedPricingRule {
+    pub id: Uuid,
+    pub name: String,
+}
@@ -0,0 +1,365 @@
+use crate::{
+    controllers::search::{
+        errors::SearchControllerError,
+        models::{AreaSearchHit, BusinessSearchHit, PitchSearchHit, SearchResults},
+    },
+    models::ModelManager,
+};
+
+pub struct SearchController;
+
+impl SearchController {
+    /// Searches pitch names and descriptions, business names and cities.
+    ///
+    /// Snippets are escaped HTML with full-text matches in `<mark>` tags, typos are caught by
+    /// trigram similarity (`<%`) but aren't highlighted. Only published pitches are searched, and
+    /// so are businesses and cities that have at least one. Returns up to `limit` results per
+    /// group.
+    pub async fn search(
+        model_manager: &ModelManager,
+        text: &str,
+        limit: i64,
+    ) -> Result<SearchResults, SearchControllerError> {
+        let pitches = sqlx::query_as::<_, PitchSearchHit>(
+            r#"
+            SELECT
+                pitches.id,
+                pitches.display_name,
+                pitches.sport,
+                locations.city,
+                ts_headline(
+                    'english',
+                    escape_html(pitches.display_name || COALESCE(': ' || pitches.description, '')),
+                    query,
+                    'StartSel=<mark>, StopSel=</mark>'
+                ) AS snippet,
+                ROUND(
+                    GREATEST(
+                        ts_rank(pitches.search_vector, query),
+                        word_similarity($1, pitches.display_name)
+                    )::NUMERIC,
+                    3
+                )::FLOAT8 AS rank
+            FROM
+                pitches
+            CROSS JOIN
+                websearch_to_tsquery('english', $1) query
+            LEFT JOIN
+                locations
+            ON
+                locations.id = pitches.location_id
+            WHERE
+                pitches.status = 'published'
+            AND (
+                pitches.search_vector @@ query
+            OR
+                $1 <% pitches.display_name
+            )
+            ORDER BY
+                rank DESC, pitches.display_name
+            LIMIT $2
+            "#,
+        )
+        .bind(text)
+        .bind(limit)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(SearchControllerError::Sqlx)?;
+
+        let businesses = sqlx::query_as::<_, BusinessSearchHit>(
+            r#"
+            SELECT
+                business.user_id AS id,
+                business.display_name,
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        pitches
+                    WHERE
+                        pitches.owner_id = business.user_id
+                    AND
+                        pitches.status = 'published'
+                ) AS pitch_count,
+                ts_headline(
+                    'english',
+                    escape_html(business.display_name),
+                    query,
+                    'StartSel=<mark>, StopSel=</mark>'
+                ) AS snippet,
+                ROUND(
+                    GREATEST(
+                        ts_rank(to_tsvector('english', business.display_name), query),
+                        word_similarity($1, business.display_name)
+                    )::NUMERIC,
+                    3
+                )::FLOAT8 AS rank
+            FROM
+                business_profiles business
+            CROSS JOIN
+                websearch_to_tsquery('english', $1) query
+            WHERE
+                (
+                    to_tsvector('english', business.display_name) @@ query
+                OR
+                    $1 <% business.display_name
+                )
+            AND
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        pitches
+                    WHERE
+                        pitches.owner_id = business.user_id
+                    AND
+                        pitches.status = 'published'
+                )
+            ORDER BY
+                rank DESC, business.display_name
+            LIMIT $2
+            "#,
+        )
+        .bind(text)
+        .bind(limit)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(SearchControllerError::Sqlx)?;
+
+        let areas = sqlx::query_as::<_, AreaSearchHit>(
+            r#"
+            SELECT
+                locations.city,
+                locations.country,
+                COUNT(*) AS pitch_count,
+                ts_headline(
+                    'english',
+                    escape_html(locations.city),
+                    websearch_to_tsquery('english', $1),
+                    'StartSel=<mark>, StopSel=</mark>'
+                ) AS snippet,
+                ROUND(
+                    GREATEST(
+                        ts_rank(
+                            to_tsvector('english', locations.city),
+                            websearch_to_tsquery('english', $1)
+                        ),
+                        word_similarity($1, locations.city)
+                    )::NUMERIC,
+                    3
+                )::FLOAT8 AS rank
+            FROM
+                locations
+            JOIN
+                pitches
+            ON
+                pitches.location_id = locations.id
+            WHERE
+                pitches.status = 'published'
+            AND (
+                to_tsvector('english', locations.city) @@ websearch_to_tsquery('english', $1)
+            OR
+                $1 <% locations.city
+            )
+            GROUP BY
+                locations.city, locations.country
+            ORDER BY
+                rank DESC, locations.city
+            LIMIT $2
+            "#,
+        )
+        .bind(text)
+        .bind(limit)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(SearchControllerError::Sqlx)?;
+
+        Ok(SearchResults {
+            pitches,
+            businesses,
+            areas,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            PitchActor, PitchController, UpdateBusinessProfilePayload, UpdateUserInfoPayload,
+            UpdateUserProfilePayload, UserController,
+            search::controller::SearchController,
+            test_utils::{delete_business, insert_business_with_pitch},
+        },
+        models::{
+            ModelManager,
+            tables::{PitchStatus, UserRole},
+        },
+    };
+    use anyhow::Context;
+    use rand::distr::{Alphanumeric, SampleString};
+    use uuid::uuid;
+
+    #[tokio::test]
+    async fn test_search_ok_typo_matches_pitch_names() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let results = SearchController::search(&model_manager, "footbal", 5)
+            .await
+            .context("failed while searching")?;
+
+        // check
+        let pitch_ids: Vec<_> = results.pitches.iter().map(|pitch| pitch.id).collect();
+        assert_eq!(
+            pitch_ids,
+            [
+                uuid!("00000000-0000-0000-0000-000000000006"),
+                uuid!("00000000-0000-0000-0000-000000000007"),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_ok_description_highlighted() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let results = SearchController::search(&model_manager, "lake", 5)
+            .await
+            .context("failed while searching")?;
+
+        // check
+        assert_eq!(results.pitches.len(), 1);
+        assert_eq!(
+            results.pitches[0].id,
+            uuid!("00000000-0000-0000-0000-000000000006")
+        );
+        assert!(results.pitches[0].snippet.contains("<mark>lake</mark>"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_ok_snippets_escape_html() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let word = Alphanumeric
+            .sample_string(&mut rand::rng(), 16)
+            .to_lowercase();
+        let (business_id, pitch_id) = insert_business_with_pitch(
+            &model_manager,
+            &format!("{word} arena"),
+            PitchStatus::Published,
+        )
+        .await?;
+        sqlx::query("UPDATE pitches SET description = $1 WHERE id = $2")
+            .bind(format!("<script>alert('{word}')</script> & more"))
+            .bind(pitch_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while setting description")?;
+
+        // exec
+        let results = SearchController::search(&model_manager, &word, 5)
+            .await
+            .context("failed while searching")?;
+
+        // check
+        assert_eq!(results.pitches.len(), 1);
+        let snippet = &results.pitches[0].snippet;
+        assert!(!snippet.contains("<script>"));
+        assert!(snippet.contains("&lt;script&gt;"));
+        assert!(snippet.contains("&amp; more"));
+        assert!(snippet.contains(&format!("<mark>{word}</mark>")));
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_ok_areas_count_pitches() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // exec
+        let results = SearchController::search(&model_manager, "sharjah", 5)
+            .await
+            .context("failed while searching")?;
+
+        // check
+        assert_eq!(results.areas.len(), 1);
+        assert_eq!(results.areas[0].city, "Sharjah");
+        assert_eq!(results.areas[0].pitch_count, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_ok_hidden_pitches_excluded() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let word = Alphanumeric
+            .sample_string(&mut rand::rng(), 16)
+            .to_lowercase();
+        let (business_id, pitch_id) = insert_business_with_pitch(
+            &model_manager,
+            &format!("{word} arena"),
+            PitchStatus::Published,
+        )
+        .await?;
+        let business_info = UpdateUserInfoPayload {
+            username: None,
+            password: None,
+            profile: Some(UpdateUserProfilePayload::Business(
+                UpdateBusinessProfilePayload {
+                    display_name: Some(format!("{word} club")),
+                },
+            )),
+        };
+        UserController::update_by_id(
+            &model_manager,
+            business_id,
+            UserRole::Business,
+            business_info,
+        )
+        .await
+        .context("failed while naming business")?;
+        let admin_id = uuid!("00000000-0000-0000-0000-000000000005");
+
+        // exec
+        let visible = SearchController::search(&model_manager, &word, 5)
+            .await
+            .context("failed while searching")?;
+        PitchController::set_pitch_status_by_id(
+            &model_manager,
+            pitch_id,
+            PitchActor::Admin(admin_id),
+            PitchStatus::Hidden,
+        )
+        .await
+        .context("failed while hiding pitch")?;
+        let hidden = SearchController::search(&model_manager, &word, 5)
+            .await
+            .context("failed while searching")?;
+
+        // check
+        assert_eq!(visible.pitches.len(), 1);
+        assert_eq!(visible.pitches[0].id, pitch_id);
+        assert_eq!(visible.businesses.len(), 1);
+        assert_eq!(visible.businesses[0].id, business_id);
+        assert_eq!(visible.businesses[0].pitch_count, 1);
+        assert!(hidden.pitches.is_empty());
+        assert!(hidden.businesses.is_empty());
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum SearchControllerError {
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(SearchControllerError);
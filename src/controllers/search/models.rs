@@ -0,0 +1,41 @@
+use crate::models::tables::Sport;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Each group is ranked on its own, best match first
+pub struct SearchResults {
+    pub pitches: Vec<PitchSearchHit>,
+    pub businesses: Vec<BusinessSearchHit>,
+    pub areas: Vec<AreaSearchHit>,
+}
+
+#[derive(FromRow)]
+pub struct PitchSearchHit {
+    pub id: Uuid,
+    pub display_name: String,
+    pub sport: Sport,
+    pub city: Option<String>,
+    /// name and description with the matched words wrapped in `<mark>`
+    pub snippet: String,
+    pub rank: f64,
+}
+
+#[derive(FromRow)]
+pub struct BusinessSearchHit {
+    pub id: Uuid,
+    pub display_name: String,
+    /// published pitches only
+    pub pitch_count: i64,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+#[derive(FromRow)]
+pub struct AreaSearchHit {
+    pub city: String,
+    pub country: String,
+    /// published pitches only
+    pub pitch_count: i64,
+    pub snippet: String,
+    pub rank: f64,
+}
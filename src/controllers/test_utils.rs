@@ -0,0 +1,139 @@
+//! Fixtures shared by the database tests of the controllers
+
+use crate::{
+    controllers::{
+        InsertPitchPayload, InsertUserPayload, PitchActor, PitchController, UserController,
+        UserProfile,
+    },
+    models::{
+        ModelManager,
+        tables::{BusinessProfile, PitchStatus, PlayerProfile, Sport},
+    },
+};
+use anyhow::Context;
+use rand::distr::{Alphanumeric, SampleString};
+use uuid::Uuid;
+
+/// Returns the id of a new business without pitches
+pub async fn insert_business(model_manager: &ModelManager) -> anyhow::Result<Uuid> {
+    let username = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let password = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let profile = UserProfile::Business(BusinessProfile {
+        display_name: Alphanumeric.sample_string(&mut rand::rng(), 16),
+    });
+    let user = InsertUserPayload {
+        username: &username,
+        password: &password,
+        profile: &profile,
+    };
+
+    UserController::insert_user(model_manager, user)
+        .await
+        .context("failed while inserting business")
+}
+
+/// Returns the ids of a new business and of its only padel pitch, in UTC at 100 AED per hour
+pub async fn insert_business_with_pitch(
+    model_manager: &ModelManager,
+    display_name: &str,
+    status: PitchStatus,
+) -> anyhow::Result<(Uuid, Uuid)> {
+    let business_id = insert_business(model_manager).await?;
+    let pitch_id = insert_pitch(
+        model_manager,
+        business_id,
+        pitch_payload(display_name, status)?,
+    )
+    .await?;
+
+    Ok((business_id, pitch_id))
+}
+
+/// A padel pitch in UTC at 100 AED per hour, to be adjusted with the struct update syntax
+pub fn pitch_payload(
+    display_name: &str,
+    status: PitchStatus,
+) -> anyhow::Result<InsertPitchPayload> {
+    Ok(InsertPitchPayload {
+        display_name: display_name.to_string(),
+        sport: Sport::Padel,
+        other_sports: Vec::new(),
+        price_per_hour: "100 AED".parse()?,
+        image_url: None,
+        surface: None,
+        environment: None,
+        description: None,
+        max_players: None,
+        amenities: Vec::new(),
+        location: None,
+        status,
+        timezone: "UTC".to_string(),
+    })
+}
+
+/// Returns the id of a new pitch of the business
+pub async fn insert_pitch(
+    model_manager: &ModelManager,
+    business_id: Uuid,
+    pitch: InsertPitchPayload,
+) -> anyhow::Result<Uuid> {
+    PitchController::create_pitch(
+        model_manager,
+        PitchActor::Owner(business_id),
+        business_id,
+        pitch,
+    )
+    .await
+    .context("failed while creating pitch")
+}
+
+/// Returns the id of a new padel player named Layla Haddad
+pub async fn insert_player(model_manager: &ModelManager) -> anyhow::Result<Uuid> {
+    let username = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let password = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let profile = UserProfile::Player(PlayerProfile {
+        first_name: "Layla".to_string(),
+        last_name: "Haddad".to_string(),
+        preferred_sports: vec![Sport::Padel],
+        sport_ratings: vec![],
+    });
+    let user = InsertUserPayload {
+        username: &username,
+        password: &password,
+        profile: &profile,
+    };
+
+    UserController::insert_user(model_manager, user)
+        .await
+        .context("failed while inserting player")
+}
+
+/// Deletes a business along with its pitches
+pub async fn delete_business(
+    model_manager: &ModelManager,
+    business_id: Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(business_id)
+        .execute(model_manager.db())
+        .await
+        .context("failed while deleting business")?;
+
+    Ok(())
+}
+
+/// Deletes users along with the bookings they made
+pub async fn delete_users(model_manager: &ModelManager, user_ids: &[Uuid]) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM bookings WHERE booked_by = ANY($1)")
+        .bind(user_ids)
+        .execute(model_manager.db())
+        .await
+        .context("failed while deleting bookings")?;
+    sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+        .bind(user_ids)
+        .execute(model_manager.db())
+        .await
+        .context("failed while deleting users")?;
+
+    Ok(())
+}
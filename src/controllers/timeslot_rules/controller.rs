@@ -0,0 +1,507 @@
+use crate::{
+    configs::config,
+    controllers::{
+        PitchActor,
+        timeslot_rules::{
+            errors::TimeslotRuleControllerError,
+            models::{CreatedTimeslotRule, InsertTimeslotRulePayload, TimeslotRule},
+        },
+    },
+    models::ModelManager,
+};
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+pub struct TimeslotRuleController;
+
+impl TimeslotRuleController {
+    /// Returns the rules of the pitch, oldest first
+    pub async fn get_rules(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        actor: PitchActor,
+    ) -> Result<Vec<TimeslotRule>, TimeslotRuleControllerError> {
+        let mut connection = model_manager
+            .db()
+            .acquire()
+            .await
+            .map_err(TimeslotRuleControllerError::Sqlx)?;
+
+        Self::check_pitch(&mut connection, pitch_id, actor).await?;
+
+        sqlx::query_as(
+            r#"
+            SELECT
+                id,
+                weekdays,
+                TO_CHAR(starts_at, 'HH24:MI') AS starts_at,
+                TO_CHAR(ends_at, 'HH24:MI') AS ends_at,
+                slot_minutes,
+                starts_on,
+                until
+            FROM
+                timeslot_rules
+            WHERE
+                pitch_id = $1
+            ORDER BY
+                created_at, id
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_all(&mut *connection)
+        .await
+        .map_err(TimeslotRuleControllerError::Sqlx)
+    }
+
+    /// Adds a rule to the pitch and generates its timeslots up to the configured horizon.
+    ///
+    /// Occurrences that overlap another timeslot or a confirmed closure, or fall outside the
+    /// opening hours, are skipped rather than failing the whole rule.
+    pub async fn create_rule(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        actor: PitchActor,
+        rule: InsertTimeslotRulePayload,
+    ) -> Result<CreatedTimeslotRule, TimeslotRuleControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(TimeslotRuleControllerError::Sqlx)?;
+
+        Self::check_pitch(&mut transaction, pitch_id, actor).await?;
+
+        let id = sqlx::query_scalar(
+            r#"
+            INSERT INTO timeslot_rules
+                (pitch_id, weekdays, starts_at, ends_at, slot_minutes, starts_on, until)
+            VALUES
+                ($1, $2, $3::TIME, $4::TIME, $5, $6, $7)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(rule.weekdays)
+        .bind(rule.starts_at)
+        .bind(rule.ends_at)
+        .bind(rule.slot_minutes)
+        .bind(rule.starts_on)
+        .bind(rule.until)
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(TimeslotRuleControllerError::Sqlx)?;
+
+        let created_timeslots = sqlx::query_scalar("SELECT materialize_timeslot_rule($1, $2)")
+            .bind(id)
+            .bind(Self::horizon())
+            .fetch_one(&mut *transaction)
+            .await
+            .map_err(TimeslotRuleControllerError::Sqlx)?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(TimeslotRuleControllerError::Sqlx)?;
+
+        Ok(CreatedTimeslotRule {
+            id,
+            created_timeslots,
+        })
+    }
+
+    /// Ends the series: its upcoming timeslots without bookings are deleted, the others are kept
+    /// as standalone timeslots.
+    pub async fn delete_rule_by_id(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), TimeslotRuleControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(TimeslotRuleControllerError::Sqlx)?;
+
+        Self::check_pitch(&mut transaction, pitch_id, actor).await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM
+                timeslots
+            WHERE
+                rule_id = $1
+            AND
+                pitch_id = $2
+            AND
+                LOWER(time_range) > NOW()
+            AND
+                NOT is_booked
+            AND
+                NOT EXISTS (SELECT 1 FROM bookings WHERE bookings.timeslot_id = timeslots.id)
+            "#,
+        )
+        .bind(id)
+        .bind(pitch_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(TimeslotRuleControllerError::Sqlx)?;
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM
+                timeslot_rules
+            WHERE
+                id = $1
+            AND
+                pitch_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(pitch_id)
+        .execute(&mut *transaction)
+        .await
+        .map_err(TimeslotRuleControllerError::Sqlx)?;
+
+        if result.rows_affected() == 0 {
+            return Err(TimeslotRuleControllerError::RuleNotFound);
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(TimeslotRuleControllerError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Rolls the horizon forward: generates the missing timeslots of every running rule.
+    ///
+    /// Safe to run any number of times, returns how many timeslots were created.
+    pub async fn materialize_rules(
+        model_manager: &ModelManager,
+    ) -> Result<i64, TimeslotRuleControllerError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT
+                COALESCE(SUM(materialize_timeslot_rule(id, $1)), 0)::BIGINT
+            FROM
+                timeslot_rules
+            WHERE
+                -- a day of margin, `until` is a local date of the pitch
+                until IS NULL OR until >= CURRENT_DATE - 1
+            "#,
+        )
+        .bind(Self::horizon())
+        .fetch_one(model_manager.db())
+        .await
+        .map_err(TimeslotRuleControllerError::Sqlx)
+    }
+
+    fn horizon() -> DateTime<Utc> {
+        Utc::now() + config().timeslot_rules_horizon
+    }
+
+    async fn check_pitch(
+        connection: &mut PgConnection,
+        pitch_id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), TimeslotRuleControllerError> {
+        sqlx::query(
+            r#"
+            SELECT
+                1
+            FROM
+                pitches
+            WHERE
+                id = $1
+            AND
+                ($2::UUID IS NULL OR owner_id = $2)
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(actor.owner_id())
+        .fetch_optional(connection)
+        .await
+        .map_err(TimeslotRuleControllerError::Sqlx)?
+        .ok_or(TimeslotRuleControllerError::PitchNotFound)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            InsertTimeslotPayload, PitchActor, PitchController, TimeslotController,
+            UpdatePitchPayload, UpdateTimeslotPayload,
+            test_utils::{delete_business, insert_business_with_pitch},
+            timeslot_rules::{
+                controller::TimeslotRuleController, models::InsertTimeslotRulePayload,
+            },
+        },
+        models::{
+            ModelManager,
+            tables::{PitchStatus, Weekday},
+        },
+    };
+    use anyhow::Context;
+    use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+    use uuid::Uuid;
+
+    /// Every day from 10:00 to 12:00 in 60 minutes slots, from `starts_on` to `until`
+    fn morning_rule(
+        starts_on: NaiveDate,
+        until: NaiveDate,
+    ) -> anyhow::Result<InsertTimeslotRulePayload> {
+        Ok(InsertTimeslotRulePayload {
+            weekdays: vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+                Weekday::Saturday,
+                Weekday::Sunday,
+            ],
+            starts_at: "10:00".parse()?,
+            ends_at: "12:00".parse()?,
+            slot_minutes: 60,
+            starts_on,
+            until: Some(until),
+        })
+    }
+
+    fn at(date: NaiveDate, hour: u32) -> DateTime<Utc> {
+        date.and_time(NaiveTime::MIN).and_utc() + Duration::hours(hour.into())
+    }
+
+    async fn materialize_rule(model_manager: &ModelManager, rule_id: Uuid) -> anyhow::Result<i32> {
+        sqlx::query_scalar("SELECT materialize_timeslot_rule($1, NOW() + INTERVAL '28 days')")
+            .bind(rule_id)
+            .fetch_one(model_manager.db())
+            .await
+            .context("failed while materializing rule")
+    }
+
+    async fn get_rule_starts(
+        model_manager: &ModelManager,
+        rule_id: Uuid,
+    ) -> anyhow::Result<Vec<DateTime<Utc>>> {
+        sqlx::query_scalar(
+            "SELECT LOWER(time_range) FROM timeslots WHERE rule_id = $1 ORDER BY LOWER(time_range)",
+        )
+        .bind(rule_id)
+        .fetch_all(model_manager.db())
+        .await
+        .context("failed while fetching rule timeslots")
+    }
+
+    #[tokio::test]
+    async fn test_create_rule_ok_materializes_idempotently() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "recurring_pitch", PitchStatus::Published)
+                .await?;
+        let tomorrow = Utc::now().date_naive() + Duration::days(1);
+        let rule = morning_rule(tomorrow, tomorrow + Duration::days(1))?;
+
+        // exec
+        let created_rule = TimeslotRuleController::create_rule(
+            &model_manager,
+            pitch_id,
+            PitchActor::Owner(business_id),
+            rule,
+        )
+        .await
+        .context("failed while creating rule")?;
+        let created_again = materialize_rule(&model_manager, created_rule.id).await?;
+
+        // check
+        assert_eq!(created_rule.created_timeslots, 4);
+        assert_eq!(created_again, 0);
+        let day_after = tomorrow + Duration::days(1);
+        assert_eq!(
+            get_rule_starts(&model_manager, created_rule.id).await?,
+            [
+                at(tomorrow, 10),
+                at(tomorrow, 11),
+                at(day_after, 10),
+                at(day_after, 11)
+            ]
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_rule_ok_skips_conflicts_and_keeps_exceptions() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "recurring_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        let tomorrow = Utc::now().date_naive() + Duration::days(1);
+        let day_after = tomorrow + Duration::days(1);
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at: at(tomorrow, 10),
+            ends_at: at(tomorrow, 11),
+            price: None,
+        };
+        TimeslotController::create_timeslot(&model_manager, actor, timeslot)
+            .await
+            .context("failed while creating timeslot")?;
+
+        // exec
+        let created_rule = TimeslotRuleController::create_rule(
+            &model_manager,
+            pitch_id,
+            actor,
+            morning_rule(tomorrow, day_after)?,
+        )
+        .await
+        .context("failed while creating rule")?;
+        let timeslot_ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM timeslots WHERE rule_id = $1 ORDER BY LOWER(time_range)",
+        )
+        .bind(created_rule.id)
+        .fetch_all(model_manager.db())
+        .await
+        .context("failed while fetching rule timeslots")?;
+        // move one occurrence and cancel another
+        let new_timeslot = UpdateTimeslotPayload {
+            starts_at: Some(at(tomorrow, 14)),
+            ends_at: Some(at(tomorrow, 15)),
+            price: None,
+        };
+        TimeslotController::update_timeslot_by_id(
+            &model_manager,
+            timeslot_ids[0],
+            actor,
+            new_timeslot,
+        )
+        .await
+        .context("failed while moving occurrence")?;
+        TimeslotController::delete_timeslot_by_id(&model_manager, timeslot_ids[1], actor)
+            .await
+            .context("failed while cancelling occurrence")?;
+        let created_again = materialize_rule(&model_manager, created_rule.id).await?;
+
+        // check
+        assert_eq!(created_rule.created_timeslots, 3);
+        assert_eq!(created_again, 0);
+        assert_eq!(
+            get_rule_starts(&model_manager, created_rule.id).await?,
+            [at(tomorrow, 14), at(day_after, 11)]
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_materialize_rule_ok_across_dst_transitions() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "recurring_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        let new_pitch = UpdatePitchPayload {
+            display_name: None,
+            sport: None,
+            other_sports: None,
+            price_per_hour: None,
+            image_url: None,
+            surface: None,
+            environment: None,
+            description: None,
+            max_players: None,
+            amenities: None,
+            location: None,
+            timezone: Some("Europe/Berlin".to_string()),
+        };
+        PitchController::update_pitch_by_id(&model_manager, pitch_id, actor, new_pitch)
+            .await
+            .context("failed while setting timezone")?;
+        // from 01:00 to 04:00 in Berlin, the clocks skip 02:00 to 03:00 on the first day and go
+        // through 02:00 to 03:00 twice on the second one
+        let mut rule_ids = Vec::new();
+        for day in ["2030-03-31", "2030-10-27"] {
+            let day = day.parse()?;
+            let rule = InsertTimeslotRulePayload {
+                starts_at: "01:00".parse()?,
+                ends_at: "04:00".parse()?,
+                ..morning_rule(day, day)?
+            };
+            let created_rule =
+                TimeslotRuleController::create_rule(&model_manager, pitch_id, actor, rule)
+                    .await
+                    .context("failed while creating rule")?;
+            rule_ids.push(created_rule.id);
+        }
+
+        // exec
+        let mut created = Vec::new();
+        for rule_id in &rule_ids {
+            let created_timeslots: i32 =
+                sqlx::query_scalar("SELECT materialize_timeslot_rule($1, '2031-01-01')")
+                    .bind(rule_id)
+                    .fetch_one(model_manager.db())
+                    .await
+                    .context("failed while materializing rule")?;
+            created.push(created_timeslots);
+        }
+
+        // check
+        // 02:00 doesn't exist on the first day, the repeated 02:00 is its first occurrence
+        assert_eq!(created, [2, 3]);
+        let periods: Vec<(DateTime<Utc>, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT
+                LOWER(time_range), UPPER(time_range)
+            FROM
+                timeslots
+            WHERE
+                pitch_id = $1
+            ORDER BY
+                LOWER(time_range)
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_all(model_manager.db())
+        .await
+        .context("failed while fetching rule timeslots")?;
+        let spring_forward = "2030-03-31".parse()?;
+        let fall_back = "2030-10-27".parse()?;
+        assert_eq!(
+            periods,
+            [
+                (at(spring_forward, 0), at(spring_forward, 1)),
+                (at(spring_forward, 1), at(spring_forward, 2)),
+                (at(fall_back, 0) - Duration::hours(1), at(fall_back, 0)),
+                (at(fall_back, 0), at(fall_back, 1)),
+                (at(fall_back, 2), at(fall_back, 3)),
+            ]
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+}
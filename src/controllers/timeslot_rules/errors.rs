@@ -0,0 +1,10 @@
+use crate::errors::error_impl;
+
+#[derive(Debug)]
+pub enum TimeslotRuleControllerError {
+    PitchNotFound,
+    RuleNotFound,
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(TimeslotRuleControllerError);
@@ -0,0 +1,36 @@
+use crate::models::{tables::Weekday, time_of_day::TimeOfDay};
+use chrono::NaiveDate;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Weekly series of timeslots, the times are wall clock times in the pitch's timezone
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct TimeslotRule {
+    pub id: Uuid,
+    pub weekdays: Vec<Weekday>,
+    /// of the first timeslot of the day
+    pub starts_at: TimeOfDay,
+    /// no timeslot of the day ends after it
+    pub ends_at: TimeOfDay,
+    pub slot_minutes: i32,
+    pub starts_on: NaiveDate,
+    /// included, `None` for a series without an end
+    pub until: Option<NaiveDate>,
+}
+
+pub struct InsertTimeslotRulePayload {
+    pub weekdays: Vec<Weekday>,
+    pub starts_at: TimeOfDay,
+    pub ends_at: TimeOfDay,
+    pub slot_minutes: i32,
+    pub starts_on: NaiveDate,
+    pub until: Option<NaiveDate>,
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub struct CreatedTimeslotRule {
+    pub id: Uuid,
+    /// how many timeslots were generated within the horizon right away
+    pub created_timeslots: i32,
+}
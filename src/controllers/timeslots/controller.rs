@@ -0,0 +1,1561 @@
+use crate::{
+    controllers::{
+        PitchActor, PricedPeriod, PricingRuleController,
+        timeslots::{
+            errors::TimeslotControllerError,
+            models::{
+                AppliedOperations, ImportAction, ImportRow, ImportRowError, ImportedRow,
+                ImportedTime, ImportedTimeslot, InsertTimeslotPayload, PricedTimeslot, Timeslot,
+                TimeslotFilters, TimeslotOperation, TimeslotOperationOutcome,
+                UpdateTimeslotPayload,
+            },
+        },
+    },
+    models::{
+        ModelManager,
+        pagination::{Keyset, PageRequest, Paged},
+        tables::PitchStatus,
+    },
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Connection, FromRow, PgConnection};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub struct TimeslotController;
+
+#[derive(FromRow)]
+struct ImportedPitch {
+    id: Uuid,
+    display_name: String,
+    timezone: String,
+}
+
+impl TimeslotController {
+    /// Returns the timeslots matching the filters, soonest first.
+    ///
+    /// Owners only see the timeslots of their pitches and admins see every timeslot, past ones
+    /// included. Everyone else only sees the upcoming timeslots of published pitches.
+    pub async fn get_timeslots(
+        model_manager: &ModelManager,
+        viewer: Option<PitchActor>,
+        filters: TimeslotFilters,
+        page: &PageRequest<<Timeslot as Keyset>::Key>,
+    ) -> Result<Paged<PricedTimeslot>, TimeslotControllerError> {
+        let (after_starts_at, after_id) = page.after.unzip();
+        let owner_id = viewer.and_then(|viewer| viewer.owner_id());
+        let can_manage = viewer.is_some();
+
+        let timeslots = sqlx::query_as(
+            r#"
+            SELECT
+                timeslots.id,
+                timeslots.pitch_id,
+                LOWER(timeslots.time_range) AS starts_at,
+                UPPER(timeslots.time_range) AS ends_at,
+                pitches.timezone,
+                LOWER(timeslots.time_range) AT TIME ZONE pitches.timezone AS local_starts_at,
+                UPPER(timeslots.time_range) AT TIME ZONE pitches.timezone AS local_ends_at,
+                timeslots.is_booked,
+                timeslots.rule_id,
+                timeslots.price AS custom_price
+            FROM
+                timeslots
+            JOIN
+                pitches
+            ON
+                pitches.id = timeslots.pitch_id
+            WHERE
+                ($1::UUID IS NULL OR pitches.owner_id = $1)
+            AND
+                ($2 OR (pitches.status = 'published' AND UPPER(timeslots.time_range) > NOW()))
+            AND
+                ($3::UUID IS NULL OR timeslots.pitch_id = $3)
+            AND
+                ($4::UUID IS NULL OR pitches.owner_id = $4)
+            AND
+                ($5::TIMESTAMPTZ IS NULL OR UPPER(timeslots.time_range) > $5)
+            AND
+                ($6::TIMESTAMPTZ IS NULL OR LOWER(timeslots.time_range) < $6)
+            AND (
+                $7::TIMESTAMPTZ IS NULL
+            OR
+                LOWER(timeslots.time_range) > $7
+            OR
+                (LOWER(timeslots.time_range) = $7 AND timeslots.id > $8)
+            )
+            ORDER BY
+                LOWER(timeslots.time_range), timeslots.id
+            LIMIT $9
+            "#,
+        )
+        .bind(owner_id)
+        .bind(can_manage)
+        .bind(filters.pitch_id)
+        .bind(filters.business_id)
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(after_starts_at)
+        .bind(after_id)
+        .bind(page.fetch_limit())
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(TimeslotControllerError::Sqlx)?;
+
+        let total = if page.include_total {
+            let total = sqlx::query_scalar(
+                r#"
+                SELECT
+                    COUNT(*)
+                FROM
+                    timeslots
+                JOIN
+                    pitches
+                ON
+                    pitches.id = timeslots.pitch_id
+                WHERE
+                    ($1::UUID IS NULL OR pitches.owner_id = $1)
+                AND
+                    ($2 OR (pitches.status = 'published' AND UPPER(timeslots.time_range) > NOW()))
+                AND
+                    ($3::UUID IS NULL OR timeslots.pitch_id = $3)
+                AND
+                    ($4::UUID IS NULL OR pitches.owner_id = $4)
+                AND
+                    ($5::TIMESTAMPTZ IS NULL OR UPPER(timeslots.time_range) > $5)
+                AND
+                    ($6::TIMESTAMPTZ IS NULL OR LOWER(timeslots.time_range) < $6)
+                "#,
+            )
+            .bind(owner_id)
+            .bind(can_manage)
+            .bind(filters.pitch_id)
+            .bind(filters.business_id)
+            .bind(filters.from)
+            .bind(filters.to)
+            .fetch_one(model_manager.db())
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+            Some(total)
+        } else {
+            None
+        };
+
+        let mut connection = model_manager
+            .db()
+            .acquire()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+        let timeslots = Self::price_timeslots(&mut connection, timeslots).await?;
+
+        Ok(Paged::new(timeslots, page.limit, total))
+    }
+
+    /// Same as [`Self::get_timeslots`] for a single pitch, reporting pitches the viewer can't see
+    /// as not found instead of returning an empty page.
+    pub async fn get_pitch_timeslots(
+        model_manager: &ModelManager,
+        pitch_id: Uuid,
+        viewer: Option<PitchActor>,
+        filters: TimeslotFilters,
+        page: &PageRequest<<Timeslot as Keyset>::Key>,
+    ) -> Result<Paged<PricedTimeslot>, TimeslotControllerError> {
+        let (owner_id, status): (Uuid, PitchStatus) = sqlx::query_as(
+            r#"
+            SELECT
+                owner_id, status
+            FROM
+                pitches
+            WHERE
+                id = $1
+            "#,
+        )
+        .bind(pitch_id)
+        .fetch_optional(model_manager.db())
+        .await
+        .map_err(TimeslotControllerError::Sqlx)?
+        .ok_or(TimeslotControllerError::PitchNotFound)?;
+
+        let can_see = match viewer {
+            Some(PitchActor::Admin(_)) => true,
+            Some(PitchActor::Owner(viewer_id)) => viewer_id == owner_id,
+            None => status == PitchStatus::Published,
+        };
+        if !can_see {
+            return Err(TimeslotControllerError::PitchNotFound);
+        }
+
+        let filters = TimeslotFilters {
+            pitch_id: Some(pitch_id),
+            ..filters
+        };
+        Self::get_timeslots(model_manager, viewer, filters, page).await
+    }
+
+    /// Adds a timeslot to the pitch.
+    ///
+    /// The timeslot has to start in the future, fit in the pitch's opening hours and not overlap
+    /// another timeslot of the pitch or of any pitch sharing a unit with it.
+    pub async fn create_timeslot(
+        model_manager: &ModelManager,
+        actor: PitchActor,
+        timeslot: InsertTimeslotPayload,
+    ) -> Result<Uuid, TimeslotControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        let id = Self::insert_timeslot(&mut transaction, actor, &timeslot).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        Ok(id)
+    }
+
+    /// Moves or reprices an upcoming timeslot that isn't booked yet.
+    ///
+    /// The new period follows the same rules as in [`Self::create_timeslot`].
+    pub async fn update_timeslot_by_id(
+        model_manager: &ModelManager,
+        id: Uuid,
+        actor: PitchActor,
+        new_timeslot: UpdateTimeslotPayload,
+    ) -> Result<(), TimeslotControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        Self::move_timeslot(&mut transaction, id, actor, &new_timeslot).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Deletes an upcoming timeslot that isn't booked.
+    ///
+    /// Bookings reference their timeslot, so a booked timeslot is also kept by the database.
+    pub async fn delete_timeslot_by_id(
+        model_manager: &ModelManager,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), TimeslotControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        Self::remove_timeslot(&mut transaction, id, actor).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Runs the operations in order within a single transaction.
+    ///
+    /// Each operation gets its own savepoint, so a failing one doesn't abort the others and every
+    /// result is reported. With `all_or_nothing` a single failure rolls everything back, otherwise
+    /// the successful operations are committed. Database errors other than the constraints
+    /// guarding timeslots abort the whole batch.
+    pub async fn apply_operations(
+        model_manager: &ModelManager,
+        actor: PitchActor,
+        operations: Vec<TimeslotOperation>,
+        all_or_nothing: bool,
+    ) -> Result<AppliedOperations, TimeslotControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        let mut results = Vec::with_capacity(operations.len());
+        for operation in operations {
+            let result = match operation {
+                TimeslotOperation::Create(timeslot) => {
+                    Self::insert_timeslot(&mut transaction, actor, &timeslot)
+                        .await
+                        .map(TimeslotOperationOutcome::Created)
+                }
+                TimeslotOperation::Update(id, new_timeslot) => {
+                    Self::move_timeslot(&mut transaction, id, actor, &new_timeslot)
+                        .await
+                        .map(|_| TimeslotOperationOutcome::Updated)
+                }
+                TimeslotOperation::Delete(id) => Self::remove_timeslot(&mut transaction, id, actor)
+                    .await
+                    .map(|_| TimeslotOperationOutcome::Deleted),
+            };
+            if let Err(TimeslotControllerError::Sqlx(err)) = result {
+                return Err(TimeslotControllerError::Sqlx(err));
+            }
+            results.push(result);
+        }
+
+        let is_committed = !all_or_nothing || results.iter().all(Result::is_ok);
+        if is_committed {
+            transaction.commit().await
+        } else {
+            transaction.rollback().await
+        }
+        .map_err(TimeslotControllerError::Sqlx)?;
+
+        Ok(AppliedOperations {
+            is_committed,
+            results,
+        })
+    }
+
+    /// Compares the rows of an imported file with the existing timeslots, within a single
+    /// transaction that is only committed when `apply` is set.
+    ///
+    /// Rows name their pitch by id or by name, among the actor's pitches, or default to
+    /// `pitch_id`. Each timeslot is created unless the same one already exists or it's in the
+    /// past, in which case it's skipped, or it can't be created, e.g. because it overlaps another
+    /// timeslot, in which case it's a conflict. Rows are processed in order, so a row overlapping
+    /// an earlier one of the file conflicts with it.
+    pub async fn import_timeslots(
+        model_manager: &ModelManager,
+        actor: PitchActor,
+        pitch_id: Option<Uuid>,
+        rows: Vec<ImportRow>,
+        apply: bool,
+    ) -> Result<Vec<ImportedRow>, TimeslotControllerError> {
+        let mut transaction = model_manager
+            .db()
+            .begin()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        let default_pitch = pitch_id.map(|pitch_id| pitch_id.to_string());
+        let pitch_refs: HashSet<&str> = rows
+            .iter()
+            .filter_map(|row| row.timeslot.as_ref().ok()?.pitch.as_deref())
+            .chain(default_pitch.as_deref())
+            .collect();
+        let pitches: Vec<ImportedPitch> = sqlx::query_as(
+            r#"
+            SELECT
+                id, display_name, timezone
+            FROM
+                pitches
+            WHERE
+                ($1::UUID IS NULL OR owner_id = $1)
+            AND
+                (id::TEXT = ANY($2) OR display_name = ANY($2))
+            "#,
+        )
+        .bind(actor.owner_id())
+        .bind(Vec::from_iter(pitch_refs))
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(TimeslotControllerError::Sqlx)?;
+        let find_pitch = |pitch_ref: &str| {
+            if let Ok(id) = pitch_ref.parse::<Uuid>() {
+                return pitches.iter().find(|pitch| pitch.id == id);
+            }
+            // names are only unique per business, admins importing for several ones use ids
+            let mut named = pitches
+                .iter()
+                .filter(|pitch| pitch.display_name == pitch_ref);
+            named.next().filter(|_| named.next().is_none())
+        };
+
+        // the local times are resolved in one go, in the timezone of their row or of their pitch,
+        // see `local_time_to_utc` for the times the clocks skip or repeat
+        let mut local_times: Vec<(NaiveDateTime, String)> = Vec::new();
+        let mut resolved_rows = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let resolved = row
+                .timeslot
+                .as_ref()
+                .map_err(|err| *err)
+                .and_then(|timeslot| {
+                    let pitch = timeslot
+                        .pitch
+                        .as_deref()
+                        .or(default_pitch.as_deref())
+                        .ok_or(ImportRowError::MissingPitch)?;
+                    let pitch = find_pitch(pitch).ok_or(ImportRowError::UnknownPitch)?;
+                    let mut local_index = |time: &ImportedTime| match time {
+                        ImportedTime::Utc(_) => None,
+                        ImportedTime::Local(time, timezone) => {
+                            let timezone = timezone.as_ref().unwrap_or(&pitch.timezone);
+                            local_times.push((*time, timezone.clone()));
+                            Some(local_times.len() - 1)
+                        }
+                    };
+                    let starts_at = local_index(&timeslot.starts_at);
+                    let ends_at = local_index(&timeslot.ends_at);
+                    Ok((pitch.id, &pitch.timezone, starts_at, ends_at))
+                });
+            resolved_rows.push(resolved);
+        }
+
+        let (times, timezones): (Vec<_>, Vec<_>) = local_times.into_iter().unzip();
+        let utc_times: Vec<Option<DateTime<Utc>>> = sqlx::query_scalar(
+            r#"
+            SELECT
+                CASE WHEN EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = local.timezone) THEN
+                    local_time_to_utc(local.time, local.timezone)
+                END
+            FROM
+                UNNEST($1::TIMESTAMP[], $2::TEXT[]) WITH ORDINALITY AS local(time, timezone, position)
+            ORDER BY
+                local.position
+            "#,
+        )
+        .bind(times)
+        .bind(timezones)
+        .fetch_all(&mut *transaction)
+        .await
+        .map_err(TimeslotControllerError::Sqlx)?;
+
+        let mut imported_rows = Vec::with_capacity(rows.len());
+        for (row, resolved) in rows.into_iter().zip(resolved_rows) {
+            let timeslot = match (row.timeslot, resolved) {
+                (Ok(timeslot), Ok((pitch_id, timezone, starts_at_index, ends_at_index))) => {
+                    let to_utc = |time: ImportedTime, index: Option<usize>| match time {
+                        ImportedTime::Utc(time) => Ok(time),
+                        ImportedTime::Local(..) => index
+                            .and_then(|index| utc_times.get(index).copied().flatten())
+                            .ok_or(ImportRowError::UnknownTimezone),
+                    };
+                    let timeslot =
+                        to_utc(timeslot.starts_at, starts_at_index).and_then(|starts_at| {
+                            let ends_at = to_utc(timeslot.ends_at, ends_at_index)?;
+                            if ends_at <= starts_at {
+                                return Err(ImportRowError::InvalidPeriod);
+                            }
+                            Ok(InsertTimeslotPayload {
+                                pitch_id,
+                                starts_at,
+                                ends_at,
+                                price: timeslot.price,
+                            })
+                        });
+                    match timeslot {
+                        Ok(timeslot) => {
+                            let action =
+                                Self::import_timeslot(&mut transaction, actor, &timeslot).await?;
+                            let (local_starts_at, local_ends_at) = sqlx::query_as(
+                                r#"
+                                SELECT
+                                    $1::TIMESTAMPTZ AT TIME ZONE $3,
+                                    $2::TIMESTAMPTZ AT TIME ZONE $3
+                                "#,
+                            )
+                            .bind(timeslot.starts_at)
+                            .bind(timeslot.ends_at)
+                            .bind(timezone)
+                            .fetch_one(&mut *transaction)
+                            .await
+                            .map_err(TimeslotControllerError::Sqlx)?;
+                            Ok(ImportedTimeslot {
+                                pitch_id: timeslot.pitch_id,
+                                starts_at: timeslot.starts_at,
+                                ends_at: timeslot.ends_at,
+                                local_starts_at,
+                                local_ends_at,
+                                price: timeslot.price,
+                                action,
+                            })
+                        }
+                        Err(err) => Err(err),
+                    }
+                }
+                (_, Err(err)) | (Err(err), _) => Err(err),
+            };
+            imported_rows.push(ImportedRow {
+                line: row.line,
+                timeslot,
+            });
+        }
+
+        if apply {
+            transaction.commit().await
+        } else {
+            transaction.rollback().await
+        }
+        .map_err(TimeslotControllerError::Sqlx)?;
+
+        Ok(imported_rows)
+    }
+
+    /// Fails only on database errors, the others make the timeslot a conflict
+    async fn import_timeslot(
+        connection: &mut PgConnection,
+        actor: PitchActor,
+        timeslot: &InsertTimeslotPayload,
+    ) -> Result<ImportAction, TimeslotControllerError> {
+        let existing_id: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT
+                id
+            FROM
+                timeslots
+            WHERE
+                pitch_id = $1
+            AND
+                time_range = TSTZRANGE($2, $3)
+            "#,
+        )
+        .bind(timeslot.pitch_id)
+        .bind(timeslot.starts_at)
+        .bind(timeslot.ends_at)
+        .fetch_optional(&mut *connection)
+        .await
+        .map_err(TimeslotControllerError::Sqlx)?;
+        if let Some(existing_id) = existing_id {
+            return Ok(ImportAction::SkipExisting(existing_id));
+        }
+
+        match Self::insert_timeslot(connection, actor, timeslot).await {
+            Ok(id) => Ok(ImportAction::Create(id)),
+            Err(TimeslotControllerError::InThePast) => Ok(ImportAction::SkipPast),
+            Err(TimeslotControllerError::Sqlx(err)) => Err(TimeslotControllerError::Sqlx(err)),
+            Err(err) => Ok(ImportAction::Conflict(err)),
+        }
+    }
+
+    /// Prices the timeslots without a custom price as if they were booked now
+    async fn price_timeslots(
+        connection: &mut PgConnection,
+        timeslots: Vec<Timeslot>,
+    ) -> Result<Vec<PricedTimeslot>, TimeslotControllerError> {
+        let periods: Vec<_> = timeslots
+            .iter()
+            .filter(|timeslot| timeslot.custom_price.is_none())
+            .map(|timeslot| PricedPeriod {
+                pitch_id: timeslot.pitch_id,
+                starts_at: timeslot.starts_at,
+                ends_at: timeslot.ends_at,
+            })
+            .collect();
+        let mut quotes = PricingRuleController::quote_periods(connection, &periods, Utc::now())
+            .await
+            .map_err(TimeslotControllerError::Pricing)?
+            .into_iter();
+
+        timeslots
+            .into_iter()
+            .map(|timeslot| {
+                let price = match timeslot.custom_price {
+                    Some(price) => price,
+                    None => {
+                        quotes
+                            .next()
+                            .ok_or(TimeslotControllerError::PitchNotFound)?
+                            .price
+                    }
+                };
+                Ok(PricedTimeslot { timeslot, price })
+            })
+            .collect()
+    }
+
+    fn check_period(
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+    ) -> Result<(), TimeslotControllerError> {
+        if starts_at >= ends_at {
+            return Err(TimeslotControllerError::InvalidPeriod);
+        }
+        if starts_at <= Utc::now() {
+            return Err(TimeslotControllerError::InThePast);
+        }
+
+        Ok(())
+    }
+
+    /// Only upcoming timeslots without bookings can be moved or deleted
+    fn check_can_change(timeslot: &Timeslot) -> Result<(), TimeslotControllerError> {
+        if timeslot.is_booked {
+            return Err(TimeslotControllerError::IsBooked);
+        }
+        if timeslot.starts_at <= Utc::now() {
+            return Err(TimeslotControllerError::InThePast);
+        }
+
+        Ok(())
+    }
+
+    /// Runs in a savepoint of the caller's transaction, which stays usable when it fails
+    async fn insert_timeslot(
+        connection: &mut PgConnection,
+        actor: PitchActor,
+        timeslot: &InsertTimeslotPayload,
+    ) -> Result<Uuid, TimeslotControllerError> {
+        Self::check_period(timeslot.starts_at, timeslot.ends_at)?;
+
+        let mut savepoint = connection
+            .begin()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        let result = sqlx::query_scalar(
+            r#"
+            INSERT INTO timeslots
+                (pitch_id, time_range, price)
+            SELECT
+                id, TSTZRANGE($3, $4), $5
+            FROM
+                pitches
+            WHERE
+                id = $1
+            AND
+                ($2::UUID IS NULL OR owner_id = $2)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(timeslot.pitch_id)
+        .bind(actor.owner_id())
+        .bind(timeslot.starts_at)
+        .bind(timeslot.ends_at)
+        .bind(timeslot.price)
+        .fetch_optional(&mut *savepoint)
+        .await;
+
+        match result.map_err(Self::map_write_error) {
+            Ok(Some(id)) => {
+                savepoint
+                    .commit()
+                    .await
+                    .map_err(TimeslotControllerError::Sqlx)?;
+                Ok(id)
+            }
+            Ok(None) => Err(TimeslotControllerError::PitchNotFound),
+            Err(TimeslotControllerError::Overlaps(_)) => {
+                savepoint
+                    .rollback()
+                    .await
+                    .map_err(TimeslotControllerError::Sqlx)?;
+                let conflicting_timeslot_id = Self::find_conflicting_timeslot(
+                    connection,
+                    timeslot.pitch_id,
+                    timeslot.starts_at,
+                    timeslot.ends_at,
+                    None,
+                )
+                .await?;
+                Err(TimeslotControllerError::Overlaps(conflicting_timeslot_id))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Runs in a savepoint of the caller's transaction, which stays usable when it fails
+    async fn move_timeslot(
+        connection: &mut PgConnection,
+        id: Uuid,
+        actor: PitchActor,
+        new_timeslot: &UpdateTimeslotPayload,
+    ) -> Result<(), TimeslotControllerError> {
+        let mut savepoint = connection
+            .begin()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        let timeslot = Self::lock_timeslot(&mut savepoint, id, actor).await?;
+        Self::check_can_change(&timeslot)?;
+
+        let starts_at = new_timeslot.starts_at.unwrap_or(timeslot.starts_at);
+        let ends_at = new_timeslot.ends_at.unwrap_or(timeslot.ends_at);
+        Self::check_period(starts_at, ends_at)?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE
+                timeslots
+            SET
+                time_range = TSTZRANGE($2, $3),
+                price = COALESCE($4, price)
+            WHERE
+                id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(starts_at)
+        .bind(ends_at)
+        .bind(new_timeslot.price)
+        .execute(&mut *savepoint)
+        .await;
+
+        match result.map_err(Self::map_write_error) {
+            Ok(_) => savepoint
+                .commit()
+                .await
+                .map_err(TimeslotControllerError::Sqlx),
+            Err(TimeslotControllerError::Overlaps(_)) => {
+                savepoint
+                    .rollback()
+                    .await
+                    .map_err(TimeslotControllerError::Sqlx)?;
+                let conflicting_timeslot_id = Self::find_conflicting_timeslot(
+                    connection,
+                    timeslot.pitch_id,
+                    starts_at,
+                    ends_at,
+                    Some(id),
+                )
+                .await?;
+                Err(TimeslotControllerError::Overlaps(conflicting_timeslot_id))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Runs in a savepoint of the caller's transaction, which stays usable when it fails
+    async fn remove_timeslot(
+        connection: &mut PgConnection,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<(), TimeslotControllerError> {
+        let mut savepoint = connection
+            .begin()
+            .await
+            .map_err(TimeslotControllerError::Sqlx)?;
+
+        let timeslot = Self::lock_timeslot(&mut savepoint, id, actor).await?;
+        Self::check_can_change(&timeslot)?;
+
+        // the occurrence is recorded so that its series doesn't generate it again
+        let result = sqlx::query(
+            r#"
+            WITH deleted AS (
+                DELETE FROM
+                    timeslots
+                WHERE
+                    id = $1
+                RETURNING
+                    rule_id, occurrence_starts_at
+            )
+            INSERT INTO cancelled_timeslot_occurrences
+                (rule_id, occurrence_starts_at)
+            SELECT
+                rule_id, occurrence_starts_at
+            FROM
+                deleted
+            WHERE
+                rule_id IS NOT NULL
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *savepoint)
+        .await;
+
+        match result {
+            Ok(_) => savepoint
+                .commit()
+                .await
+                .map_err(TimeslotControllerError::Sqlx),
+            Err(sqlx::Error::Database(err))
+                if err.constraint() == Some("bookings_timeslot_id_fkey") =>
+            {
+                Err(TimeslotControllerError::IsBooked)
+            }
+            Err(err) => Err(TimeslotControllerError::Sqlx(err)),
+        }
+    }
+
+    /// Maps the constraints guarding the period and the price of a timeslot to their errors.
+    ///
+    /// The conflicting timeslot of an overlap is left empty since the savepoint that hit it is
+    /// aborted, see [`Self::find_conflicting_timeslot`].
+    fn map_write_error(err: sqlx::Error) -> TimeslotControllerError {
+        match err {
+            sqlx::Error::Database(err)
+                if matches!(
+                    err.constraint(),
+                    Some(
+                        "timeslots_pitch_id_time_range_excl"
+                            | "timeslot_occupancy_unit_id_time_range_excl"
+                    )
+                ) =>
+            {
+                TimeslotControllerError::Overlaps(None)
+            }
+            sqlx::Error::Database(err)
+                if err.constraint() == Some("timeslots_within_opening_hours") =>
+            {
+                TimeslotControllerError::OutsideOpeningHours
+            }
+            sqlx::Error::Database(err) if err.constraint() == Some("timeslots_price_check") => {
+                TimeslotControllerError::NegativePrice
+            }
+            sqlx::Error::Database(err) if err.constraint() == Some("timeslots_price_currency") => {
+                TimeslotControllerError::PriceCurrencyMismatch
+            }
+            err => TimeslotControllerError::Sqlx(err),
+        }
+    }
+
+    /// Returns the earliest timeslot overlapping the period, either on the pitch itself or on a
+    /// pitch sharing one of its units
+    async fn find_conflicting_timeslot(
+        connection: &mut PgConnection,
+        pitch_id: Uuid,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        except_id: Option<Uuid>,
+    ) -> Result<Option<Uuid>, TimeslotControllerError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT
+                timeslots.id
+            FROM
+                timeslots
+            WHERE
+                timeslots.time_range && TSTZRANGE($2, $3)
+            AND
+                timeslots.id IS DISTINCT FROM $4
+            AND (
+                timeslots.pitch_id = $1
+            OR
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        timeslot_occupancy
+                    WHERE
+                        timeslot_occupancy.timeslot_id = timeslots.id
+                    AND
+                        timeslot_occupancy.unit_id IN (SELECT unit_id FROM pitch_units($1))
+                )
+            )
+            ORDER BY
+                LOWER(timeslots.time_range), timeslots.id
+            LIMIT 1
+            "#,
+        )
+        .bind(pitch_id)
+        .bind(starts_at)
+        .bind(ends_at)
+        .bind(except_id)
+        .fetch_optional(connection)
+        .await
+        .map_err(TimeslotControllerError::Sqlx)
+    }
+
+    async fn lock_timeslot(
+        connection: &mut PgConnection,
+        id: Uuid,
+        actor: PitchActor,
+    ) -> Result<Timeslot, TimeslotControllerError> {
+        sqlx::query_as(
+            r#"
+            SELECT
+                timeslots.id,
+                timeslots.pitch_id,
+                LOWER(timeslots.time_range) AS starts_at,
+                UPPER(timeslots.time_range) AS ends_at,
+                pitches.timezone,
+                LOWER(timeslots.time_range) AT TIME ZONE pitches.timezone AS local_starts_at,
+                UPPER(timeslots.time_range) AT TIME ZONE pitches.timezone AS local_ends_at,
+                timeslots.is_booked,
+                timeslots.rule_id,
+                timeslots.price AS custom_price
+            FROM
+                timeslots
+            JOIN
+                pitches
+            ON
+                pitches.id = timeslots.pitch_id
+            WHERE
+                timeslots.id = $1
+            AND
+                ($2::UUID IS NULL OR pitches.owner_id = $2)
+            FOR UPDATE OF
+                timeslots
+            "#,
+        )
+        .bind(id)
+        .bind(actor.owner_id())
+        .fetch_optional(connection)
+        .await
+        .map_err(TimeslotControllerError::Sqlx)?
+        .ok_or(TimeslotControllerError::TimeslotNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        controllers::{
+            PitchActor,
+            test_utils::{delete_business, insert_business_with_pitch},
+            timeslots::{
+                controller::TimeslotController,
+                errors::TimeslotControllerError,
+                import::ImportFormat,
+                models::{
+                    ImportAction, ImportRowError, InsertTimeslotPayload, TimeslotFilters,
+                    TimeslotOperation, TimeslotOperationOutcome, UpdateTimeslotPayload,
+                },
+            },
+        },
+        models::{
+            ModelManager,
+            local_time::with_local_offset,
+            pagination::PageRequest,
+            price::{Currency, Price},
+            tables::PitchStatus,
+        },
+    };
+    use anyhow::Context;
+    use chrono::{Duration, DurationRound, NaiveTime, SecondsFormat, Utc};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_create_timeslot_err_overlaps_names_conflict() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        let starts_at = (Utc::now() + Duration::days(1)).duration_trunc(Duration::hours(1))?;
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::hours(1),
+            price: None,
+        };
+        let timeslot_id = TimeslotController::create_timeslot(&model_manager, actor, timeslot)
+            .await
+            .context("failed while creating timeslot")?;
+
+        // exec
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at: starts_at + Duration::minutes(30),
+            ends_at: starts_at + Duration::minutes(90),
+            price: None,
+        };
+        let result = TimeslotController::create_timeslot(&model_manager, actor, timeslot).await;
+
+        // check
+        assert!(
+            matches!(result, Err(TimeslotControllerError::Overlaps(Some(id))) if id == timeslot_id),
+            "result: {result:?}"
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_timeslot_err_in_the_past() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+
+        // exec
+        let starts_at = Utc::now() - Duration::hours(2);
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::hours(1),
+            price: None,
+        };
+        let result = TimeslotController::create_timeslot(
+            &model_manager,
+            PitchActor::Owner(business_id),
+            timeslot,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(TimeslotControllerError::InThePast)),
+            "result: {result:?}"
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_timeslot_err_not_owner() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+        let (other_business_id, _) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+        let starts_at = (Utc::now() + Duration::days(1)).duration_trunc(Duration::hours(1))?;
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::hours(1),
+            price: None,
+        };
+        let timeslot_id = TimeslotController::create_timeslot(
+            &model_manager,
+            PitchActor::Owner(business_id),
+            timeslot,
+        )
+        .await
+        .context("failed while creating timeslot")?;
+
+        // exec
+        let new_timeslot = UpdateTimeslotPayload {
+            ends_at: Some(starts_at + Duration::hours(2)),
+            price: None,
+            ..Default::default()
+        };
+        let result = TimeslotController::update_timeslot_by_id(
+            &model_manager,
+            timeslot_id,
+            PitchActor::Owner(other_business_id),
+            new_timeslot,
+        )
+        .await;
+
+        // check
+        assert!(
+            matches!(result, Err(TimeslotControllerError::TimeslotNotFound)),
+            "result: {result:?}"
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+        delete_business(&model_manager, other_business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_timeslot_err_booked() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        let starts_at = (Utc::now() + Duration::days(1)).duration_trunc(Duration::hours(1))?;
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::hours(1),
+            price: None,
+        };
+        let timeslot_id = TimeslotController::create_timeslot(&model_manager, actor, timeslot)
+            .await
+            .context("failed while creating timeslot")?;
+        // the booking alone keeps the timeslot, even when `is_booked` wasn't updated
+        let booking_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO bookings
+                (timeslot_id, booked_by, price)
+            SELECT
+                $1, id, ROW(10000, 'AED')::price
+            FROM
+                users
+            WHERE
+                username = 'player_1'
+            RETURNING
+                id
+            "#,
+        )
+        .bind(timeslot_id)
+        .fetch_one(model_manager.db())
+        .await
+        .context("failed while inserting booking")?;
+
+        // exec
+        let result =
+            TimeslotController::delete_timeslot_by_id(&model_manager, timeslot_id, actor).await;
+
+        // check
+        assert!(
+            matches!(result, Err(TimeslotControllerError::IsBooked)),
+            "result: {result:?}"
+        );
+
+        // clean
+        sqlx::query("DELETE FROM bookings WHERE id = $1")
+            .bind(booking_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while deleting booking")?;
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_pitch_timeslots_ok_draft_only_shown_to_owner() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Draft)
+                .await?;
+        let page = PageRequest::first(10);
+
+        // exec
+        let result = TimeslotController::get_pitch_timeslots(
+            &model_manager,
+            pitch_id,
+            None,
+            TimeslotFilters::default(),
+            &page,
+        )
+        .await;
+        let owner_timeslots = TimeslotController::get_pitch_timeslots(
+            &model_manager,
+            pitch_id,
+            Some(PitchActor::Owner(business_id)),
+            TimeslotFilters::default(),
+            &page,
+        )
+        .await
+        .context("failed while fetching timeslots as owner")?;
+
+        // check
+        assert!(
+            matches!(result, Err(TimeslotControllerError::PitchNotFound)),
+            "result: {result:?}"
+        );
+        assert!(owner_timeslots.items.is_empty());
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_operations_ok_best_effort_reports_conflicts() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+        let starts_at = (Utc::now() + Duration::days(1)).duration_trunc(Duration::hours(1))?;
+        let operations = vec![
+            TimeslotOperation::Create(InsertTimeslotPayload {
+                pitch_id,
+                starts_at,
+                ends_at: starts_at + Duration::hours(1),
+                price: None,
+            }),
+            TimeslotOperation::Create(InsertTimeslotPayload {
+                pitch_id,
+                starts_at: starts_at + Duration::minutes(30),
+                ends_at: starts_at + Duration::minutes(90),
+                price: None,
+            }),
+            TimeslotOperation::Create(InsertTimeslotPayload {
+                pitch_id,
+                starts_at: starts_at + Duration::hours(1),
+                ends_at: starts_at + Duration::hours(2),
+                price: None,
+            }),
+        ];
+
+        // exec
+        let applied = TimeslotController::apply_operations(
+            &model_manager,
+            PitchActor::Owner(business_id),
+            operations,
+            false,
+        )
+        .await
+        .context("failed while applying operations")?;
+
+        // check
+        assert!(applied.is_committed);
+        let [first, second, third] = &applied.results[..] else {
+            panic!("results: {:?}", applied.results);
+        };
+        let Ok(TimeslotOperationOutcome::Created(first_id)) = first else {
+            panic!("result: {first:?}");
+        };
+        assert!(
+            matches!(second, Err(TimeslotControllerError::Overlaps(Some(id))) if id == first_id),
+            "result: {second:?}"
+        );
+        assert!(
+            matches!(third, Ok(TimeslotOperationOutcome::Created(_))),
+            "result: {third:?}"
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_operations_ok_all_or_nothing_rolls_back() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        let starts_at = (Utc::now() + Duration::days(1)).duration_trunc(Duration::hours(1))?;
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::hours(1),
+            price: None,
+        };
+        let timeslot_id = TimeslotController::create_timeslot(&model_manager, actor, timeslot)
+            .await
+            .context("failed while creating timeslot")?;
+
+        // exec
+        let operations = vec![
+            TimeslotOperation::Delete(timeslot_id),
+            TimeslotOperation::Create(InsertTimeslotPayload {
+                pitch_id,
+                starts_at: starts_at - Duration::days(2),
+                ends_at: starts_at - Duration::days(2) + Duration::hours(1),
+                price: None,
+            }),
+        ];
+        let applied = TimeslotController::apply_operations(&model_manager, actor, operations, true)
+            .await
+            .context("failed while applying operations")?;
+
+        // check
+        assert!(!applied.is_committed);
+        assert!(
+            matches!(
+                &applied.results[..],
+                [
+                    Ok(TimeslotOperationOutcome::Deleted),
+                    Err(TimeslotControllerError::InThePast)
+                ]
+            ),
+            "results: {:?}",
+            applied.results
+        );
+        let timeslots = TimeslotController::get_pitch_timeslots(
+            &model_manager,
+            pitch_id,
+            Some(actor),
+            TimeslotFilters::default(),
+            &PageRequest::first(10),
+        )
+        .await
+        .context("failed while fetching timeslots")?;
+        let timeslot_ids: Vec<_> = timeslots
+            .items
+            .iter()
+            .map(|timeslot| timeslot.timeslot.id)
+            .collect();
+        assert_eq!(timeslot_ids, [timeslot_id]);
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_timeslot_ok_price_defaults_to_pitch_price() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        let aed: Currency = "AED".parse()?;
+        let starts_at = (Utc::now() + Duration::days(1)).duration_trunc(Duration::hours(1))?;
+
+        // exec
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at,
+            ends_at: starts_at + Duration::minutes(90),
+            price: None,
+        };
+        TimeslotController::create_timeslot(&model_manager, actor, timeslot)
+            .await
+            .context("failed while creating timeslot")?;
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at: starts_at + Duration::hours(2),
+            ends_at: starts_at + Duration::hours(3),
+            price: Some(Price::new(5000, aed)),
+        };
+        TimeslotController::create_timeslot(&model_manager, actor, timeslot)
+            .await
+            .context("failed while creating timeslot with a price")?;
+        let timeslot = InsertTimeslotPayload {
+            pitch_id,
+            starts_at: starts_at + Duration::hours(4),
+            ends_at: starts_at + Duration::hours(5),
+            price: Some(Price::new(5000, "USD".parse()?)),
+        };
+        let result = TimeslotController::create_timeslot(&model_manager, actor, timeslot).await;
+        let timeslots = TimeslotController::get_pitch_timeslots(
+            &model_manager,
+            pitch_id,
+            Some(actor),
+            TimeslotFilters::default(),
+            &PageRequest::first(10),
+        )
+        .await
+        .context("failed while fetching timeslots")?;
+
+        // check
+        assert!(
+            matches!(result, Err(TimeslotControllerError::PriceCurrencyMismatch)),
+            "result: {result:?}"
+        );
+        let prices: Vec<_> = timeslots
+            .items
+            .iter()
+            .map(|timeslot| (timeslot.price, timeslot.timeslot.custom_price.is_some()))
+            .collect();
+        assert_eq!(
+            prices,
+            [
+                (Price::new(15000, aed), false),
+                (Price::new(5000, aed), true)
+            ]
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_timeslots_ok_dry_run_then_apply() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        sqlx::query("UPDATE pitches SET timezone = 'Asia/Dubai' WHERE id = $1")
+            .bind(pitch_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while setting timezone")?;
+        let tomorrow = Utc::now().date_naive() + Duration::days(1);
+        let at = |hours: i64, minutes: i64| {
+            (tomorrow.and_time(NaiveTime::MIN).and_utc()
+                + Duration::hours(hours)
+                + Duration::minutes(minutes))
+            .to_rfc3339_opts(SecondsFormat::Secs, true)
+        };
+        let existing = InsertTimeslotPayload {
+            pitch_id,
+            starts_at: at(18, 0).parse()?,
+            ends_at: at(19, 0).parse()?,
+            price: None,
+        };
+        let existing_id = TimeslotController::create_timeslot(&model_manager, actor, existing)
+            .await
+            .context("failed while creating timeslot")?;
+        let yesterday = Utc::now().date_naive() - Duration::days(1);
+        let file = [
+            "pitch,start,end,price".to_string(),
+            format!("timeslots_pitch,{},{},", at(18, 0), at(19, 0)),
+            format!("timeslots_pitch,{},{},", at(18, 30), at(19, 30)),
+            format!("timeslots_pitch,{tomorrow} 20:00,{tomorrow} 21:00,150 AED"),
+            format!(",{},{},", at(16, 30), at(17, 30)),
+            format!("Court 9,{},{},", at(8, 0), at(9, 0)),
+            format!("timeslots_pitch,{yesterday} 20:00,{yesterday} 21:00,"),
+            format!("timeslots_pitch,{},{},", at(10, 0), at(9, 0)),
+        ]
+        .join("\n");
+        let rows = ImportFormat::Csv.parse(&file)?;
+        let count_timeslots = async || -> anyhow::Result<i64> {
+            sqlx::query_scalar("SELECT COUNT(*) FROM timeslots WHERE pitch_id = $1")
+                .bind(pitch_id)
+                .fetch_one(model_manager.db())
+                .await
+                .context("failed while counting timeslots")
+        };
+
+        // exec
+        let dry_run = TimeslotController::import_timeslots(
+            &model_manager,
+            actor,
+            Some(pitch_id),
+            rows,
+            false,
+        )
+        .await
+        .context("failed while importing dry run")?;
+        let count_after_dry_run = count_timeslots().await?;
+        let rows = ImportFormat::Csv.parse(&file)?;
+        let applied =
+            TimeslotController::import_timeslots(&model_manager, actor, Some(pitch_id), rows, true)
+                .await
+                .context("failed while applying import")?;
+
+        // check
+        assert_eq!(count_after_dry_run, 1);
+        assert_eq!(count_timeslots().await?, 2);
+        for imported_rows in [&dry_run, &applied] {
+            let lines: Vec<_> = imported_rows.iter().map(|row| row.line).collect();
+            assert_eq!(lines, [2, 3, 4, 5, 6, 7, 8]);
+            let actions: Vec<_> = imported_rows
+                .iter()
+                .map(|row| row.timeslot.as_ref().map(|timeslot| &timeslot.action))
+                .collect();
+            assert!(
+                matches!(
+                    actions[..],
+                    [
+                        Ok(ImportAction::SkipExisting(skipped_id)),
+                        Ok(ImportAction::Conflict(TimeslotControllerError::Overlaps(Some(
+                            conflicting_id
+                        )))),
+                        Ok(ImportAction::Create(_)),
+                        Ok(ImportAction::Conflict(TimeslotControllerError::Overlaps(_))),
+                        Err(ImportRowError::UnknownPitch),
+                        Ok(ImportAction::SkipPast),
+                        Err(ImportRowError::InvalidPeriod),
+                    ] if *skipped_id == existing_id && *conflicting_id == existing_id
+                ),
+                "actions: {actions:?}"
+            );
+            let created = imported_rows[2].timeslot.as_ref().unwrap();
+            assert_eq!(
+                created.starts_at,
+                at(16, 0).parse::<chrono::DateTime<Utc>>()?
+            );
+            assert_eq!(created.price, Some(Price::new(15000, "AED".parse()?)));
+        }
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_timeslots_ok_local_times_across_dst_transitions() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let (business_id, pitch_id) =
+            insert_business_with_pitch(&model_manager, "timeslots_pitch", PitchStatus::Published)
+                .await?;
+        let actor = PitchActor::Owner(business_id);
+        sqlx::query("UPDATE pitches SET timezone = 'Europe/Berlin' WHERE id = $1")
+            .bind(pitch_id)
+            .execute(model_manager.db())
+            .await
+            .context("failed while setting timezone")?;
+        // the clocks skip 02:00 to 03:00 on 2030-03-31 and go through 02:00 to 03:00 twice on
+        // 2030-10-27
+        let file = [
+            "pitch,start,end",
+            "timeslots_pitch,2030-03-31 01:30,2030-03-31 03:30",
+            "timeslots_pitch,2030-03-31 02:30,2030-03-31 04:00",
+            "timeslots_pitch,2030-10-27 01:30,2030-10-27 02:30",
+        ]
+        .join("\n");
+        let rows = ImportFormat::Csv.parse(&file)?;
+
+        // exec
+        let imported_rows =
+            TimeslotController::import_timeslots(&model_manager, actor, Some(pitch_id), rows, true)
+                .await
+                .context("failed while importing")?;
+        let timeslots = TimeslotController::get_pitch_timeslots(
+            &model_manager,
+            pitch_id,
+            Some(actor),
+            TimeslotFilters::default(),
+            &PageRequest::first(10),
+        )
+        .await
+        .context("failed while fetching timeslots")?;
+
+        // check
+        assert!(
+            imported_rows.iter().all(|row| matches!(
+                row.timeslot.as_ref().map(|timeslot| &timeslot.action),
+                Ok(ImportAction::Create(_))
+            )),
+            "imported_rows: {imported_rows:?}"
+        );
+        let local_periods: Vec<_> = timeslots
+            .items
+            .iter()
+            .map(|priced| {
+                let timeslot = &priced.timeslot;
+                (
+                    with_local_offset(timeslot.starts_at, timeslot.local_starts_at).to_rfc3339(),
+                    with_local_offset(timeslot.ends_at, timeslot.local_ends_at).to_rfc3339(),
+                )
+            })
+            .collect();
+        // a skipped time is read with the offset from before the gap, a repeated time is its
+        // first occurrence
+        assert_eq!(
+            local_periods,
+            [
+                (
+                    "2030-03-31T01:30:00+01:00".to_string(),
+                    "2030-03-31T03:30:00+02:00".to_string()
+                ),
+                (
+                    "2030-03-31T03:30:00+02:00".to_string(),
+                    "2030-03-31T04:00:00+02:00".to_string()
+                ),
+                (
+                    "2030-10-27T01:30:00+02:00".to_string(),
+                    "2030-10-27T02:30:00+02:00".to_string()
+                ),
+            ]
+        );
+        assert!(
+            timeslots
+                .items
+                .iter()
+                .all(|priced| priced.timeslot.timezone == "Europe/Berlin")
+        );
+
+        // clean
+        delete_business(&model_manager, business_id).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{controllers::PricingRuleControllerError, errors::error_impl};
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum TimeslotControllerError {
+    PitchNotFound,
+    TimeslotNotFound,
+    InvalidPeriod,
+    InThePast,
+    OutsideOpeningHours,
+    IsBooked,
+    NegativePrice,
+    /// the price isn't in the currency of the pitch
+    PriceCurrencyMismatch,
+    /// holds the conflicting timeslot, `None` when it was removed in the meantime
+    Overlaps(Option<Uuid>),
+    /// the file to import isn't CSV with `start` and `end` columns, or iCalendar
+    InvalidImportFile,
+    Pricing(PricingRuleControllerError),
+    Sqlx(sqlx::Error),
+}
+
+error_impl!(TimeslotControllerError);
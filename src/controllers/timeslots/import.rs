@@ -0,0 +1,580 @@
+//! Reads the timeslots of a file exported from a spreadsheet or another booking tool.
+//!
+//! CSV files start with a header naming their columns: `start` and `end` are required, `pitch`
+//! (id or name) and `price` (e.g. `120.50 AED`) are optional, other columns are ignored. Times
+//! are RFC 3339, or local to the pitch without an offset.
+//!
+//! iCalendar files give one timeslot per `VEVENT`, cancelled events are left out and recurring
+//! ones are rejected. Times are UTC, local to their `TZID`, or local to the pitch when floating.
+//! The end is given by `DTEND` or by a `DURATION` from the start.
+//!
+//! A row that can't be read is kept with its error, so that the whole file gets reported at once.
+
+use crate::{
+    controllers::timeslots::{
+        errors::TimeslotControllerError,
+        models::{ImportRow, ImportRowError, ImportedTime, ParsedTimeslot},
+    },
+    models::price::Price,
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta};
+
+const LOCAL_TIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+const ICALENDAR_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImportFormat {
+    Csv,
+    ICalendar,
+}
+
+impl ImportFormat {
+    /// Fails when the file as a whole can't be read, e.g. a CSV file without `start` and `end`
+    /// columns
+    pub fn parse(self, text: &str) -> Result<Vec<ImportRow>, TimeslotControllerError> {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        match self {
+            Self::Csv => parse_csv(text),
+            Self::ICalendar => parse_icalendar(text),
+        }
+    }
+}
+
+fn parse_csv(text: &str) -> Result<Vec<ImportRow>, TimeslotControllerError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, header) = lines
+        .next()
+        .ok_or(TimeslotControllerError::InvalidImportFile)?;
+    let header = split_csv_line(header).ok_or(TimeslotControllerError::InvalidImportFile)?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|column| column.trim().eq_ignore_ascii_case(name))
+    };
+    let (Some(start), Some(end)) = (column("start"), column("end")) else {
+        return Err(TimeslotControllerError::InvalidImportFile);
+    };
+    let (pitch, price) = (column("pitch"), column("price"));
+
+    let rows = lines
+        .map(|(line, text)| {
+            let timeslot = split_csv_line(text)
+                .filter(|fields| fields.len() == header.len())
+                .ok_or(ImportRowError::MissingColumns)
+                .and_then(|fields| {
+                    let field = |index: Option<usize>| {
+                        index
+                            .map(|index| fields[index].trim())
+                            .filter(|field| !field.is_empty())
+                    };
+                    Ok(ParsedTimeslot {
+                        pitch: field(pitch).map(str::to_string),
+                        starts_at: parse_csv_time(field(Some(start)))
+                            .ok_or(ImportRowError::InvalidStart)?,
+                        ends_at: parse_csv_time(field(Some(end)))
+                            .ok_or(ImportRowError::InvalidEnd)?,
+                        price: field(price)
+                            .map(str::parse::<Price>)
+                            .transpose()
+                            .map_err(|_| ImportRowError::InvalidPrice)?,
+                    })
+                });
+            ImportRow { line, timeslot }
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+/// Splits on commas outside double quotes, `""` being a quote within them. `None` when a quote
+/// isn't closed, fields spanning several lines aren't supported.
+fn split_csv_line(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut is_quoted = false;
+    let mut characters = line.chars().peekable();
+    while let Some(character) = characters.next() {
+        match (character, is_quoted) {
+            ('"', true) if characters.peek() == Some(&'"') => {
+                field.push('"');
+                characters.next();
+            }
+            ('"', _) => is_quoted = !is_quoted,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(character),
+        }
+    }
+    if is_quoted {
+        return None;
+    }
+    fields.push(field);
+
+    Some(fields)
+}
+
+fn parse_csv_time(field: Option<&str>) -> Option<ImportedTime> {
+    let field = field?;
+    if let Ok(time) = DateTime::parse_from_rfc3339(field) {
+        return Some(ImportedTime::Utc(time.to_utc()));
+    }
+
+    LOCAL_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(field, format).ok())
+        .map(|time| ImportedTime::Local(time, None))
+}
+
+/// Content line, e.g. `DTSTART;TZID=Asia/Dubai:20300107T180000`
+struct Property {
+    /// upper-cased
+    name: String,
+    /// with upper-cased keys
+    parameters: Vec<(String, String)>,
+    value: String,
+}
+
+#[derive(Default)]
+struct Event {
+    line: usize,
+    starts_at: Option<Result<ImportedTime, ImportRowError>>,
+    ends_at: Option<Result<ImportedTime, ImportRowError>>,
+    /// `None` when it can't be read
+    duration: Option<Option<TimeDelta>>,
+    is_cancelled: bool,
+    is_recurring: bool,
+}
+
+impl Event {
+    fn into_timeslot(self) -> Result<ParsedTimeslot, ImportRowError> {
+        if self.is_recurring {
+            return Err(ImportRowError::RecurringEvent);
+        }
+        let starts_at = self
+            .starts_at
+            .unwrap_or(Err(ImportRowError::InvalidStart))?;
+        let ends_at = match (self.ends_at, self.duration) {
+            (Some(ends_at), _) => ends_at?,
+            (None, Some(Some(duration))) => match &starts_at {
+                ImportedTime::Utc(time) => ImportedTime::Utc(*time + duration),
+                ImportedTime::Local(time, timezone) => {
+                    ImportedTime::Local(*time + duration, timezone.clone())
+                }
+            },
+            (None, _) => return Err(ImportRowError::InvalidEnd),
+        };
+
+        Ok(ParsedTimeslot {
+            pitch: None,
+            starts_at,
+            ends_at,
+            price: None,
+        })
+    }
+}
+
+fn parse_icalendar(text: &str) -> Result<Vec<ImportRow>, TimeslotControllerError> {
+    let lines = unfold(text);
+    if !lines
+        .first()
+        .is_some_and(|(_, line)| line.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err(TimeslotControllerError::InvalidImportFile);
+    }
+
+    let mut rows = Vec::new();
+    let mut event: Option<Event> = None;
+    // components nested in an event, e.g. alarms, whose properties aren't the event's
+    let mut nested_components = 0;
+    for (line, content) in lines {
+        let Some(Property {
+            name,
+            parameters,
+            value,
+        }) = split_property(&content)
+        else {
+            continue;
+        };
+        match (name.as_str(), event.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                event = Some(Event {
+                    line,
+                    ..Event::default()
+                });
+            }
+            ("BEGIN", Some(_)) => nested_components += 1,
+            ("END", Some(_)) if nested_components > 0 => nested_components -= 1,
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                let Some(event) = event.take() else {
+                    continue;
+                };
+                if event.is_cancelled {
+                    continue;
+                }
+                rows.push(ImportRow {
+                    line: event.line,
+                    timeslot: event.into_timeslot(),
+                });
+            }
+            (_, Some(_)) if nested_components > 0 => {}
+            ("DTSTART", Some(event)) => {
+                let time = parse_icalendar_time(&parameters, &value);
+                event.starts_at = Some(time.unwrap_or(Err(ImportRowError::InvalidStart)));
+            }
+            ("DTEND", Some(event)) => {
+                let time = parse_icalendar_time(&parameters, &value);
+                event.ends_at = Some(time.unwrap_or(Err(ImportRowError::InvalidEnd)));
+            }
+            ("DURATION", Some(event)) => event.duration = Some(parse_icalendar_duration(&value)),
+            ("RRULE" | "RDATE" | "EXDATE", Some(event)) => event.is_recurring = true,
+            ("STATUS", Some(event)) => event.is_cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Joins the lines folded by RFC 5545 section 3.1, each with the number of its first line
+fn unfold(text: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, last))) => last.push_str(continuation),
+            _ if line.trim().is_empty() => {}
+            _ => lines.push((index + 1, line.to_string())),
+        }
+    }
+
+    lines
+}
+
+fn split_property(line: &str) -> Option<Property> {
+    let mut is_quoted = false;
+    let colon = line.char_indices().find_map(|(index, character)| {
+        match character {
+            '"' => is_quoted = !is_quoted,
+            ':' if !is_quoted => return Some(index),
+            _ => {}
+        }
+        None
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+    let mut head = head.split(';');
+    let name = head.next()?.trim().to_ascii_uppercase();
+    let parameters = head
+        .filter_map(|parameter| parameter.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.trim().to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+
+    Some(Property {
+        name,
+        parameters,
+        value: value.trim().to_string(),
+    })
+}
+
+/// `None` when the time can't be read
+fn parse_icalendar_time(
+    parameters: &[(String, String)],
+    value: &str,
+) -> Option<Result<ImportedTime, ImportRowError>> {
+    let parameter = |name: &str| {
+        parameters
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+    if parameter("VALUE").is_some_and(|kind| kind.eq_ignore_ascii_case("DATE"))
+        || NaiveDate::parse_from_str(value, "%Y%m%d").is_ok()
+    {
+        return Some(Err(ImportRowError::AllDayEvent));
+    }
+
+    let time = match value.strip_suffix('Z') {
+        Some(value) => ImportedTime::Utc(
+            NaiveDateTime::parse_from_str(value, ICALENDAR_TIME_FORMAT)
+                .ok()?
+                .and_utc(),
+        ),
+        None => ImportedTime::Local(
+            NaiveDateTime::parse_from_str(value, ICALENDAR_TIME_FORMAT).ok()?,
+            parameter("TZID").map(str::to_string),
+        ),
+    };
+
+    Some(Ok(time))
+}
+
+/// Reads a positive duration of RFC 5545 section 3.3.6, e.g. `PT1H30M` or `P1W`, `None` when it
+/// can't be read
+fn parse_icalendar_duration(value: &str) -> Option<TimeDelta> {
+    let value = value.strip_prefix('+').unwrap_or(value).strip_prefix('P')?;
+    let (date, time) = value.split_once('T').unwrap_or((value, ""));
+    if date.is_empty() && time.is_empty() {
+        return None;
+    }
+
+    let mut duration = TimeDelta::zero();
+    for (part, units) in [
+        (date, &[('W', 604_800), ('D', 86_400)][..]),
+        (time, &[('H', 3_600), ('M', 60), ('S', 1)][..]),
+    ] {
+        let mut rest = part;
+        let mut units = units.iter();
+        while !rest.is_empty() {
+            let digits = rest.find(|character: char| !character.is_ascii_digit())?;
+            let unit = rest[digits..].chars().next()?;
+            let (_, seconds) = units.find(|(name, _)| *name == unit)?;
+            let count: i64 = rest[..digits].parse().ok()?;
+            duration =
+                duration.checked_add(&TimeDelta::try_seconds(count.checked_mul(*seconds)?)?)?;
+            rest = &rest[digits + 1..];
+        }
+    }
+
+    Some(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(time: &str, timezone: Option<&str>) -> ImportedTime {
+        ImportedTime::Local(time.parse().unwrap(), timezone.map(str::to_string))
+    }
+
+    fn errors(rows: &[ImportRow]) -> Vec<(usize, ImportRowError)> {
+        rows.iter()
+            .filter_map(|row| row.timeslot.as_ref().err().map(|err| (row.line, *err)))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_csv_ok_reports_bad_rows_with_their_line() {
+        let text = concat!(
+            "\u{feff}Pitch,Start,End,Price,Notes\r\n",
+            "\"Court 1, indoor\",2030-01-07T18:00:00+04:00,2030-01-07T19:00:00+04:00,150.00 AED,\r\n",
+            "\r\n",
+            "Court 2,2030-01-07 18:00,2030-01-07 19:30,,\"said \"\"hi\"\"\"\r\n",
+            "Court 2,tomorrow,2030-01-07 19:30,,\r\n",
+            "Court 2,2030-01-07 18:00,2030-01-07 19:30,150,\r\n",
+            "Court 2,2030-01-07 18:00\r\n",
+        );
+
+        let rows = ImportFormat::Csv.parse(text).unwrap();
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(
+            rows[0].timeslot.as_ref().unwrap(),
+            &ParsedTimeslot {
+                pitch: Some("Court 1, indoor".to_string()),
+                starts_at: ImportedTime::Utc("2030-01-07T14:00:00Z".parse().unwrap()),
+                ends_at: ImportedTime::Utc("2030-01-07T15:00:00Z".parse().unwrap()),
+                price: Some(Price::new(15_000, "AED".parse().unwrap())),
+            }
+        );
+        assert_eq!(rows[1].line, 4);
+        assert_eq!(
+            rows[1].timeslot.as_ref().unwrap(),
+            &ParsedTimeslot {
+                pitch: Some("Court 2".to_string()),
+                starts_at: local("2030-01-07T18:00:00", None),
+                ends_at: local("2030-01-07T19:30:00", None),
+                price: None,
+            }
+        );
+        assert_eq!(
+            errors(&rows),
+            [
+                (5, ImportRowError::InvalidStart),
+                (6, ImportRowError::InvalidPrice),
+                (7, ImportRowError::MissingColumns),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_err_header_without_start() {
+        let result =
+            ImportFormat::Csv.parse("pitch,from,end\nCourt 1,2030-01-07 18:00,2030-01-07 19:00\n");
+
+        assert!(
+            matches!(result, Err(TimeslotControllerError::InvalidImportFile)),
+            "result: {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_parse_icalendar_ok() {
+        let text = concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "VERSION:2.0\r\n",
+            "BEGIN:VTIMEZONE\r\n",
+            "TZID:Europe/Berlin\r\n",
+            "BEGIN:STANDARD\r\n",
+            "DTSTART:19701025T030000\r\n",
+            "END:STANDARD\r\n",
+            "END:VTIMEZONE\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:1@example.com\r\n",
+            "DTSTART;TZID=\"Europe/Berlin\":20300107T180000\r\n",
+            "DTEND;TZID=Europe/Berlin:2030\r\n",
+            " 0107T193000\r\n",
+            "BEGIN:VALARM\r\n",
+            "DTSTART:20300107T170000Z\r\n",
+            "END:VALARM\r\n",
+            "END:VEVENT\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART:20300108T180000Z\r\n",
+            "DTEND:20300108T190000\r\n",
+            "END:VEVENT\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART:20300109T180000Z\r\n",
+            "DTEND:20300109T190000Z\r\n",
+            "STATUS:CANCELLED\r\n",
+            "END:VEVENT\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART;VALUE=DATE:20300110\r\n",
+            "DTEND;VALUE=DATE:20300111\r\n",
+            "END:VEVENT\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART:20300111T180000Z\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        );
+
+        let rows = ImportFormat::ICalendar.parse(text).unwrap();
+
+        let lines: Vec<_> = rows.iter().map(|row| row.line).collect();
+        assert_eq!(lines, [9, 18, 27, 31]);
+        assert_eq!(
+            rows[0].timeslot.as_ref().unwrap(),
+            &ParsedTimeslot {
+                pitch: None,
+                starts_at: local("2030-01-07T18:00:00", Some("Europe/Berlin")),
+                ends_at: local("2030-01-07T19:30:00", Some("Europe/Berlin")),
+                price: None,
+            }
+        );
+        assert_eq!(
+            rows[1].timeslot.as_ref().unwrap(),
+            &ParsedTimeslot {
+                pitch: None,
+                starts_at: ImportedTime::Utc("2030-01-08T18:00:00Z".parse().unwrap()),
+                ends_at: local("2030-01-08T19:00:00", None),
+                price: None,
+            }
+        );
+        assert_eq!(
+            errors(&rows),
+            [
+                (27, ImportRowError::AllDayEvent),
+                (31, ImportRowError::InvalidEnd),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_icalendar_ok_end_from_duration() {
+        let text = concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART:20300107T180000Z\r\n",
+            "DURATION:PT1H30M\r\n",
+            "END:VEVENT\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART;TZID=Asia/Dubai:20300107T230000\r\n",
+            "DURATION:P1DT2H\r\n",
+            "END:VEVENT\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART:20300108T180000Z\r\n",
+            "DURATION:1H\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        );
+
+        let rows = ImportFormat::ICalendar.parse(text).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0].timeslot.as_ref().unwrap(),
+            &ParsedTimeslot {
+                pitch: None,
+                starts_at: ImportedTime::Utc("2030-01-07T18:00:00Z".parse().unwrap()),
+                ends_at: ImportedTime::Utc("2030-01-07T19:30:00Z".parse().unwrap()),
+                price: None,
+            }
+        );
+        assert_eq!(
+            rows[1].timeslot.as_ref().unwrap(),
+            &ParsedTimeslot {
+                pitch: None,
+                starts_at: local("2030-01-07T23:00:00", Some("Asia/Dubai")),
+                ends_at: local("2030-01-09T01:00:00", Some("Asia/Dubai")),
+                price: None,
+            }
+        );
+        assert_eq!(errors(&rows), [(10, ImportRowError::InvalidEnd)]);
+    }
+
+    #[test]
+    fn test_parse_icalendar_ok_rejects_recurring_events() {
+        let text = concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART:20300107T180000Z\r\n",
+            "DTEND:20300107T190000Z\r\n",
+            "RRULE:FREQ=WEEKLY;COUNT=4\r\n",
+            "EXDATE:20300114T180000Z\r\n",
+            "END:VEVENT\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART:20300108T180000Z\r\n",
+            "DTEND:20300108T190000Z\r\n",
+            "RDATE:20300115T180000Z\r\n",
+            "END:VEVENT\r\n",
+            "BEGIN:VEVENT\r\n",
+            "DTSTART:20300109T180000Z\r\n",
+            "DTEND:20300109T190000Z\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        );
+
+        let rows = ImportFormat::ICalendar.parse(text).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert!(rows[2].timeslot.is_ok());
+        assert_eq!(
+            errors(&rows),
+            [
+                (2, ImportRowError::RecurringEvent),
+                (8, ImportRowError::RecurringEvent),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_icalendar_err_not_a_calendar() {
+        let result = ImportFormat::ICalendar.parse("pitch,start,end\n");
+
+        assert!(
+            matches!(result, Err(TimeslotControllerError::InvalidImportFile)),
+            "result: {result:?}"
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod controller;
+pub mod errors;
+pub mod import;
+pub mod models;
@@ -0,0 +1,168 @@
+use crate::{
+    controllers::timeslots::errors::TimeslotControllerError,
+    models::{pagination::Keyset, price::Price},
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[cfg_attr(test, derive(Debug))]
+#[derive(FromRow)]
+pub struct Timeslot {
+    pub id: Uuid,
+    pub pitch_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// IANA name of the pitch's timezone, the local times are wall clock times there
+    pub timezone: String,
+    pub local_starts_at: NaiveDateTime,
+    pub local_ends_at: NaiveDateTime,
+    pub is_booked: bool,
+    /// the recurring rule that generated it
+    pub rule_id: Option<Uuid>,
+    /// set on the timeslot, the pricing rules of the pitch don't apply then
+    pub custom_price: Option<Price>,
+}
+
+impl Keyset for Timeslot {
+    type Key = (DateTime<Utc>, Uuid);
+
+    fn key(&self) -> Self::Key {
+        (self.starts_at, self.id)
+    }
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub struct PricedTimeslot {
+    pub timeslot: Timeslot,
+    /// the custom price of the timeslot, otherwise what the pricing rules of the pitch make it
+    /// cost if booked now
+    pub price: Price,
+}
+
+impl Keyset for PricedTimeslot {
+    type Key = <Timeslot as Keyset>::Key;
+
+    fn key(&self) -> Self::Key {
+        self.timeslot.key()
+    }
+}
+
+pub struct InsertTimeslotPayload {
+    pub pitch_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// overrides the hourly price of the pitch, in the pitch's currency
+    pub price: Option<Price>,
+}
+
+/// Fields set to `None` are left unchanged
+#[derive(Default)]
+pub struct UpdateTimeslotPayload {
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub price: Option<Price>,
+}
+
+#[derive(Default)]
+pub struct TimeslotFilters {
+    pub pitch_id: Option<Uuid>,
+    pub business_id: Option<Uuid>,
+    /// keeps the timeslots that end after it
+    pub from: Option<DateTime<Utc>>,
+    /// keeps the timeslots that start before it
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// One step of [`super::controller::TimeslotController::apply_operations`]
+pub enum TimeslotOperation {
+    Create(InsertTimeslotPayload),
+    Update(Uuid, UpdateTimeslotPayload),
+    Delete(Uuid),
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub enum TimeslotOperationOutcome {
+    /// holds the id of the new timeslot
+    Created(Uuid),
+    Updated,
+    Deleted,
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub struct AppliedOperations {
+    /// `false` when an operation failed in all-or-nothing mode, nothing was applied then
+    pub is_committed: bool,
+    /// in the order of the operations
+    pub results: Vec<Result<TimeslotOperationOutcome, TimeslotControllerError>>,
+}
+
+/// Row of an imported file, see [`super::import`]
+#[cfg_attr(test, derive(Debug))]
+pub struct ImportRow {
+    /// where the row or the event starts in the file, from 1
+    pub line: usize,
+    pub timeslot: Result<ParsedTimeslot, ImportRowError>,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct ParsedTimeslot {
+    /// id or name of the pitch, the pitch of the import when left out
+    pub pitch: Option<String>,
+    pub starts_at: ImportedTime,
+    pub ends_at: ImportedTime,
+    pub price: Option<Price>,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum ImportedTime {
+    Utc(DateTime<Utc>),
+    /// in the given timezone, or in the pitch's one
+    Local(NaiveDateTime, Option<String>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportRowError {
+    /// the row doesn't have a value for every column of the header
+    MissingColumns,
+    InvalidStart,
+    InvalidEnd,
+    InvalidPrice,
+    AllDayEvent,
+    /// the event repeats with `RRULE` or `RDATE`, or has exceptions with `EXDATE`
+    RecurringEvent,
+    MissingPitch,
+    UnknownPitch,
+    UnknownTimezone,
+    /// the timeslot doesn't end after it starts
+    InvalidPeriod,
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub struct ImportedRow {
+    pub line: usize,
+    pub timeslot: Result<ImportedTimeslot, ImportRowError>,
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub struct ImportedTimeslot {
+    pub pitch_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// wall clock times in the pitch's timezone
+    pub local_starts_at: NaiveDateTime,
+    pub local_ends_at: NaiveDateTime,
+    pub price: Option<Price>,
+    pub action: ImportAction,
+}
+
+/// What importing a timeslot does, or did when the import is applied
+#[cfg_attr(test, derive(Debug))]
+pub enum ImportAction {
+    /// holds the id of the new timeslot, only kept when the import is applied
+    Create(Uuid),
+    /// the same timeslot already exists, or an earlier row of the file creates it
+    SkipExisting(Uuid),
+    SkipPast,
+    Conflict(TimeslotControllerError),
+}
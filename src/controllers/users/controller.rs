@@ -9,7 +9,7 @@ use crate::{
     },
     models::{
         ModelManager,
-        tables::{BusinessProfile, PlayerProfile, UserRole},
+        tables::{BusinessProfile, PlayerProfile, PlayerSportRating, Sport, UserRole},
     },
     secrets::SecretManager,
 };
@@ -50,10 +50,14 @@ impl UserController {
                 .await
                 .map_err(UserControllerError::Sqlx)?
                 .ok_or(UserControllerError::UserNotFound)?;
+
+                let mut profile = raw_player_user.profile;
+                profile.sport_ratings = Self::get_sport_ratings_by_id(model_manager, id).await?;
+
                 UserPersonalInfo {
                     id,
                     username: raw_player_user.username,
-                    profile: UserProfile::Player(raw_player_user.profile),
+                    profile: UserProfile::Player(profile),
                 }
             }
             UserRole::Business => {
@@ -114,6 +118,28 @@ impl UserController {
         Ok(user)
     }
 
+    async fn get_sport_ratings_by_id(
+        model_manager: &ModelManager,
+        player_id: Uuid,
+    ) -> Result<Vec<PlayerSportRating>, UserControllerError> {
+        sqlx::query_as(
+            r#"
+            SELECT
+                sport, self_declared_level, system_rating, confidence
+            FROM
+                player_sport_ratings
+            WHERE
+                player_id = $1
+            ORDER BY
+                sport
+            "#,
+        )
+        .bind(player_id)
+        .fetch_all(model_manager.db())
+        .await
+        .map_err(UserControllerError::Sqlx)
+    }
+
     pub async fn get_login_info_by_username(
         model_manager: &ModelManager,
         username: &str,
@@ -150,6 +176,7 @@ impl UserController {
                 first_name,
                 last_name,
                 preferred_sports,
+                ..
             }) => sqlx::query_scalar(
                 r#"
                 WITH inserted_user AS (
@@ -158,11 +185,17 @@ impl UserController {
                     VALUES
                         ($1, $2, $3, 'player')
                     RETURNING id
+                ), inserted_profile AS (
+                    INSERT INTO player_profiles
+                        (user_id, first_name, last_name, preferred_sports)
+                    VALUES
+                        ((SELECT id FROM inserted_user), $4, $5, $6)
+                    RETURNING user_id
                 )
-                INSERT INTO player_profiles
-                    (user_id, first_name, last_name, preferred_sports)
+                INSERT INTO player_privacy_settings
+                    (user_id)
                 VALUES
-                    ((SELECT id FROM inserted_user), $4, $5, $6)
+                    ((SELECT user_id FROM inserted_profile))
                 RETURNING user_id
                 "#,
             )
@@ -272,6 +305,7 @@ impl UserController {
         let mut query_builder = QueryBuilder::new("");
         let mut separated_query_builder = query_builder.separated(", ");
         let mut is_profiles_table_updated = false;
+        let mut self_declared_levels = None;
 
         if let Some(profile) = new_user_info.profile {
             match (profile, user_role) {
@@ -298,6 +332,8 @@ impl UserController {
                             .push("preferred_sports = ")
                             .push_bind_unseparated(preferred_sports);
                     }
+
+                    self_declared_levels = profile.self_declared_levels;
                 }
                 (UpdateUserProfilePayload::Business(profile), UserRole::Business) => {
                     separated_query_builder.push_unseparated("UPDATE business_profiles SET ");
@@ -309,7 +345,7 @@ impl UserController {
                             .push_bind_unseparated(display_name);
                     }
                 }
-                _ => panic!("invalid profile"), // TODO: return an error "invalid profile" or the like
+                _ => return Err(UserControllerError::ProfileRoleMismatch),
             }
         }
 
@@ -322,7 +358,36 @@ impl UserController {
 
         // ---
 
-        if !(is_users_table_updated || is_profiles_table_updated) {
+        let upsert_sport_ratings_query = self_declared_levels
+            .filter(|levels| !levels.is_empty())
+            .map(|levels| {
+                let (sports, levels): (Vec<Sport>, Vec<Option<i16>>) = levels
+                    .into_iter()
+                    .map(|(sport, level)| (sport, level.map(i16::from)))
+                    .unzip();
+                sqlx::query(
+                    r#"
+                    INSERT INTO player_sport_ratings
+                        (player_id, sport, self_declared_level)
+                    SELECT
+                        $1, *
+                    FROM
+                        UNNEST($2::sport[], $3::SMALLINT[])
+                    ON CONFLICT (player_id, sport) DO UPDATE SET
+                        self_declared_level = EXCLUDED.self_declared_level
+                    "#,
+                )
+                .bind(id)
+                .bind(sports)
+                .bind(levels)
+            });
+
+        // ---
+
+        if !(is_users_table_updated
+            || is_profiles_table_updated
+            || upsert_sport_ratings_query.is_some())
+        {
             return Ok(());
         }
 
@@ -354,6 +419,19 @@ impl UserController {
             }
         }
 
+        if let Some(query) = upsert_sport_ratings_query {
+            match query.execute(&mut *transaction).await {
+                Ok(_) => {}
+                Err(sqlx::Error::Database(err))
+                    if err.constraint()
+                        == Some("player_sport_ratings_self_declared_level_check") =>
+                {
+                    return Err(UserControllerError::InvalidSelfDeclaredLevel);
+                }
+                Err(err) => return Err(UserControllerError::Sqlx(err)),
+            }
+        }
+
         transaction
             .commit()
             .await
@@ -368,12 +446,13 @@ mod tests {
     use crate::{
         configs::config,
         controllers::{
-            InsertUserPayload, UserProfile,
+            InsertUserPayload, UpdateBusinessProfilePayload, UpdateUserInfoPayload,
+            UpdateUserProfilePayload, UserProfile,
             users::{controller::UserController, errors::UserControllerError},
         },
         models::{
             ModelManager,
-            tables::{BusinessProfile, PlayerProfile, Sport, User, UserRole},
+            tables::{BusinessProfile, PlayerProfile, PlayerSportRating, Sport, User, UserRole},
         },
         secrets::SecretManager,
     };
@@ -408,6 +487,12 @@ mod tests {
                 first_name: String::from("player_1_first"),
                 last_name: String::from("player_1_last"),
                 preferred_sports: vec![Sport::Football],
+                sport_ratings: vec![PlayerSportRating {
+                    sport: Sport::Football,
+                    self_declared_level: Some(6),
+                    system_rating: Some(5.5),
+                    confidence: 0.25,
+                }],
             })
         );
 
@@ -561,6 +646,7 @@ mod tests {
             first_name: Alphanumeric.sample_string(&mut rand::rng(), 16),
             last_name: Alphanumeric.sample_string(&mut rand::rng(), 16),
             preferred_sports: vec![Sport::Football, Sport::Basketball],
+            sport_ratings: vec![],
         };
         let profile = UserProfile::Player(profile);
         let user = InsertUserPayload {
@@ -775,4 +861,46 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_update_by_id_err_profile_role_mismatch() -> anyhow::Result<()> {
+        let model_manager = ModelManager::new().await;
+
+        // prepare
+        let username = "player_1";
+        let id = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+            .bind(username)
+            .fetch_one(model_manager.db())
+            .await
+            .context("failed while fetching id")?;
+        let new_user_info = UpdateUserInfoPayload {
+            username: Some(Alphanumeric.sample_string(&mut rand::rng(), 16)),
+            password: None,
+            profile: Some(UpdateUserProfilePayload::Business(
+                UpdateBusinessProfilePayload {
+                    display_name: Some(Alphanumeric.sample_string(&mut rand::rng(), 16)),
+                },
+            )),
+        };
+
+        // exec
+        let result =
+            UserController::update_by_id(&model_manager, id, UserRole::Player, new_user_info).await;
+
+        // check
+        assert!(
+            matches!(result, Err(UserControllerError::ProfileRoleMismatch)),
+            "result: {result:?}"
+        );
+
+        let fetched_username: String =
+            sqlx::query_scalar("SELECT username FROM users WHERE id = $1")
+                .bind(id)
+                .fetch_one(model_manager.db())
+                .await
+                .context("failed while fetching username")?;
+        assert_eq!(fetched_username, username);
+
+        Ok(())
+    }
 }
@@ -6,6 +6,8 @@ pub enum UserControllerError {
     InvalidUsernameForLogin,
     UsernameAlreadyExists,
     BusinessDisplayNameAlreadyExists,
+    ProfileRoleMismatch,
+    InvalidSelfDeclaredLevel,
     Sqlx(sqlx::Error),
 }
 
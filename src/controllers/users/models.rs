@@ -1,5 +1,6 @@
 use crate::models::tables::{BusinessProfile, PlayerProfile, Sport, UserRole};
 use sqlx::FromRow;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 #[cfg_attr(test, derive(Debug))]
@@ -65,6 +66,8 @@ pub struct UpdatePlayerProfilePayload {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub preferred_sports: Option<Vec<Sport>>,
+    /// a `None` level clears the self-declared level of that sport
+    pub self_declared_levels: Option<HashMap<Sport, Option<u8>>>,
 }
 
 pub struct UpdateBusinessProfilePayload {
@@ -5,14 +5,66 @@ use serde::Serialize;
 #[serde(rename_all = "snake_case")]
 pub enum ClientError {
     UserNotFound,
+    PitchNotFound,
+    PitchOwnerIsRequired,
     InvalidUsernameOrPassword,
     LoginNeeded,
     DatabaseError,
     UsernameAlreadyExists,
     BusinessDisplayNameAlreadyExists,
+    PitchDisplayNameAlreadyExists,
+    PriceMustNotBeNegative,
+    PriceCurrencyMustMatchThePitch,
+    MaxPlayersMustBePositive,
+    UnknownAmenity,
+    InvalidCoordinates,
+    RadiusMustBePositiveAndRequiresNear,
+    PriceRangeMustBeNonNegativeAndOrdered,
+    SortByDistanceRequiresNearOrBbox,
+    PitchHasBookings,
+    PitchStatusTransitionNotAllowed,
+    ClosureNotFound,
+    ClosureOverlapsAnotherClosure,
+    ClosureMustEndAfterItStartsAndInTheFuture,
+    ClosureReasonMustNotBeEmptyOrTooLong,
+    OpeningHoursMustCloseAfterTheyOpenAndNotOverlap,
+    UnknownTimezone,
+    FacilityNotFound,
+    FacilityDisplayNameAlreadyExists,
+    ComponentsMustBeOtherUnitsOfTheFacilityWithoutCycles,
+    UnitsHaveOverlappingTimeslots,
+    TimeslotNotFound,
+    TimeslotOverlapsAnotherTimeslot,
+    TimeslotMustEndAfterItStarts,
+    TimeslotIsInThePast,
+    TimeslotMustBeWithinOpeningHours,
+    TimeslotIsBooked,
+    TimeslotRuleNotFound,
+    TimeslotRuleMustHaveWeekdaysAndFitOneSlotBeforeItsEnd,
+    TimeslotIsNotBookable,
+    PricingRuleNotFound,
+    PricingRulePriorityIsTaken,
+    PricingRuleMustHaveWeekdaysOrderedRangesAndOnePriceChange,
+    PriceQuoteMustEndAfterItStartsWithinSevenDays,
+    PriceIsOutOfRange,
+    CalendarFeedNotFound,
+    BulkOperationsMustBeBetweenOneAndFiveHundred,
+    ImportFileMustBeTextCsvOrTextCalendar,
+    ImportFileMustBeCsvWithStartAndEndColumnsOrIcalendar,
+    ImportFileMustHaveAtMostFiveHundredRows,
+    DateRangeMustBeOrderedAndAtMostNinetyTwoDays,
+    InvalidQueryParameters,
+    InvalidCursor,
+    SearchQueryMustHaveBetweenOneAndHundredCharacters,
     PasswordAndConfirmPasswordAreDifferent,
     AdminCannotCreateAccount,
     ThisOperationIsForAdminsOnly,
+    ThisOperationIsForPlayersOnly,
+    ThisOperationIsForBusinessesOnly,
+    ThisOperationIsForThePitchOwnerOnly,
+    ProfileDoesNotMatchAccountType,
+    SkillLevelMustBeBetweenOneAndTen,
+    InternalServerError,
 }
 
 error_impl!(ClientError);
@@ -21,7 +73,12 @@ impl From<&ServerError> for ClientError {
     fn from(server_error: &ServerError) -> Self {
         match server_error {
             ServerError::UserIsNotAdmin => Self::ThisOperationIsForAdminsOnly,
+            ServerError::UserIsNotPlayer => Self::ThisOperationIsForPlayersOnly,
+            ServerError::UserIsNotBusiness => Self::ThisOperationIsForBusinessesOnly,
+            ServerError::NotPitchOwner => Self::ThisOperationIsForThePitchOwnerOnly,
             ServerError::UserNotFound => Self::UserNotFound,
+            ServerError::PitchNotFound => Self::PitchNotFound,
+            ServerError::PitchOwnerIsRequired => Self::PitchOwnerIsRequired,
             ServerError::InvalidUsernameForLogin | ServerError::WrongPassword => {
                 Self::InvalidUsernameOrPassword
             }
@@ -34,7 +91,70 @@ impl From<&ServerError> for ClientError {
             }
             ServerError::UsernameAlreadyExists => Self::UsernameAlreadyExists,
             ServerError::BusinessDisplayNameAlreadyExists => Self::BusinessDisplayNameAlreadyExists,
+            ServerError::PitchDisplayNameAlreadyExists => Self::PitchDisplayNameAlreadyExists,
+            ServerError::NegativePitchPrice => Self::PriceMustNotBeNegative,
+            ServerError::PriceCurrencyMismatch => Self::PriceCurrencyMustMatchThePitch,
+            ServerError::InvalidMaxPlayers => Self::MaxPlayersMustBePositive,
+            ServerError::UnknownAmenity => Self::UnknownAmenity,
+            ServerError::InvalidCoordinates => Self::InvalidCoordinates,
+            ServerError::InvalidGeoFilter => Self::RadiusMustBePositiveAndRequiresNear,
+            ServerError::InvalidPriceRange => Self::PriceRangeMustBeNonNegativeAndOrdered,
+            ServerError::SortByDistanceWithoutOrigin => Self::SortByDistanceRequiresNearOrBbox,
+            ServerError::PitchHasBookings => Self::PitchHasBookings,
+            ServerError::InvalidPitchStatusTransition => Self::PitchStatusTransitionNotAllowed,
+            ServerError::ClosureNotFound => Self::ClosureNotFound,
+            ServerError::ClosureOverlaps => Self::ClosureOverlapsAnotherClosure,
+            ServerError::InvalidClosurePeriod => Self::ClosureMustEndAfterItStartsAndInTheFuture,
+            ServerError::InvalidClosureReason => Self::ClosureReasonMustNotBeEmptyOrTooLong,
+            ServerError::InvalidOpeningHours => {
+                Self::OpeningHoursMustCloseAfterTheyOpenAndNotOverlap
+            }
+            ServerError::UnknownTimezone => Self::UnknownTimezone,
+            ServerError::FacilityNotFound => Self::FacilityNotFound,
+            ServerError::FacilityDisplayNameAlreadyExists => Self::FacilityDisplayNameAlreadyExists,
+            ServerError::InvalidFacilityComponents => {
+                Self::ComponentsMustBeOtherUnitsOfTheFacilityWithoutCycles
+            }
+            ServerError::FacilityUnitsOverlap => Self::UnitsHaveOverlappingTimeslots,
+            ServerError::TimeslotNotFound => Self::TimeslotNotFound,
+            ServerError::TimeslotOverlaps(_) => Self::TimeslotOverlapsAnotherTimeslot,
+            ServerError::InvalidTimeslotPeriod => Self::TimeslotMustEndAfterItStarts,
+            ServerError::TimeslotInThePast => Self::TimeslotIsInThePast,
+            ServerError::TimeslotOutsideOpeningHours => Self::TimeslotMustBeWithinOpeningHours,
+            ServerError::TimeslotIsBooked => Self::TimeslotIsBooked,
+            ServerError::TimeslotRuleNotFound => Self::TimeslotRuleNotFound,
+            ServerError::InvalidTimeslotRule => {
+                Self::TimeslotRuleMustHaveWeekdaysAndFitOneSlotBeforeItsEnd
+            }
+            ServerError::TimeslotNotBookable => Self::TimeslotIsNotBookable,
+            ServerError::PricingRuleNotFound => Self::PricingRuleNotFound,
+            ServerError::PricingRulePriorityTaken => Self::PricingRulePriorityIsTaken,
+            ServerError::InvalidPricingRule => {
+                Self::PricingRuleMustHaveWeekdaysOrderedRangesAndOnePriceChange
+            }
+            ServerError::InvalidPriceQuotePeriod => {
+                Self::PriceQuoteMustEndAfterItStartsWithinSevenDays
+            }
+            ServerError::PriceOutOfRange => Self::PriceIsOutOfRange,
+            ServerError::CalendarFeedNotFound => Self::CalendarFeedNotFound,
+            ServerError::InvalidBulkOperations => {
+                Self::BulkOperationsMustBeBetweenOneAndFiveHundred
+            }
+            ServerError::UnsupportedImportFormat => Self::ImportFileMustBeTextCsvOrTextCalendar,
+            ServerError::InvalidImportFile => {
+                Self::ImportFileMustBeCsvWithStartAndEndColumnsOrIcalendar
+            }
+            ServerError::TooManyImportRows => Self::ImportFileMustHaveAtMostFiveHundredRows,
+            ServerError::InvalidDateRange => Self::DateRangeMustBeOrderedAndAtMostNinetyTwoDays,
+            ServerError::InvalidQueryParams(_) => Self::InvalidQueryParameters,
+            ServerError::InvalidCursor => Self::InvalidCursor,
+            ServerError::InvalidSearchQuery => {
+                Self::SearchQueryMustHaveBetweenOneAndHundredCharacters
+            }
             ServerError::AdminCannotSignup => Self::AdminCannotCreateAccount,
+            ServerError::ProfileRoleMismatch => Self::ProfileDoesNotMatchAccountType,
+            ServerError::InvalidSelfDeclaredLevel => Self::SkillLevelMustBeBetweenOneAndTen,
+            ServerError::HandlerPanicked(_) => Self::InternalServerError,
         }
     }
 }
@@ -1,6 +1,13 @@
 use crate::{
-    controllers::{PitchControllerError, UserControllerError},
+    controllers::{
+        AvailabilityControllerError, BookingControllerError, CalendarFeedControllerError,
+        ClosureControllerError, FacilityControllerError, FavoriteControllerError,
+        NotificationControllerError, OpeningHoursControllerError, PitchControllerError,
+        PlayerControllerError, PricingRuleControllerError, SearchControllerError,
+        TimeslotControllerError, TimeslotRuleControllerError, UserControllerError,
+    },
     errors::error_impl,
+    models::pagination::InvalidCursor,
     secrets::SecretDoesNotMatchTarget,
 };
 use axum::{
@@ -8,7 +15,9 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
+use serde_json::{Value, json};
 use serde_with::{DisplayFromStr, serde_as};
+use uuid::Uuid;
 
 #[serde_as]
 #[derive(Serialize, Clone, Debug)]
@@ -25,10 +34,77 @@ pub enum ServerError {
     DataBase(String),
     AdminCannotSignup,
     UserIsNotAdmin,
+    UserIsNotPlayer,
+    UserIsNotBusiness,
+    /// a business acting on pitches or facilities of another business
+    NotPitchOwner,
+    PitchNotFound,
+    PitchOwnerIsRequired,
+    PitchDisplayNameAlreadyExists,
+    NegativePitchPrice,
+    PriceCurrencyMismatch,
+    InvalidMaxPlayers,
+    UnknownAmenity,
+    InvalidCoordinates,
+    InvalidGeoFilter,
+    InvalidPriceRange,
+    SortByDistanceWithoutOrigin,
+    PitchHasBookings,
+    InvalidPitchStatusTransition,
+    ClosureNotFound,
+    ClosureOverlaps,
+    InvalidClosurePeriod,
+    InvalidClosureReason,
+    InvalidOpeningHours,
+    UnknownTimezone,
+    FacilityNotFound,
+    FacilityDisplayNameAlreadyExists,
+    InvalidFacilityComponents,
+    FacilityUnitsOverlap,
+    TimeslotNotFound,
+    /// holds the conflicting timeslot when it's still there
+    TimeslotOverlaps(Option<Uuid>),
+    InvalidTimeslotPeriod,
+    TimeslotInThePast,
+    TimeslotOutsideOpeningHours,
+    TimeslotIsBooked,
+    TimeslotRuleNotFound,
+    InvalidTimeslotRule,
+    TimeslotNotBookable,
+    PricingRuleNotFound,
+    PricingRulePriorityTaken,
+    InvalidPricingRule,
+    InvalidPriceQuotePeriod,
+    PriceOutOfRange,
+    CalendarFeedNotFound,
+    InvalidBulkOperations,
+    UnsupportedImportFormat,
+    InvalidImportFile,
+    TooManyImportRows,
+    InvalidDateRange,
+    InvalidQueryParams(String),
+    InvalidCursor,
+    InvalidSearchQuery,
+    ProfileRoleMismatch,
+    InvalidSelfDeclaredLevel,
+    HandlerPanicked(String),
 }
 
 error_impl!(ServerError);
 
+impl ServerError {
+    /// Extra context that is safe to show to the client, next to the message
+    pub fn client_details(&self) -> Option<Value> {
+        match self {
+            Self::InvalidQueryParams(details) => Some(json!(details)),
+            Self::TimeslotOverlaps(Some(timeslot_id)) => {
+                Some(json!({ "conflicting_timeslot_id": timeslot_id }))
+            }
+            _ => None,
+        }
+    }
+}
+
 impl From<UserControllerError> for ServerError {
     fn from(user_controller_error: UserControllerError) -> Self {
         match user_controller_error {
@@ -38,19 +114,179 @@ impl From<UserControllerError> for ServerError {
             UserControllerError::BusinessDisplayNameAlreadyExists => {
                 Self::BusinessDisplayNameAlreadyExists
             }
+            UserControllerError::ProfileRoleMismatch => Self::ProfileRoleMismatch,
+            UserControllerError::InvalidSelfDeclaredLevel => Self::InvalidSelfDeclaredLevel,
             UserControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
         }
     }
 }
 
+impl From<PlayerControllerError> for ServerError {
+    fn from(player_controller_error: PlayerControllerError) -> Self {
+        match player_controller_error {
+            PlayerControllerError::PlayerNotFound => Self::UserNotFound,
+            PlayerControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<FavoriteControllerError> for ServerError {
+    fn from(favorite_controller_error: FavoriteControllerError) -> Self {
+        match favorite_controller_error {
+            FavoriteControllerError::PitchNotFound => Self::PitchNotFound,
+            FavoriteControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
 impl From<PitchControllerError> for ServerError {
     fn from(pitch_controller_error: PitchControllerError) -> Self {
         match pitch_controller_error {
+            PitchControllerError::PitchNotFound => Self::PitchNotFound,
+            PitchControllerError::OwnerNotFound => Self::UserNotFound,
+            PitchControllerError::NotOwner => Self::NotPitchOwner,
+            PitchControllerError::DisplayNameAlreadyExists => Self::PitchDisplayNameAlreadyExists,
+            PitchControllerError::NegativePrice => Self::NegativePitchPrice,
+            PitchControllerError::PriceOutOfRange => Self::PriceOutOfRange,
+            PitchControllerError::PriceCurrencyMismatch => Self::PriceCurrencyMismatch,
+            PitchControllerError::InvalidMaxPlayers => Self::InvalidMaxPlayers,
+            PitchControllerError::UnknownAmenity => Self::UnknownAmenity,
+            PitchControllerError::InvalidCoordinates => Self::InvalidCoordinates,
+            PitchControllerError::PitchHasBookings => Self::PitchHasBookings,
+            PitchControllerError::InvalidStatusTransition => Self::InvalidPitchStatusTransition,
+            PitchControllerError::UnknownTimezone => Self::UnknownTimezone,
             PitchControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
         }
     }
 }
 
+impl From<ClosureControllerError> for ServerError {
+    fn from(closure_controller_error: ClosureControllerError) -> Self {
+        match closure_controller_error {
+            ClosureControllerError::PitchNotFound => Self::PitchNotFound,
+            ClosureControllerError::ClosureNotFound => Self::ClosureNotFound,
+            ClosureControllerError::ClosureOverlaps => Self::ClosureOverlaps,
+            ClosureControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<FacilityControllerError> for ServerError {
+    fn from(facility_controller_error: FacilityControllerError) -> Self {
+        match facility_controller_error {
+            FacilityControllerError::FacilityNotFound => Self::FacilityNotFound,
+            FacilityControllerError::PitchNotFound => Self::PitchNotFound,
+            FacilityControllerError::OwnerNotFound => Self::UserNotFound,
+            FacilityControllerError::NotOwner => Self::NotPitchOwner,
+            FacilityControllerError::DisplayNameAlreadyExists => {
+                Self::FacilityDisplayNameAlreadyExists
+            }
+            FacilityControllerError::InvalidComponents => Self::InvalidFacilityComponents,
+            FacilityControllerError::UnitsHaveOverlappingTimeslots => Self::FacilityUnitsOverlap,
+            FacilityControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<TimeslotControllerError> for ServerError {
+    fn from(timeslot_controller_error: TimeslotControllerError) -> Self {
+        match timeslot_controller_error {
+            TimeslotControllerError::PitchNotFound => Self::PitchNotFound,
+            TimeslotControllerError::TimeslotNotFound => Self::TimeslotNotFound,
+            TimeslotControllerError::InvalidPeriod => Self::InvalidTimeslotPeriod,
+            TimeslotControllerError::InThePast => Self::TimeslotInThePast,
+            TimeslotControllerError::OutsideOpeningHours => Self::TimeslotOutsideOpeningHours,
+            TimeslotControllerError::IsBooked => Self::TimeslotIsBooked,
+            TimeslotControllerError::NegativePrice => Self::NegativePitchPrice,
+            TimeslotControllerError::PriceCurrencyMismatch => Self::PriceCurrencyMismatch,
+            TimeslotControllerError::Overlaps(timeslot_id) => Self::TimeslotOverlaps(timeslot_id),
+            TimeslotControllerError::InvalidImportFile => Self::InvalidImportFile,
+            TimeslotControllerError::Pricing(err) => err.into(),
+            TimeslotControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<TimeslotRuleControllerError> for ServerError {
+    fn from(timeslot_rule_controller_error: TimeslotRuleControllerError) -> Self {
+        match timeslot_rule_controller_error {
+            TimeslotRuleControllerError::PitchNotFound => Self::PitchNotFound,
+            TimeslotRuleControllerError::RuleNotFound => Self::TimeslotRuleNotFound,
+            TimeslotRuleControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<PricingRuleControllerError> for ServerError {
+    fn from(pricing_rule_controller_error: PricingRuleControllerError) -> Self {
+        match pricing_rule_controller_error {
+            PricingRuleControllerError::PitchNotFound => Self::PitchNotFound,
+            PricingRuleControllerError::RuleNotFound => Self::PricingRuleNotFound,
+            PricingRuleControllerError::PriorityTaken => Self::PricingRulePriorityTaken,
+            PricingRuleControllerError::PriceOutOfRange => Self::PriceOutOfRange,
+            PricingRuleControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<BookingControllerError> for ServerError {
+    fn from(booking_controller_error: BookingControllerError) -> Self {
+        match booking_controller_error {
+            BookingControllerError::TimeslotNotFound => Self::TimeslotNotFound,
+            BookingControllerError::TimeslotIsBooked => Self::TimeslotIsBooked,
+            BookingControllerError::TimeslotNotBookable => Self::TimeslotNotBookable,
+            BookingControllerError::Pricing(err) => err.into(),
+            BookingControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<AvailabilityControllerError> for ServerError {
+    fn from(availability_controller_error: AvailabilityControllerError) -> Self {
+        match availability_controller_error {
+            AvailabilityControllerError::PitchNotFound => Self::PitchNotFound,
+            AvailabilityControllerError::UnknownTimezone => Self::UnknownTimezone,
+            AvailabilityControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<CalendarFeedControllerError> for ServerError {
+    fn from(calendar_feed_controller_error: CalendarFeedControllerError) -> Self {
+        match calendar_feed_controller_error {
+            CalendarFeedControllerError::PitchNotFound => Self::PitchNotFound,
+            CalendarFeedControllerError::FeedNotFound => Self::CalendarFeedNotFound,
+            CalendarFeedControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<OpeningHoursControllerError> for ServerError {
+    fn from(opening_hours_controller_error: OpeningHoursControllerError) -> Self {
+        match opening_hours_controller_error {
+            OpeningHoursControllerError::PitchNotFound => Self::PitchNotFound,
+            OpeningHoursControllerError::UnknownTimezone => Self::UnknownTimezone,
+            OpeningHoursControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<NotificationControllerError> for ServerError {
+    fn from(notification_controller_error: NotificationControllerError) -> Self {
+        match notification_controller_error {
+            NotificationControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
+impl From<SearchControllerError> for ServerError {
+    fn from(search_controller_error: SearchControllerError) -> Self {
+        match search_controller_error {
+            SearchControllerError::Sqlx(err) => Self::DataBase(err.to_string()),
+        }
+    }
+}
+
 impl From<jsonwebtoken::errors::Error> for ServerError {
     fn from(err: jsonwebtoken::errors::Error) -> Self {
         Self::AuthTokenErr(err)
@@ -66,25 +302,83 @@ impl From<SecretDoesNotMatchTarget> for ServerError {
 impl From<&ServerError> for StatusCode {
     fn from(server_error: &ServerError) -> Self {
         match server_error {
-            ServerError::UserIsNotAdmin => StatusCode::FORBIDDEN,
+            ServerError::UserIsNotAdmin
+            | ServerError::UserIsNotPlayer
+            | ServerError::UserIsNotBusiness
+            | ServerError::NotPitchOwner => StatusCode::FORBIDDEN,
             ServerError::InvalidUsernameForLogin
             | ServerError::WrongPassword
             | ServerError::AuthTokenErr(_)
             | ServerError::AuthTokenNotFoundInCookies => StatusCode::UNAUTHORIZED,
-            ServerError::DataBase(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ServerError::DataBase(_) | ServerError::HandlerPanicked(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
             // TODO: check if there is a better status code for admin can't signup (eg: forbidin,
             // unautherized,bad request!!,not acceptatle!!,misdirected request, unprocessable
             // entity)
             ServerError::PasswordAndConfirmPasswordAreDifferent
             | ServerError::UserNotFound
-            | ServerError::AdminCannotSignup => StatusCode::BAD_REQUEST,
-            ServerError::UsernameAlreadyExists | ServerError::BusinessDisplayNameAlreadyExists => {
-                StatusCode::CONFLICT
-            }
+            | ServerError::PitchNotFound
+            | ServerError::ClosureNotFound
+            | ServerError::InvalidClosurePeriod
+            | ServerError::InvalidClosureReason
+            | ServerError::InvalidOpeningHours
+            | ServerError::UnknownTimezone
+            | ServerError::InvalidDateRange
+            | ServerError::FacilityNotFound
+            | ServerError::InvalidFacilityComponents
+            | ServerError::TimeslotNotFound
+            | ServerError::InvalidTimeslotPeriod
+            | ServerError::TimeslotInThePast
+            | ServerError::TimeslotOutsideOpeningHours
+            | ServerError::TimeslotRuleNotFound
+            | ServerError::InvalidTimeslotRule
+            | ServerError::PricingRuleNotFound
+            | ServerError::InvalidPricingRule
+            | ServerError::InvalidPriceQuotePeriod
+            | ServerError::PriceOutOfRange
+            | ServerError::CalendarFeedNotFound
+            | ServerError::InvalidBulkOperations
+            | ServerError::InvalidImportFile
+            | ServerError::TooManyImportRows
+            | ServerError::AdminCannotSignup
+            | ServerError::InvalidSelfDeclaredLevel
+            | ServerError::NegativePitchPrice
+            | ServerError::PriceCurrencyMismatch
+            | ServerError::InvalidMaxPlayers
+            | ServerError::UnknownAmenity
+            | ServerError::InvalidCoordinates
+            | ServerError::InvalidGeoFilter
+            | ServerError::InvalidPriceRange
+            | ServerError::SortByDistanceWithoutOrigin
+            | ServerError::InvalidQueryParams(_)
+            | ServerError::InvalidCursor
+            | ServerError::InvalidSearchQuery
+            | ServerError::PitchOwnerIsRequired => StatusCode::BAD_REQUEST,
+            ServerError::UsernameAlreadyExists
+            | ServerError::BusinessDisplayNameAlreadyExists
+            | ServerError::PitchDisplayNameAlreadyExists
+            | ServerError::PitchHasBookings
+            | ServerError::InvalidPitchStatusTransition
+            | ServerError::ClosureOverlaps
+            | ServerError::FacilityDisplayNameAlreadyExists
+            | ServerError::FacilityUnitsOverlap
+            | ServerError::TimeslotOverlaps(_)
+            | ServerError::TimeslotIsBooked
+            | ServerError::TimeslotNotBookable
+            | ServerError::PricingRulePriorityTaken => StatusCode::CONFLICT,
+            ServerError::ProfileRoleMismatch => StatusCode::UNPROCESSABLE_ENTITY,
+            ServerError::UnsupportedImportFormat => StatusCode::UNSUPPORTED_MEDIA_TYPE,
         }
     }
 }
 
+impl From<InvalidCursor> for ServerError {
+    fn from(_: InvalidCursor) -> Self {
+        Self::InvalidCursor
+    }
+}
+
 impl IntoResponse for ServerError {
     fn into_response(self) -> Response {
         let status_code = StatusCode::from(&self);
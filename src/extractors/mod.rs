@@ -1,3 +1,5 @@
 mod auth_token;
+mod paginated;
 
 pub use auth_token::AuthToken;
+pub use paginated::Paginated;
@@ -0,0 +1,54 @@
+use crate::{
+    errors::ServerError,
+    models::api_schemas::query_params::{NoFilters, PaginationQuery},
+};
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{Uri, request::Parts},
+};
+use serde::de::DeserializeOwned;
+
+/// Query params of a list endpoint.
+///
+/// The pagination params are split from the endpoint's own params `Q`, so both can still reject
+/// unknown fields.
+pub struct Paginated<Q = NoFilters> {
+    pub pagination: PaginationQuery,
+    pub query: Q,
+}
+
+impl<S, Q> FromRequestParts<S> for Paginated<Q>
+where
+    S: Send + Sync,
+    Q: DeserializeOwned,
+{
+    type Rejection = ServerError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let (pagination_params, query_params): (Vec<&str>, Vec<&str>) = parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .partition(|param| {
+                let key = param.split('=').next().unwrap_or_default();
+                PaginationQuery::KEYS.contains(&key)
+            });
+
+        Ok(Self {
+            pagination: parse_query(&pagination_params.join("&"))?,
+            query: parse_query(&query_params.join("&"))?,
+        })
+    }
+}
+
+fn parse_query<T: DeserializeOwned>(query: &str) -> Result<T, ServerError> {
+    let uri: Uri = format!("/?{query}")
+        .parse()
+        .expect("the params come from a valid URI");
+
+    Query::try_from_uri(&uri)
+        .map(|Query(query)| query)
+        .map_err(|rejection| ServerError::InvalidQueryParams(rejection.body_text()))
+}
@@ -47,6 +47,24 @@ impl<'r> RequestLogInfo<'r> {
     }
 }
 
+/// Log line of a failed run of a background task
+#[derive(Serialize)]
+pub struct TaskLogInfo<'t> {
+    task: &'t str,
+    timestamp: u128,
+    server_error: &'t ServerError,
+}
+
+impl<'t> TaskLogInfo<'t> {
+    pub fn new(task: &'t str, server_error: &'t ServerError) -> Self {
+        Self {
+            task,
+            timestamp: get_millis_since_epoch(),
+            server_error,
+        }
+    }
+}
+
 fn get_millis_since_epoch() -> u128 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1,4 +1,7 @@
-use crate::{configs::config, models::ModelManager};
+use crate::{
+    configs::config, controllers::TimeslotRuleController, errors::ServerError,
+    logging::TaskLogInfo, models::ModelManager,
+};
 use axum::{
     Router,
     http::{Method, StatusCode, Uri},
@@ -6,8 +9,10 @@ use axum::{
     response::IntoResponse,
     routing::get,
 };
-use tokio::{net::TcpListener, signal};
+use serde_json::json;
+use tokio::{net::TcpListener, signal, time};
 use tower_cookies::CookieManagerLayer;
+use tower_http::catch_panic::CatchPanicLayer;
 
 mod configs;
 mod controllers;
@@ -31,6 +36,8 @@ async fn main() {
     #[cfg(debug_assertions)]
     model_manager.seed_fake_data().await;
 
+    tokio::spawn(materialize_timeslot_rules(model_manager.clone()));
+
     let app = Router::new().nest("/api/v1", get_app_router(model_manager.clone()));
     let listener = TcpListener::bind(&config().server_address)
         .await
@@ -49,6 +56,19 @@ async fn main() {
         .expect("axum::serve never retruns");
 }
 
+/// Keeps the timeslots of recurring rules generated up to the horizon, which rolls forward with time
+async fn materialize_timeslot_rules(model_manager: ModelManager) {
+    let mut interval = time::interval(config().timeslot_rules_refresh_interval);
+    loop {
+        interval.tick().await;
+        if let Err(err) = TimeslotRuleController::materialize_rules(&model_manager).await {
+            let server_error = ServerError::from(err);
+            let log_line = TaskLogInfo::new("materialize_timeslot_rules", &server_error);
+            println!("{}", json!(log_line));
+        }
+    }
+}
+
 async fn shutdown_signal(
     #[cfg_attr(not(debug_assertions), allow(unused))] model_manager: ModelManager,
 ) {
@@ -89,7 +109,14 @@ fn get_app_router(model_manager: ModelManager) -> Router {
         .nest("/auth", routers::auth::get_router())
         .nest("/users", routers::users::get_router())
         .nest("/pitches", routers::pitches::get_router())
+        .nest("/facilities", routers::facilities::get_router())
+        .nest("/players", routers::players::get_router())
+        .nest("/timeslots", routers::timeslots::get_router())
+        .nest("/bookings", routers::bookings::get_router())
+        .nest("/calendar-feeds", routers::calendar_feeds::get_router())
+        .nest("/search", routers::search::get_router())
         .with_state(model_manager)
+        .layer(CatchPanicLayer::custom(middlewares::handle_panic))
         .layer(middleware::map_response(
             middlewares::insert_response_body_on_error,
         ))
@@ -12,6 +12,7 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use serde_json::json;
+use std::any::Any;
 use uuid::Uuid;
 
 pub async fn generate_request_id(mut request: Request) -> Request {
@@ -27,11 +28,14 @@ pub async fn insert_response_body_on_error(
 ) -> Response {
     if let Some(server_error) = response.extensions().get::<ServerError>() {
         let client_error = ClientError::from(server_error);
-        let body = json!({
+        let mut body = json!({
             "request_id": request_id,
             "status": response.status().as_u16(),
             "message": client_error,
         });
+        if let Some(details) = server_error.client_details() {
+            body["details"] = details;
+        }
 
         let (parts, _body) = response.into_parts();
         let mut response = (parts.status, Json(body)).into_response();
@@ -43,6 +47,19 @@ pub async fn insert_response_body_on_error(
     }
 }
 
+/// Converts a panic in a handler into a `ServerError` so that it gets the standard error body
+pub fn handle_panic(panic_payload: Box<dyn Any + Send + 'static>) -> Response {
+    let message = if let Some(message) = panic_payload.downcast_ref::<&str>() {
+        String::from(*message)
+    } else if let Some(message) = panic_payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic payload")
+    };
+
+    ServerError::HandlerPanicked(message).into_response()
+}
+
 pub async fn log_response(
     Extension(request_id): Extension<Uuid>,
     auth_token: Option<AuthToken>,
@@ -83,3 +100,14 @@ pub async fn authenticate_admin(
         _ => Err(ServerError::UserIsNotAdmin),
     }
 }
+
+pub async fn authenticate_player(
+    auth_token: AuthToken,
+    request: Request,
+    next: Next,
+) -> Result<Response, ServerError> {
+    match auth_token.user_role {
+        UserRole::Player => Ok(next.run(request).await),
+        _ => Err(ServerError::UserIsNotPlayer),
+    }
+}
@@ -1,8 +1,178 @@
-use crate::models::tables::UserRole;
-use serde::Deserialize;
+use crate::models::{
+    geo::{BoundingBox, GeoPoint},
+    pagination::{DEFAULT_PAGE_LIMIT, InvalidCursor, MAX_PAGE_LIMIT, PageRequest, decode_cursor},
+    price::Price,
+    tables::{PitchEnvironment, Sport, SurfaceType, UserRole},
+    time_window::TimeWindow,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_with::{DisplayFromStr, StringWithSeparator, formats::CommaSeparator, serde_as};
+use uuid::Uuid;
 
 #[derive(Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct GetUserInfoQuery {
     pub user_role: UserRole,
 }
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetPitchesQuery {
+    /// admins only
+    pub business_id: Option<Uuid>,
+    /// comma separated, pitches can have any of them
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, Sport>")]
+    #[serde(default)]
+    pub sport: Vec<Sport>,
+    /// per hour with its currency, e.g. `150 AED`, only pitches in that currency match
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub min_price: Option<Price>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub max_price: Option<Price>,
+    pub surface: Option<SurfaceType>,
+    pub environment: Option<PitchEnvironment>,
+    /// comma separated, pitches must have all of them
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[serde(default)]
+    pub amenities: Vec<String>,
+    pub min_capacity: Option<i16>,
+    /// `lat,lng`, results are sorted by distance from it
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub near: Option<GeoPoint>,
+    /// requires `near`
+    pub radius_km: Option<f64>,
+    /// `min_lng,min_lat,max_lng,max_lat`
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub bbox: Option<BoundingBox>,
+    /// `start,end` in RFC 3339, pitches must have a free timeslot inside it
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub available_between: Option<TimeWindow>,
+    /// players and guests get pitches sorted by relevance unless this is `false` or `sort` is set
+    pub relevance: Option<bool>,
+    pub sort: Option<PitchSortQuery>,
+    /// adds how the relevance score of each pitch was computed
+    #[serde(default)]
+    pub debug: bool,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum PitchSortQuery {
+    #[serde(rename = "price")]
+    Price,
+    #[serde(rename = "-price")]
+    PriceDesc,
+    #[serde(rename = "rating")]
+    Rating,
+    #[serde(rename = "-rating")]
+    RatingDesc,
+    /// requires `near` or `bbox`
+    #[serde(rename = "distance")]
+    Distance,
+}
+
+/// Pagination params, shared by every list endpoint through the `Paginated` extractor
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaginationQuery {
+    pub limit: Option<u32>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub include_total: bool,
+}
+
+impl PaginationQuery {
+    pub const KEYS: [&str; 3] = ["limit", "cursor", "include_total"];
+
+    pub fn page_request<K: DeserializeOwned>(&self) -> Result<PageRequest<K>, InvalidCursor> {
+        let after = self.cursor.as_deref().map(decode_cursor).transpose()?;
+
+        Ok(PageRequest {
+            limit: self
+                .limit
+                .unwrap_or(DEFAULT_PAGE_LIMIT)
+                .clamp(1, MAX_PAGE_LIMIT)
+                .into(),
+            after,
+            include_total: self.include_total,
+        })
+    }
+}
+
+/// For list endpoints that only take pagination params
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoFilters {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchQuery {
+    /// free text, the whole query is matched against every group
+    #[serde(default)]
+    pub q: String,
+    /// results per group
+    pub limit: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetEffectiveOpeningHoursQuery {
+    /// local date of the pitch
+    pub from: NaiveDate,
+    /// included
+    pub to: NaiveDate,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportTimeslotsQuery {
+    /// of the rows that don't name their pitch
+    pub pitch_id: Option<Uuid>,
+    /// only the diff is returned until the import is applied
+    #[serde(default)]
+    pub apply: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetAvailabilityQuery {
+    /// local date in `tz`
+    pub from: NaiveDate,
+    /// included
+    pub to: NaiveDate,
+    /// IANA name the days are cut in, the pitch's timezone by default
+    pub tz: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetTimeslotsQuery {
+    pub pitch_id: Option<Uuid>,
+    /// owner of the pitches
+    pub business_id: Option<Uuid>,
+    /// keeps the timeslots that end after it
+    pub from: Option<DateTime<Utc>>,
+    /// keeps the timeslots that start before it
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetPitchTimeslotsQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetPriceQuoteQuery {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
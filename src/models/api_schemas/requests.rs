@@ -1,5 +1,14 @@
-use crate::models::{api_schemas::common_schemas::UserProfile, tables::Sport};
+use crate::models::{
+    api_schemas::common_schemas::UserProfile,
+    price::Price,
+    tables::{PitchEnvironment, PitchStatus, PrivacyLevel, Sport, SurfaceType, Weekday},
+    time_of_day::TimeOfDay,
+};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
+use serde_with::{DisplayFromStr, serde_as};
+use std::collections::HashMap;
+use uuid::Uuid;
 
 #[derive(Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -42,6 +51,7 @@ pub struct UpdatePlayerProfilePayload {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub preferred_sports: Option<Vec<Sport>>,
+    pub self_declared_levels: Option<HashMap<Sport, Option<u8>>>,
 }
 
 #[derive(Deserialize)]
@@ -49,3 +59,254 @@ pub struct UpdatePlayerProfilePayload {
 pub struct UpdateBusinessProfilePayload {
     pub display_name: Option<String>,
 }
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdatePrivacySettingsPayload {
+    pub name_visibility: Option<PrivacyLevel>,
+    pub member_since_visibility: Option<PrivacyLevel>,
+    pub total_bookings_visibility: Option<PrivacyLevel>,
+    pub favorite_sport_visibility: Option<PrivacyLevel>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreatePitchPayload {
+    /// required for admins, businesses can only create pitches for themselves
+    pub owner_id: Option<Uuid>,
+    pub name: String,
+    pub sport: Sport,
+    /// the pitch can also be played for these sports
+    #[serde(default)]
+    pub other_sports: Vec<Sport>,
+    /// its currency can't be changed later
+    pub price_per_hour: Price,
+    pub image_url: Option<String>,
+    pub surface: Option<SurfaceType>,
+    pub environment: Option<PitchEnvironment>,
+    pub description: Option<String>,
+    pub max_players: Option<i16>,
+    /// names from `GET /pitches/amenities`
+    #[serde(default)]
+    pub amenities: Vec<String>,
+    pub location: Option<PitchLocationPayload>,
+    /// drafts are only listed for their owner and for admins until they are published
+    #[serde(default)]
+    pub is_draft: bool,
+    /// IANA name, e.g. "Asia/Dubai", defaults to UTC
+    pub timezone: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdatePitchPayload {
+    pub name: Option<String>,
+    pub sport: Option<Sport>,
+    /// replaces the current other sports
+    pub other_sports: Option<Vec<Sport>>,
+    /// in the currency of the pitch
+    pub price_per_hour: Option<Price>,
+    pub image_url: Option<String>,
+    pub surface: Option<SurfaceType>,
+    pub environment: Option<PitchEnvironment>,
+    pub description: Option<String>,
+    pub max_players: Option<i16>,
+    /// replaces the current amenities
+    pub amenities: Option<Vec<String>>,
+    /// replaces the current location
+    pub location: Option<PitchLocationPayload>,
+    /// IANA name, the timeslots already created keep their instants
+    pub timezone: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PitchLocationPayload {
+    pub address_line_1: String,
+    pub address_line_2: Option<String>,
+    pub city: String,
+    pub postal_code: Option<String>,
+    pub country: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetPitchStatusPayload {
+    pub status: PitchStatus,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateClosurePayload {
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// shown on the pitch page
+    pub reason: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateFacilityPayload {
+    /// required for admins, businesses can only create facilities for themselves
+    pub owner_id: Option<Uuid>,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetFacilityUnitPayload {
+    /// other units of the facility that can't be played on at the same time, e.g. the two halves
+    /// of a full field
+    #[serde(default)]
+    pub component_ids: Vec<Uuid>,
+}
+
+/// Replaces all the opening hours of the pitch, times are `HH:MM` in `timezone`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetOpeningHoursPayload {
+    /// IANA name, e.g. `Asia/Dubai`
+    pub timezone: String,
+    /// leave it empty to open all day, every day
+    pub weekly: Vec<WeeklyOpeningHoursPayload>,
+    #[serde(default)]
+    pub exceptions: Vec<OpeningHoursExceptionPayload>,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeeklyOpeningHoursPayload {
+    pub weekday: Weekday,
+    #[serde_as(as = "DisplayFromStr")]
+    pub opens_at: TimeOfDay,
+    /// `24:00` for midnight
+    #[serde_as(as = "DisplayFromStr")]
+    pub closes_at: TimeOfDay,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpeningHoursExceptionPayload {
+    pub date: NaiveDate,
+    /// leave both out to close the pitch all day
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub opens_at: Option<TimeOfDay>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub closes_at: Option<TimeOfDay>,
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTimeslotPayload {
+    pub pitch_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// overrides the hourly price of the pitch, in the pitch's currency
+    pub price: Option<Price>,
+}
+
+/// Leave a field out to keep it unchanged
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateTimeslotPayload {
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub price: Option<Price>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BulkTimeslotsPayload {
+    #[serde(default)]
+    pub mode: BulkMode,
+    /// run in order, so an operation sees the changes of the previous ones
+    pub operations: Vec<BulkTimeslotOperation>,
+}
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkMode {
+    /// a single failing operation rolls back all of them
+    #[default]
+    AllOrNothing,
+    /// the operations that succeed are kept
+    BestEffort,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+pub enum BulkTimeslotOperation {
+    Create {
+        pitch_id: Uuid,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        price: Option<Price>,
+    },
+    /// leave a field out to keep it unchanged
+    Update {
+        timeslot_id: Uuid,
+        starts_at: Option<DateTime<Utc>>,
+        ends_at: Option<DateTime<Utc>>,
+        price: Option<Price>,
+    },
+    Delete {
+        timeslot_id: Uuid,
+    },
+}
+
+/// Times are `HH:MM` in the pitch's timezone
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTimeslotRulePayload {
+    pub weekdays: Vec<Weekday>,
+    /// of the first timeslot of each day
+    #[serde_as(as = "DisplayFromStr")]
+    pub starts_at: TimeOfDay,
+    /// `24:00` for midnight, no timeslot ends after it
+    #[serde_as(as = "DisplayFromStr")]
+    pub ends_at: TimeOfDay,
+    pub slot_minutes: i32,
+    pub starts_on: NaiveDate,
+    /// included, leave it out for a series without an end
+    pub until: Option<NaiveDate>,
+}
+
+/// Times are `HH:MM` and dates are local ones, in the pitch's timezone
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreatePricingRulePayload {
+    pub name: String,
+    /// the highest one wins when several rules match, unique among the rules of the pitch
+    pub priority: i32,
+    pub weekdays: Vec<Weekday>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub starts_at: TimeOfDay,
+    /// `24:00` for midnight
+    #[serde_as(as = "DisplayFromStr")]
+    pub ends_at: TimeOfDay,
+    pub starts_on: Option<NaiveDate>,
+    /// included
+    pub until: Option<NaiveDate>,
+    /// minutes between the booking and the start of the timeslot
+    pub min_lead_minutes: Option<i32>,
+    pub max_lead_minutes: Option<i32>,
+    /// replaces the hourly price of the pitch, set it or `adjustment_percent`
+    pub price_per_hour_cents: Option<i32>,
+    /// `25` for 25% more, `-10` for a 10% discount
+    pub adjustment_percent: Option<i32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateBookingPayload {
+    pub timeslot_id: Uuid,
+}
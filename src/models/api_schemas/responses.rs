@@ -1,11 +1,58 @@
-use crate::models::api_schemas::{
-    common_schemas::UserProfile, impl_into_response_with_json_body, impl_into_response_with_no_body,
+use crate::{
+    errors::ClientError,
+    models::{
+        api_schemas::{
+            common_schemas::UserProfile, impl_into_response_with_json_body,
+            impl_into_response_with_no_body,
+        },
+        pagination::Paged,
+        price::Price,
+        tables::{
+            AvailabilityKind, PitchEnvironment, PitchStatus, PlayerPrivacySettings, Sport,
+            SurfaceType, Weekday,
+        },
+        time_of_day::TimeOfDay,
+    },
 };
-use axum::http::StatusCode;
+use axum::{
+    Json,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use serde::Serialize;
-use serde_with::skip_serializing_none;
+use serde_with::{DisplayFromStr, serde_as, skip_serializing_none};
 use uuid::Uuid;
 
+/// Envelope of every list response
+#[derive(Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// pass it as `cursor` to get the next page, `null` on the last page
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+    /// only set when requested with `include_total=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+}
+
+impl<T> From<Paged<T>> for Page<T> {
+    fn from(paged: Paged<T>) -> Self {
+        Self {
+            items: paged.items,
+            has_more: paged.next_cursor.is_some(),
+            next_cursor: paged.next_cursor,
+            total: paged.total,
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for Page<T> {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
 #[derive(Serialize)]
 pub struct GetUserPersonalInfoResponse {
     pub id: Uuid,
@@ -26,33 +73,579 @@ pub struct SignupResponse {
 
 pub struct UpdateUserInfoResponse;
 
-/////////// ------
+#[derive(Serialize)]
+pub struct GetPrivacySettingsResponse(pub PlayerPrivacySettings);
+
+pub struct UpdatePrivacySettingsResponse;
+
+/// Fields hidden by the player's privacy settings are omitted
+#[skip_serializing_none]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPlayerPublicProfileResponse {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub member_since: Option<DateTime<Utc>>,
+    pub total_bookings: Option<i64>,
+    pub favorite_sport: Option<Sport>,
+}
+
 #[skip_serializing_none]
 #[derive(Serialize)]
 // TODO: check if we can use snake_case here and modify the fetching function in JS
 #[serde(rename_all = "camelCase")]
 pub struct Pitch {
-    pub id: String,
+    pub id: Uuid,
     pub name: String,
-    pub sport: String,
-    pub location: String,
-    pub address: String,
-    pub price_per_hour: f64,
-    pub rating: f64,
-    pub review_count: i32,
-    pub image_url: String,
+    pub sport: Sport,
+    /// only set when the pitch can also be played for other sports
+    pub other_sports: Option<Vec<Sport>>,
+    /// only set when the pitch is a unit of a facility
+    pub facility_id: Option<Uuid>,
+    pub price_per_hour: Price,
+    /// `None` if the pitch has no reviews yet
+    pub rating: Option<f64>,
+    pub review_count: i64,
+    pub image_url: Option<String>,
+    pub surface: Option<SurfaceType>,
+    pub environment: Option<PitchEnvironment>,
+    pub description: Option<String>,
+    pub max_players: Option<i16>,
     pub amenities: Vec<String>,
-    pub description: String,
+    /// IANA name, the pitch's times are wall clock times there
+    pub timezone: String,
+    /// city and country, e.g. "Sharjah, UAE"
+    pub location: Option<String>,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// only set when searching with `near` or `bbox`
+    pub distance_km: Option<f64>,
     pub owner_name: String,
-    pub owner_avatar: String,
+    pub owner_avatar: Option<String>,
+    pub is_favorite: bool,
+    /// only set for pitches that aren't published, which only their owner and admins can see
+    pub status: Option<PitchStatus>,
+    /// the current or next closure
+    pub closure: Option<PitchClosureNotice>,
+    /// only set with `debug=true` when sorted by relevance
+    pub relevance: Option<PitchRelevance>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PitchClosureNotice {
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+    pub reason: String,
+}
+
+/// Parts of the relevance score, each between 0 and 1 and `null` when it doesn't apply
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PitchRelevance {
+    pub score: f64,
+    pub sport: Option<f64>,
+    pub distance: Option<f64>,
+    pub price: Option<f64>,
+    pub rating: Option<f64>,
+    pub availability: Option<f64>,
+    pub popularity: Option<f64>,
 }
-/////////// ------
+
+pub type GetPitchesResponse = Page<Pitch>;
 
 #[derive(Serialize)]
-pub struct GetPitchesResponse(pub Vec<Pitch>);
+pub struct GetAmenitiesResponse {
+    pub amenities: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct CreatePitchResponse {
+    pub pitch_id: Uuid,
+}
+
+pub struct UpdatePitchResponse;
+
+pub struct DeletePitchResponse;
+
+#[derive(Serialize)]
+pub struct FavoritePitch {
+    pub pitch_id: Uuid,
+    pub display_name: String,
+    pub sport: Sport,
+    pub favorited_at: DateTime<Utc>,
+}
+
+pub type GetFavoritesResponse = Page<FavoritePitch>;
+
+pub struct UpdateFavoritesResponse;
+
+#[derive(Serialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub type GetNotificationsResponse = Page<Notification>;
+
+#[derive(Serialize)]
+pub struct GetPitchFavoritesCountResponse {
+    pub pitch_id: Uuid,
+    pub favorites_count: i64,
+}
+
+#[derive(Serialize)]
+pub struct PitchClosure {
+    pub id: Uuid,
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+    pub reason: String,
+    /// pending closures are only listed for the pitch's owner and for admins
+    pub is_confirmed: bool,
+}
+
+#[derive(Serialize)]
+pub struct GetPitchClosuresResponse {
+    pub closures: Vec<PitchClosure>,
+}
+
+#[derive(Serialize)]
+pub struct AffectedBooking {
+    pub booking_id: Uuid,
+    pub timeslot_id: Uuid,
+    pub booked_by: Uuid,
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+}
+
+/// Closures with affected bookings stay pending until the owner confirms them
+#[derive(Serialize)]
+pub struct CreateClosureResponse {
+    pub closure_id: Uuid,
+    pub is_confirmed: bool,
+    pub affected_bookings: Vec<AffectedBooking>,
+}
+
+pub struct UpdateClosureResponse;
+
+pub struct DeleteClosureResponse;
+
+#[derive(Serialize)]
+pub struct CreateFacilityResponse {
+    pub facility_id: Uuid,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize)]
+pub struct FacilityUnit {
+    pub id: Uuid,
+    pub name: String,
+    pub sport: Sport,
+    pub other_sports: Vec<Sport>,
+    /// only set for units that aren't published, which only the owner and admins can see
+    pub status: Option<PitchStatus>,
+    /// empty for single courts
+    pub component_ids: Vec<Uuid>,
+}
+
+#[derive(Serialize)]
+pub struct GetFacilityResponse {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub units: Vec<FacilityUnit>,
+}
+
+pub struct UpdateFacilityResponse;
+
+pub struct DeleteFacilityResponse;
+
+#[derive(Serialize)]
+pub struct Timeslot {
+    pub id: Uuid,
+    pub pitch_id: Uuid,
+    /// with the offset of the pitch's timezone at that time
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+    /// IANA name of the pitch's timezone
+    pub timezone: String,
+    pub is_booked: bool,
+    /// the recurring rule that generated it
+    pub rule_id: Option<Uuid>,
+    /// the price set on the timeslot, otherwise what the pricing rules of the pitch make it cost
+    /// if booked now
+    pub price: Price,
+    /// whether the price was set on the timeslot, the pricing rules don't apply then
+    pub has_custom_price: bool,
+}
+
+pub type GetTimeslotsResponse = Page<Timeslot>;
+
+#[derive(Serialize)]
+pub struct CreateTimeslotResponse {
+    pub timeslot_id: Uuid,
+}
+
+pub struct UpdateTimeslotResponse;
+
+pub struct DeleteTimeslotResponse;
+
+#[derive(Serialize)]
+pub struct BulkTimeslotsResponse {
+    /// `false` when nothing was applied, in all-or-nothing mode with a failed operation
+    pub is_committed: bool,
+    /// one per operation, in the same order
+    pub results: Vec<BulkTimeslotResult>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize)]
+pub struct BulkTimeslotResult {
+    pub status: BulkTimeslotStatus,
+    /// of the created, updated or deleted timeslot
+    pub timeslot_id: Option<Uuid>,
+    /// same message as the single operation endpoints
+    pub error: Option<ClientError>,
+    pub conflicting_timeslot_id: Option<Uuid>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkTimeslotStatus {
+    Created,
+    Updated,
+    Deleted,
+    Failed,
+    /// succeeded, but another operation failed in all-or-nothing mode
+    RolledBack,
+}
+
+/// What importing a file does to the timeslots, or did once applied
+#[derive(Serialize)]
+pub struct ImportTimeslotsResponse {
+    pub is_applied: bool,
+    pub creates: Vec<ImportedTimeslot>,
+    /// timeslots that can't be created, e.g. overlapping another one
+    pub conflicts: Vec<ImportedTimeslot>,
+    /// timeslots that already exist or are in the past
+    pub skips: Vec<ImportedTimeslot>,
+    /// rows that can't be read
+    pub errors: Vec<ImportRowError>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize)]
+pub struct ImportedTimeslot {
+    /// in the file, from 1
+    pub line: usize,
+    pub pitch_id: Uuid,
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+    pub price: Option<Price>,
+    /// of the created timeslot once applied, or of the existing one
+    pub timeslot_id: Option<Uuid>,
+    /// same message as `POST /timeslots`
+    pub error: Option<ClientError>,
+    pub conflicting_timeslot_id: Option<Uuid>,
+}
+
+#[derive(Serialize)]
+pub struct ImportRowError {
+    pub line: usize,
+    pub message: ImportRowMessage,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowMessage {
+    RowMustHaveAValueForEveryColumn,
+    StartMustBeADateTime,
+    EndMustBeADateTime,
+    PriceMustBeAnAmountAndACurrency,
+    AllDayEventsCannotBeImported,
+    RecurringEventsCannotBeImported,
+    PitchIsRequired,
+    PitchNotFound,
+    UnknownTimezone,
+    EndMustBeAfterStart,
+}
+
+#[serde_as]
+#[derive(Serialize)]
+pub struct TimeslotRule {
+    pub id: Uuid,
+    pub weekdays: Vec<Weekday>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub starts_at: TimeOfDay,
+    #[serde_as(as = "DisplayFromStr")]
+    pub ends_at: TimeOfDay,
+    pub slot_minutes: i32,
+    pub starts_on: NaiveDate,
+    pub until: Option<NaiveDate>,
+}
+
+#[derive(Serialize)]
+pub struct GetTimeslotRulesResponse {
+    pub rules: Vec<TimeslotRule>,
+}
+
+#[derive(Serialize)]
+pub struct CreateTimeslotRuleResponse {
+    pub rule_id: Uuid,
+    /// timeslots generated right away, the following ones are generated as time goes by
+    pub created_timeslots: i32,
+}
+
+pub struct DeleteTimeslotRuleResponse;
+
+#[derive(Serialize)]
+pub struct PitchAvailability {
+    pub timezone: String,
+    pub days: Vec<AvailabilityDay>,
+}
+
+#[derive(Serialize)]
+pub struct AvailabilityDay {
+    pub date: NaiveDate,
+    /// ordered and not overlapping, the gaps are open hours without timeslots
+    pub intervals: Vec<AvailabilityInterval>,
+}
+
+#[derive(Serialize)]
+pub struct AvailabilityInterval {
+    pub kind: AvailabilityKind,
+    /// with the offset of the requested timezone
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+}
+
+/// Sent with an `ETag`, and without a body when the client's copy is still current
+pub struct GetAvailabilityResponse {
+    pub etag: String,
+    /// `None` when `If-None-Match` already has `etag`
+    pub availability: Option<PitchAvailability>,
+}
+
+impl IntoResponse for GetAvailabilityResponse {
+    fn into_response(self) -> Response {
+        let etag = [(header::ETAG, format!("\"{}\"", self.etag))];
+        match self.availability {
+            Some(availability) => (etag, Json(availability)).into_response(),
+            None => (StatusCode::NOT_MODIFIED, etag).into_response(),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Serialize)]
+pub struct PricingRule {
+    pub id: Uuid,
+    pub name: String,
+    pub priority: i32,
+    pub weekdays: Vec<Weekday>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub starts_at: TimeOfDay,
+    #[serde_as(as = "DisplayFromStr")]
+    pub ends_at: TimeOfDay,
+    pub starts_on: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub min_lead_minutes: Option<i32>,
+    pub max_lead_minutes: Option<i32>,
+    pub price_per_hour_cents: Option<i32>,
+    pub adjustment_percent: Option<i32>,
+}
+
+#[derive(Serialize)]
+pub struct GetPricingRulesResponse {
+    /// highest priority first
+    pub rules: Vec<PricingRule>,
+}
+
+#[derive(Serialize)]
+pub struct CreatePricingRuleResponse {
+    pub rule_id: Uuid,
+}
+
+pub struct DeletePricingRuleResponse;
+
+#[derive(Serialize)]
+pub struct GetPriceQuoteResponse {
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+    /// what booking the period now would cost
+    pub price: Price,
+    pub segments: Vec<PriceQuoteSegment>,
+}
+
+#[derive(Serialize)]
+pub struct PriceQuoteSegment {
+    pub starts_at: DateTime<FixedOffset>,
+    pub ends_at: DateTime<FixedOffset>,
+    /// the rule that sets the hourly price, none when it's the pitch's own price
+    pub rule_id: Option<Uuid>,
+    pub rule_name: Option<String>,
+    pub price_per_hour: Price,
+    pub price: Price,
+}
+
+#[derive(Serialize)]
+pub struct CreateBookingResponse {
+    pub booking_id: Uuid,
+    /// what the timeslot was sold at, later pricing changes don't affect it
+    pub price: Price,
+}
+
+/// Secret link to subscribe to an `.ics` feed, anyone holding it can read the feed
+#[derive(Serialize)]
+pub struct CalendarFeedResponse {
+    pub token: String,
+    /// relative to the API root
+    pub path: String,
+}
+
+pub struct DeleteCalendarFeedResponse;
+
+/// iCalendar body, for calendar apps to subscribe to
+pub struct GetCalendarResponse {
+    pub ics: String,
+}
+
+impl IntoResponse for GetCalendarResponse {
+    fn into_response(self) -> Response {
+        let content_type = [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")];
+        (content_type, self.ics).into_response()
+    }
+}
+
+#[serde_as]
+#[derive(Serialize)]
+pub struct PitchWeeklyHours {
+    pub weekday: Weekday,
+    #[serde_as(as = "DisplayFromStr")]
+    pub opens_at: TimeOfDay,
+    #[serde_as(as = "DisplayFromStr")]
+    pub closes_at: TimeOfDay,
+}
+
+#[serde_as]
+#[derive(Serialize)]
+pub struct PitchOpeningHoursException {
+    pub date: NaiveDate,
+    /// both are `null` when the pitch is closed all day
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub opens_at: Option<TimeOfDay>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub closes_at: Option<TimeOfDay>,
+    pub reason: Option<String>,
+}
+
+/// Times are `HH:MM` in `timezone`
+#[derive(Serialize)]
+pub struct GetOpeningHoursResponse {
+    pub timezone: String,
+    pub weekly: Vec<PitchWeeklyHours>,
+    /// only the ones that haven't passed yet
+    pub exceptions: Vec<PitchOpeningHoursException>,
+}
+
+pub struct SetOpeningHoursResponse;
+
+#[derive(Serialize)]
+pub struct OpeningInterval {
+    pub opens_at: DateTime<FixedOffset>,
+    pub closes_at: DateTime<FixedOffset>,
+}
+
+#[derive(Serialize)]
+pub struct EffectiveOpeningDay {
+    pub date: NaiveDate,
+    pub is_open: bool,
+    pub intervals: Vec<OpeningInterval>,
+    /// of the exception that replaced the weekly hours that day
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct GetEffectiveOpeningHoursResponse {
+    pub timezone: String,
+    pub days: Vec<EffectiveOpeningDay>,
+}
+
+#[derive(Serialize)]
+pub struct PitchSearchHit {
+    pub id: Uuid,
+    pub display_name: String,
+    pub sport: Sport,
+    pub city: Option<String>,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+#[derive(Serialize)]
+pub struct BusinessSearchHit {
+    pub id: Uuid,
+    pub display_name: String,
+    pub pitch_count: i64,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+#[derive(Serialize)]
+pub struct AreaSearchHit {
+    pub city: String,
+    pub country: String,
+    pub pitch_count: i64,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+/// Results grouped by type, best match first in each group
+#[derive(Serialize)]
+pub struct SearchResponse {
+    pub pitches: Vec<PitchSearchHit>,
+    pub businesses: Vec<BusinessSearchHit>,
+    pub areas: Vec<AreaSearchHit>,
+}
 
 impl_into_response_with_json_body!(GetUserPersonalInfoResponse);
 impl_into_response_with_json_body!(LoginResponse);
 impl_into_response_with_json_body!(SignupResponse, StatusCode::CREATED);
 impl_into_response_with_no_body!(UpdateUserInfoResponse, StatusCode::NO_CONTENT);
-impl_into_response_with_json_body!(GetPitchesResponse);
+impl_into_response_with_json_body!(GetAmenitiesResponse);
+impl_into_response_with_json_body!(CreatePitchResponse, StatusCode::CREATED);
+impl_into_response_with_no_body!(UpdatePitchResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_no_body!(DeletePitchResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(GetPrivacySettingsResponse);
+impl_into_response_with_no_body!(UpdatePrivacySettingsResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(GetPlayerPublicProfileResponse);
+impl_into_response_with_no_body!(UpdateFavoritesResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(GetPitchFavoritesCountResponse);
+impl_into_response_with_json_body!(GetPitchClosuresResponse);
+impl_into_response_with_json_body!(CreateClosureResponse, StatusCode::CREATED);
+impl_into_response_with_no_body!(UpdateClosureResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_no_body!(DeleteClosureResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(CreateFacilityResponse, StatusCode::CREATED);
+impl_into_response_with_json_body!(GetFacilityResponse);
+impl_into_response_with_no_body!(UpdateFacilityResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_no_body!(DeleteFacilityResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(CreateTimeslotResponse, StatusCode::CREATED);
+impl_into_response_with_no_body!(UpdateTimeslotResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_no_body!(DeleteTimeslotResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(BulkTimeslotsResponse);
+impl_into_response_with_json_body!(ImportTimeslotsResponse);
+impl_into_response_with_json_body!(GetTimeslotRulesResponse);
+impl_into_response_with_json_body!(CreateTimeslotRuleResponse, StatusCode::CREATED);
+impl_into_response_with_no_body!(DeleteTimeslotRuleResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(GetPricingRulesResponse);
+impl_into_response_with_json_body!(CreatePricingRuleResponse, StatusCode::CREATED);
+impl_into_response_with_no_body!(DeletePricingRuleResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(GetPriceQuoteResponse);
+impl_into_response_with_json_body!(CreateBookingResponse, StatusCode::CREATED);
+impl_into_response_with_json_body!(CalendarFeedResponse);
+impl_into_response_with_no_body!(DeleteCalendarFeedResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(GetOpeningHoursResponse);
+impl_into_response_with_no_body!(SetOpeningHoursResponse, StatusCode::NO_CONTENT);
+impl_into_response_with_json_body!(GetEffectiveOpeningHoursResponse);
+impl_into_response_with_json_body!(SearchResponse);
@@ -0,0 +1,134 @@
+use std::{fmt, str::FromStr};
+
+/// A point on earth in degrees, written as `lat,lng` in query params
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Clone, Copy)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)).then_some(
+            Self {
+                latitude,
+                longitude,
+            },
+        )
+    }
+}
+
+impl FromStr for GeoPoint {
+    type Err = InvalidGeoFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [latitude, longitude] = parse_coordinates(s)?;
+        Self::new(latitude, longitude).ok_or(InvalidGeoFormat("coordinates out of range"))
+    }
+}
+
+/// Written as `min_lng,min_lat,max_lng,max_lat` in query params (the GeoJSON order).
+///
+/// Boxes crossing the antimeridian are not supported.
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Clone, Copy)]
+pub struct BoundingBox {
+    pub south_west: GeoPoint,
+    pub north_east: GeoPoint,
+}
+
+impl BoundingBox {
+    pub fn center(&self) -> GeoPoint {
+        GeoPoint {
+            latitude: (self.south_west.latitude + self.north_east.latitude) / 2.0,
+            longitude: (self.south_west.longitude + self.north_east.longitude) / 2.0,
+        }
+    }
+}
+
+impl FromStr for BoundingBox {
+    type Err = InvalidGeoFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [min_longitude, min_latitude, max_longitude, max_latitude] = parse_coordinates(s)?;
+        let south_west = GeoPoint::new(min_latitude, min_longitude)
+            .ok_or(InvalidGeoFormat("coordinates out of range"))?;
+        let north_east = GeoPoint::new(max_latitude, max_longitude)
+            .ok_or(InvalidGeoFormat("coordinates out of range"))?;
+
+        if south_west.latitude > north_east.latitude || south_west.longitude > north_east.longitude
+        {
+            return Err(InvalidGeoFormat(
+                "the south west corner must come before the north east one",
+            ));
+        }
+
+        Ok(Self {
+            south_west,
+            north_east,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidGeoFormat(&'static str);
+
+impl fmt::Display for InvalidGeoFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+fn parse_coordinates<const N: usize>(s: &str) -> Result<[f64; N], InvalidGeoFormat> {
+    let mut coordinates = [0.0_f64; N];
+    let mut parts = s.split(',');
+
+    for coordinate in &mut coordinates {
+        *coordinate = parts
+            .next()
+            .ok_or(InvalidGeoFormat("too few coordinates"))?
+            .trim()
+            .parse()
+            .map_err(|_| InvalidGeoFormat("coordinates must be numbers"))?;
+
+        if !coordinate.is_finite() {
+            return Err(InvalidGeoFormat("coordinates must be numbers"));
+        }
+    }
+
+    if parts.next().is_some() {
+        return Err(InvalidGeoFormat("too many coordinates"));
+    }
+
+    Ok(coordinates)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::geo::{BoundingBox, GeoPoint};
+
+    #[test]
+    fn test_parse_geo_point_ok() {
+        let point: GeoPoint = "25.3463, 55.4209".parse().unwrap();
+        assert_eq!(point, GeoPoint::new(25.3463, 55.4209).unwrap());
+    }
+
+    #[test]
+    fn test_parse_geo_point_err() {
+        for s in ["25.3", "25.3,55.4,1", "91,0", "0,181", "a,b", "NaN,0"] {
+            assert!(s.parse::<GeoPoint>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_parse_bounding_box_ok() {
+        let bbox: BoundingBox = "55,25,56,26".parse().unwrap();
+        assert_eq!(bbox.center(), GeoPoint::new(25.5, 55.5).unwrap());
+    }
+
+    #[test]
+    fn test_parse_bounding_box_err_corners_swapped() {
+        assert!("56,26,55,25".parse::<BoundingBox>().is_err());
+    }
+}
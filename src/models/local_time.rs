@@ -0,0 +1,11 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, Utc};
+
+/// Attaches the offset a timezone had at `utc`, given the wall clock time there.
+///
+/// The database knows the IANA timezones, so it computes `local` with `AT TIME ZONE`.
+pub fn with_local_offset(utc: DateTime<Utc>, local: NaiveDateTime) -> DateTime<FixedOffset> {
+    let offset_seconds = (local - utc.naive_utc()).num_seconds() as i32;
+    let offset = FixedOffset::east_opt(offset_seconds).unwrap_or_else(|| Utc.fix());
+
+    utc.with_timezone(&offset)
+}
@@ -2,7 +2,13 @@ use crate::configs::config;
 use sqlx::PgPool;
 
 pub mod api_schemas;
+pub mod geo;
+pub mod local_time;
+pub mod pagination;
+pub mod price;
 pub mod tables;
+pub mod time_of_day;
+pub mod time_window;
 
 #[derive(Clone)]
 pub struct ModelManager(PgPool);
@@ -0,0 +1,137 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Serialize, de::DeserializeOwned};
+
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+/// Rows that can be paginated by keyset.
+///
+/// The key must match the `ORDER BY` of the query, the next page starts right after the key of
+/// the last row of the current one, so rows inserted in between don't shift the pages.
+pub trait Keyset {
+    type Key: Serialize + DeserializeOwned;
+
+    fn key(&self) -> Self::Key;
+}
+
+pub struct PageRequest<K> {
+    pub limit: i64,
+    /// key of the last row of the previous page, `None` for the first page
+    pub after: Option<K>,
+    pub include_total: bool,
+}
+
+impl<K> PageRequest<K> {
+    #[cfg(test)]
+    pub fn first(limit: i64) -> Self {
+        Self {
+            limit,
+            after: None,
+            include_total: false,
+        }
+    }
+
+    /// One more row than the limit is fetched to know whether there is a next page
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit + 1
+    }
+}
+
+#[cfg_attr(test, derive(Debug))]
+pub struct Paged<T> {
+    pub items: Vec<T>,
+    /// `None` on the last page
+    pub next_cursor: Option<String>,
+    pub total: Option<i64>,
+}
+
+impl<T: Keyset> Paged<T> {
+    /// Builds a page from rows fetched with [`PageRequest::fetch_limit`]
+    pub fn new(mut rows: Vec<T>, limit: i64, total: Option<i64>) -> Self {
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+
+        let next_cursor = match rows.last() {
+            Some(last) if has_more => Some(encode_cursor(&last.key())),
+            _ => None,
+        };
+
+        Self {
+            items: rows,
+            next_cursor,
+            total,
+        }
+    }
+}
+
+impl<T> Paged<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Paged<U> {
+        Paged {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            total: self.total,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidCursor;
+
+/// Cursors are opaque to clients, they are the keyset as base64url encoded JSON
+pub fn encode_cursor<K: Serialize>(key: &K) -> String {
+    let json = serde_json::to_vec(key).expect("keysets are serializable");
+    URL_SAFE_NO_PAD.encode(json)
+}
+
+pub fn decode_cursor<K: DeserializeOwned>(cursor: &str) -> Result<K, InvalidCursor> {
+    let json = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| InvalidCursor)?;
+    serde_json::from_slice(&json).map_err(|_| InvalidCursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    struct Row(i32);
+
+    impl Keyset for Row {
+        type Key = i32;
+
+        fn key(&self) -> Self::Key {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let key = (Some(4.07), Uuid::nil());
+
+        let cursor = encode_cursor(&key);
+
+        assert_eq!(decode_cursor::<(Option<f64>, Uuid)>(&cursor).unwrap(), key);
+    }
+
+    #[test]
+    fn test_decode_cursor_err_invalid() {
+        assert!(decode_cursor::<(Option<f64>, Uuid)>("not a cursor").is_err());
+        assert!(decode_cursor::<(Option<f64>, Uuid)>(&encode_cursor(&"text")).is_err());
+    }
+
+    #[test]
+    fn test_paged_new_has_more() {
+        let page = Paged::new(vec![Row(1), Row(2), Row(3)], 2, None);
+
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.next_cursor, Some(encode_cursor(&2)));
+    }
+
+    #[test]
+    fn test_paged_new_last_page() {
+        let page = Paged::new(vec![Row(1), Row(2)], 2, Some(2));
+
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.next_cursor, None);
+        assert_eq!(page.total, Some(2));
+    }
+}
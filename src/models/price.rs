@@ -0,0 +1,394 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Decode, Encode, Postgres, Type,
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef},
+};
+use std::{error::Error, fmt, str::FromStr};
+
+/// Active ISO 4217 currencies with the number of digits of their minor unit
+#[rustfmt::skip]
+const CURRENCIES: &[(&str, u8)] = &[
+    ("AED", 2), ("AFN", 2), ("ALL", 2), ("AMD", 2), ("ANG", 2), ("AOA", 2), ("ARS", 2),
+    ("AUD", 2), ("AWG", 2), ("AZN", 2), ("BAM", 2), ("BBD", 2), ("BDT", 2), ("BGN", 2),
+    ("BHD", 3), ("BIF", 0), ("BMD", 2), ("BND", 2), ("BOB", 2), ("BRL", 2), ("BSD", 2),
+    ("BTN", 2), ("BWP", 2), ("BYN", 2), ("BZD", 2), ("CAD", 2), ("CDF", 2), ("CHF", 2),
+    ("CLP", 0), ("CNY", 2), ("COP", 2), ("CRC", 2), ("CUP", 2), ("CVE", 2), ("CZK", 2),
+    ("DJF", 0), ("DKK", 2), ("DOP", 2), ("DZD", 2), ("EGP", 2), ("ERN", 2), ("ETB", 2),
+    ("EUR", 2), ("FJD", 2), ("FKP", 2), ("GBP", 2), ("GEL", 2), ("GHS", 2), ("GIP", 2),
+    ("GMD", 2), ("GNF", 0), ("GTQ", 2), ("GYD", 2), ("HKD", 2), ("HNL", 2), ("HTG", 2),
+    ("HUF", 2), ("IDR", 2), ("ILS", 2), ("INR", 2), ("IQD", 3), ("IRR", 2), ("ISK", 0),
+    ("JMD", 2), ("JOD", 3), ("JPY", 0), ("KES", 2), ("KGS", 2), ("KHR", 2), ("KMF", 0),
+    ("KPW", 2), ("KRW", 0), ("KWD", 3), ("KYD", 2), ("KZT", 2), ("LAK", 2), ("LBP", 2),
+    ("LKR", 2), ("LRD", 2), ("LSL", 2), ("LYD", 3), ("MAD", 2), ("MDL", 2), ("MGA", 2),
+    ("MKD", 2), ("MMK", 2), ("MNT", 2), ("MOP", 2), ("MRU", 2), ("MUR", 2), ("MVR", 2),
+    ("MWK", 2), ("MXN", 2), ("MYR", 2), ("MZN", 2), ("NAD", 2), ("NGN", 2), ("NIO", 2),
+    ("NOK", 2), ("NPR", 2), ("NZD", 2), ("OMR", 3), ("PAB", 2), ("PEN", 2), ("PGK", 2),
+    ("PHP", 2), ("PKR", 2), ("PLN", 2), ("PYG", 0), ("QAR", 2), ("RON", 2), ("RSD", 2),
+    ("RUB", 2), ("RWF", 0), ("SAR", 2), ("SBD", 2), ("SCR", 2), ("SDG", 2), ("SEK", 2),
+    ("SGD", 2), ("SHP", 2), ("SLE", 2), ("SOS", 2), ("SRD", 2), ("SSP", 2), ("STN", 2),
+    ("SVC", 2), ("SYP", 2), ("SZL", 2), ("THB", 2), ("TJS", 2), ("TMT", 2), ("TND", 3),
+    ("TOP", 2), ("TRY", 2), ("TTD", 2), ("TWD", 2), ("TZS", 2), ("UAH", 2), ("UGX", 0),
+    ("USD", 2), ("UYU", 2), ("UZS", 2), ("VES", 2), ("VND", 0), ("VUV", 0), ("WST", 2),
+    ("XAF", 0), ("XCD", 2), ("XOF", 0), ("XPF", 0), ("YER", 2), ("ZAR", 2), ("ZMW", 2),
+    ("ZWG", 2),
+];
+
+/// An ISO 4217 currency code like `AED`, only active codes are accepted.
+///
+/// Stored in postgres as `CHAR(3)`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    pub fn code(&self) -> &str {
+        // only built from the ASCII codes of `CURRENCIES`
+        std::str::from_utf8(&self.0).expect("currency codes are ASCII")
+    }
+
+    /// Digits after the decimal point, e.g. 2 for `AED` (fils) and 0 for `JPY`
+    pub fn minor_unit_digits(&self) -> u8 {
+        CURRENCIES
+            .iter()
+            .find(|(code, _)| code.as_bytes() == self.0)
+            .map(|(_, digits)| *digits)
+            .expect("currencies are only built from `CURRENCIES`")
+    }
+}
+
+impl FromStr for Currency {
+    type Err = InvalidCurrency;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CURRENCIES
+            .iter()
+            .find(|(code, _)| *code == s)
+            .map(|(code, _)| {
+                let code = code.as_bytes();
+                Self([code[0], code[1], code[2]])
+            })
+            .ok_or(InvalidCurrency)
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Type<Postgres> for Currency {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("bpchar")
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for Currency {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<Postgres>>::encode(self.code(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Currency {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(<&str as Decode<Postgres>>::decode(value)?.parse()?)
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidCurrency;
+
+impl Error for InvalidCurrency {}
+
+impl fmt::Display for InvalidCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected an ISO 4217 currency code like `AED`")
+    }
+}
+
+/// An amount of money in the minor unit of its currency, e.g. `{"amountCents": 12050,
+/// "currency": "AED"}` for 120.50 AED.
+///
+/// Stored in postgres as the composite type `price`. Amounts of different currencies are never
+/// mixed, the arithmetic fails instead.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[sqlx(type_name = "price")]
+pub struct Price {
+    amount_cents: i64,
+    currency: Currency,
+}
+
+impl Price {
+    pub fn new(amount_cents: i64, currency: Currency) -> Self {
+        Self {
+            amount_cents,
+            currency,
+        }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    pub fn amount_cents(&self) -> i64 {
+        self.amount_cents
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.amount_cents < 0
+    }
+
+    pub fn checked_add(self, other: Self) -> Result<Self, PriceError> {
+        self.check_currency(other)?;
+        self.with_amount(self.amount_cents.checked_add(other.amount_cents))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Result<Self, PriceError> {
+        self.check_currency(other)?;
+        self.with_amount(self.amount_cents.checked_sub(other.amount_cents))
+    }
+
+    /// Multiplies by `numerator / denominator`, rounding half away from zero, e.g. to pro-rate an
+    /// hourly price over some minutes or to apply a percentage.
+    pub fn checked_mul_ratio(self, numerator: i64, denominator: i64) -> Result<Self, PriceError> {
+        if denominator == 0 {
+            return Err(PriceError::Overflow);
+        }
+        let product = i128::from(self.amount_cents) * i128::from(numerator);
+        let denominator = i128::from(denominator);
+        let quotient = product / denominator;
+        let remainder = product % denominator;
+        let rounded = if 2 * remainder.abs() >= denominator.abs() {
+            quotient + (product.signum() * denominator.signum())
+        } else {
+            quotient
+        };
+        self.with_amount(i64::try_from(rounded).ok())
+    }
+
+    fn check_currency(&self, other: Self) -> Result<(), PriceError> {
+        if self.currency != other.currency {
+            return Err(PriceError::CurrencyMismatch);
+        }
+        Ok(())
+    }
+
+    fn with_amount(self, amount_cents: Option<i64>) -> Result<Self, PriceError> {
+        let amount_cents = amount_cents.ok_or(PriceError::Overflow)?;
+        Ok(Self::new(amount_cents, self.currency))
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = u32::from(self.currency.minor_unit_digits());
+        let sign = if self.is_negative() { "-" } else { "" };
+        let amount = self.amount_cents.unsigned_abs();
+        if digits == 0 {
+            return write!(f, "{sign}{amount} {}", self.currency);
+        }
+        let scale = 10_u64.pow(digits);
+        write!(
+            f,
+            "{sign}{}.{:0width$} {}",
+            amount / scale,
+            amount % scale,
+            self.currency,
+            width = digits as usize
+        )
+    }
+}
+
+/// The inverse of `Display`, e.g. `120.50 AED`, the fraction may be shorter or left out
+impl FromStr for Price {
+    type Err = InvalidPrice;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (amount, currency) = s.trim().split_once(' ').ok_or(InvalidPrice)?;
+        let currency: Currency = currency.trim().parse().map_err(|_| InvalidPrice)?;
+        let (is_negative, amount) = match amount.strip_prefix('-') {
+            Some(amount) => (true, amount),
+            None => (false, amount),
+        };
+        let (units, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+        let digits = usize::from(currency.minor_unit_digits());
+        let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+        if units.is_empty() || !is_digits(units) || !is_digits(fraction) || fraction.len() > digits
+        {
+            return Err(InvalidPrice);
+        }
+
+        let amount_cents = format!("{units}{fraction:0<digits$}")
+            .parse::<i64>()
+            .map_err(|_| InvalidPrice)?;
+        let amount_cents = if is_negative {
+            -amount_cents
+        } else {
+            amount_cents
+        };
+
+        Ok(Self::new(amount_cents, currency))
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidPrice;
+
+impl Error for InvalidPrice {}
+
+impl fmt::Display for InvalidPrice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected an amount and a currency code like `120.50 AED`")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PriceError {
+    CurrencyMismatch,
+    Overflow,
+}
+
+impl Error for PriceError {}
+
+impl fmt::Display for PriceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CurrencyMismatch => f.write_str("prices have different currencies"),
+            Self::Overflow => f.write_str("price is out of range"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currency_from_str() {
+        let aed: Currency = "AED".parse().unwrap();
+
+        assert_eq!(aed.to_string(), "AED");
+        assert_eq!(aed.minor_unit_digits(), 2);
+        assert_eq!("KWD".parse::<Currency>().unwrap().minor_unit_digits(), 3);
+        assert!("aed".parse::<Currency>().is_err());
+        assert!("XYZ".parse::<Currency>().is_err());
+        assert!("AEDD".parse::<Currency>().is_err());
+    }
+
+    #[test]
+    fn test_price_arithmetic() {
+        let aed: Currency = "AED".parse().unwrap();
+        let usd: Currency = "USD".parse().unwrap();
+        let price = Price::new(15_000, aed);
+
+        assert_eq!(
+            price.checked_add(Price::new(50, aed)),
+            Ok(Price::new(15_050, aed))
+        );
+        assert_eq!(
+            price.checked_sub(Price::new(20_000, aed)),
+            Ok(Price::new(-5_000, aed))
+        );
+        assert_eq!(price.checked_mul_ratio(3, 1), Ok(Price::new(45_000, aed)));
+        assert_eq!(
+            price.checked_add(Price::new(1, usd)),
+            Err(PriceError::CurrencyMismatch)
+        );
+        assert_eq!(
+            Price::new(i64::MAX, aed).checked_add(Price::new(1, aed)),
+            Err(PriceError::Overflow)
+        );
+        assert_eq!(
+            price.checked_mul_ratio(i64::MAX, 1),
+            Err(PriceError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_price_mul_ratio_rounds_half_away_from_zero() {
+        let aed: Currency = "AED".parse().unwrap();
+
+        // 90 minutes of 150.00 per hour
+        assert_eq!(
+            Price::new(15_000, aed).checked_mul_ratio(90, 60),
+            Ok(Price::new(22_500, aed))
+        );
+        assert_eq!(
+            Price::new(5, aed).checked_mul_ratio(1, 2),
+            Ok(Price::new(3, aed))
+        );
+        assert_eq!(
+            Price::new(-5, aed).checked_mul_ratio(1, 2),
+            Ok(Price::new(-3, aed))
+        );
+        assert_eq!(
+            Price::new(10, aed).checked_mul_ratio(1, 3),
+            Ok(Price::new(3, aed))
+        );
+        assert_eq!(
+            Price::new(10, aed).checked_mul_ratio(1, 0),
+            Err(PriceError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_price_from_str() {
+        let aed: Currency = "AED".parse().unwrap();
+        let jpy: Currency = "JPY".parse().unwrap();
+
+        assert_eq!(
+            "120.50 AED".parse::<Price>().unwrap(),
+            Price::new(12_050, aed)
+        );
+        assert_eq!(
+            "120.5 AED".parse::<Price>().unwrap(),
+            Price::new(12_050, aed)
+        );
+        assert_eq!("120 AED".parse::<Price>().unwrap(), Price::new(12_000, aed));
+        assert_eq!("-0.05 AED".parse::<Price>().unwrap(), Price::new(-5, aed));
+        assert_eq!("1500 JPY".parse::<Price>().unwrap(), Price::new(1_500, jpy));
+        assert!("120.505 AED".parse::<Price>().is_err());
+        assert!("1.5 JPY".parse::<Price>().is_err());
+        assert!("120,50 AED".parse::<Price>().is_err());
+        assert!(".50 AED".parse::<Price>().is_err());
+        assert!("120.50".parse::<Price>().is_err());
+        assert!("99999999999999999999 AED".parse::<Price>().is_err());
+    }
+
+    #[test]
+    fn test_price_serde() {
+        let price: Price =
+            serde_json::from_str(r#"{"amountCents":12050,"currency":"AED"}"#).unwrap();
+
+        assert_eq!(price.to_string(), "120.50 AED");
+        assert_eq!(
+            serde_json::to_string(&price).unwrap(),
+            r#"{"amountCents":12050,"currency":"AED"}"#
+        );
+        assert!(serde_json::from_str::<Price>(r#"{"amountCents":1,"currency":"ABC"}"#).is_err());
+        assert!(serde_json::from_str::<Price>(r#"{"amountCents":1.5,"currency":"AED"}"#).is_err());
+    }
+}
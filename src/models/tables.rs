@@ -1,5 +1,10 @@
-use serde::{Deserialize, Serialize};
+use crate::models::price::Currency;
+use serde::{
+    Deserialize, Serialize,
+    de::{IntoDeserializer, value},
+};
 use sqlx::FromRow;
+use std::str::FromStr;
 use uuid::Uuid;
 
 #[cfg_attr(test, derive(Debug, PartialEq))]
@@ -12,8 +17,8 @@ pub enum UserRole {
     Admin,
 }
 
-#[cfg_attr(test, derive(Debug, PartialEq))]
-#[derive(Serialize, Deserialize, sqlx::Type)]
+#[cfg_attr(test, derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "sport", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub enum Sport {
@@ -22,6 +27,118 @@ pub enum Sport {
     Basketball,
 }
 
+/// Parses the serde name, for comma separated lists in query params
+impl FromStr for Sport {
+    type Err = value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::deserialize(s.into_deserializer())
+    }
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Clone, Copy, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "privacy_level", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum PrivacyLevel {
+    Public,
+    FriendsOnly,
+    Hidden,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Clone, Copy, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "surface_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum SurfaceType {
+    Grass,
+    ArtificialTurf,
+    Hardcourt,
+    Clay,
+    Wood,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Clone, Copy, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "pitch_environment", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum PitchEnvironment {
+    Indoor,
+    Outdoor,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "pitch_admin_action", rename_all = "snake_case")]
+pub enum PitchAdminAction {
+    Create,
+    Update,
+    Hide,
+    Unhide,
+    Delete,
+    Publish,
+    Archive,
+    Restore,
+}
+
+/// Only published pitches are listed to players and guests, and only their timeslots can be
+/// booked
+#[cfg_attr(test, derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "pitch_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum PitchStatus {
+    Draft,
+    Published,
+    Hidden,
+    Archived,
+}
+
+impl PitchStatus {
+    /// Drafts are published once, after that a pitch can only be hidden, or archived and
+    /// restored as a draft
+    pub fn can_change_to(self, status: Self) -> bool {
+        matches!(
+            (self, status),
+            (Self::Draft, Self::Published)
+                | (Self::Published, Self::Hidden)
+                | (Self::Hidden, Self::Published)
+                | (Self::Draft | Self::Published | Self::Hidden, Self::Archived)
+                | (Self::Archived, Self::Draft)
+        )
+    }
+}
+
+/// What a part of a pitch's day is taken by, the kinds are listed from the strongest one
+#[cfg_attr(test, derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, sqlx::Type, Serialize)]
+#[sqlx(type_name = "availability_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AvailabilityKind {
+    /// outside the opening hours or during a confirmed closure
+    Closed,
+    Booked,
+    /// taken up by a booked timeslot of another pitch sharing one of its units
+    Held,
+    /// an upcoming timeslot that can be booked
+    Free,
+}
+
+/// Opening hours repeat every week on these days
+#[cfg_attr(test, derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "weekday", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
 #[cfg(test)]
 #[derive(FromRow)]
 pub struct User {
@@ -43,6 +160,22 @@ pub struct PlayerProfile {
     pub last_name: String,
     // TODO: make sure this is a set (ie items are unique) in the DB
     pub preferred_sports: Vec<Sport>,
+    /// stored in `player_sport_ratings`, so it's fetched separately
+    #[sqlx(skip)]
+    #[serde(skip_deserializing)]
+    pub sport_ratings: Vec<PlayerSportRating>,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(FromRow, Serialize)]
+pub struct PlayerSportRating {
+    pub sport: Sport,
+    /// between 1 and 10
+    pub self_declared_level: Option<i16>,
+    /// computed from the player's play history, `None` until the first rated game
+    pub system_rating: Option<f32>,
+    /// between 0 and 1
+    pub confidence: f32,
 }
 
 #[cfg_attr(test, derive(Debug, PartialEq))]
@@ -52,10 +185,36 @@ pub struct BusinessProfile {
     pub display_name: String,
 }
 
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(FromRow, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlayerPrivacySettings {
+    pub name_visibility: PrivacyLevel,
+    pub member_since_visibility: PrivacyLevel,
+    pub total_bookings_visibility: PrivacyLevel,
+    pub favorite_sport_visibility: PrivacyLevel,
+}
+
 #[derive(FromRow, Serialize)]
 pub struct Pitch {
     pub id: Uuid,
     pub owner_id: Uuid,
     pub display_name: String,
     pub sport: Sport,
+    /// the pitch can also be played for these sports, `sport` stays the main one
+    pub other_sports: Vec<Sport>,
+    /// set when the pitch is a unit of a facility
+    pub facility_id: Option<Uuid>,
+    pub price_per_hour_cents: i32,
+    /// prices of the pitch and of its timeslots are all in this currency
+    pub currency: Currency,
+    pub image_url: Option<String>,
+    pub surface: Option<SurfaceType>,
+    pub environment: Option<PitchEnvironment>,
+    pub description: Option<String>,
+    pub max_players: Option<i16>,
+    /// pitches that aren't published are only listed for their owner and for admins
+    pub status: PitchStatus,
+    /// IANA name, timeslot rules, pricing rules and opening hours are wall clock times there
+    pub timezone: String,
 }
@@ -0,0 +1,110 @@
+use sqlx::{
+    Decode, Encode, Postgres, Type,
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef},
+};
+use std::{error::Error, fmt, str::FromStr};
+
+/// A wall clock time `HH:MM`, from `00:00` to `24:00` so that an interval can end at midnight.
+///
+/// Bound to postgres as text, cast it to `TIME` (which accepts `24:00` unlike `NaiveTime`) and read
+/// it back with `TO_CHAR(.., 'HH24:MI')`.
+#[cfg_attr(test, derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeOfDay {
+    minutes: u16,
+}
+
+impl TimeOfDay {
+    const MIDNIGHT: u16 = 24 * 60;
+
+    /// Minutes since midnight
+    pub fn minutes(self) -> u16 {
+        self.minutes
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = InvalidTimeOfDay;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hours, minutes) = s.split_once(':').ok_or(InvalidTimeOfDay)?;
+        if hours.len() != 2 || minutes.len() != 2 {
+            return Err(InvalidTimeOfDay);
+        }
+        let hours: u16 = hours.parse().map_err(|_| InvalidTimeOfDay)?;
+        let minutes: u16 = minutes.parse().map_err(|_| InvalidTimeOfDay)?;
+
+        if hours > 24 || minutes >= 60 || hours * 60 + minutes > Self::MIDNIGHT {
+            return Err(InvalidTimeOfDay);
+        }
+
+        Ok(Self {
+            minutes: hours * 60 + minutes,
+        })
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.minutes / 60, self.minutes % 60)
+    }
+}
+
+impl Type<Postgres> for TimeOfDay {
+    fn type_info() -> PgTypeInfo {
+        <&str as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for TimeOfDay {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <String as Encode<Postgres>>::encode(self.to_string(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for TimeOfDay {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(<&str as Decode<Postgres>>::decode(value)?.parse()?)
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidTimeOfDay;
+
+impl Error for InvalidTimeOfDay {}
+
+impl fmt::Display for InvalidTimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected a time between `00:00` and `24:00`")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_of_day_from_str_ok() {
+        let time: TimeOfDay = "08:30".parse().unwrap();
+        let midnight: TimeOfDay = "24:00".parse().unwrap();
+
+        assert_eq!(time.to_string(), "08:30");
+        assert_eq!(midnight.to_string(), "24:00");
+        assert!(time < midnight);
+    }
+
+    #[test]
+    fn test_time_of_day_from_str_err() {
+        assert!("8:30".parse::<TimeOfDay>().is_err());
+        assert!("08:60".parse::<TimeOfDay>().is_err());
+        assert!("24:30".parse::<TimeOfDay>().is_err());
+        assert!("25:00".parse::<TimeOfDay>().is_err());
+        assert!("08-30".parse::<TimeOfDay>().is_err());
+    }
+}
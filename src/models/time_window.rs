@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use std::{fmt, str::FromStr};
+
+/// A half-open `[start, end)` window, written as two RFC 3339 timestamps `start,end` in query
+/// params
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Clone, Copy)]
+pub struct TimeWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl FromStr for TimeWindow {
+    type Err = InvalidTimeWindow;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once(',')
+            .ok_or(InvalidTimeWindow("expected `start,end`"))?;
+        let parse = |timestamp: &str| {
+            DateTime::parse_from_rfc3339(timestamp.trim())
+                .map(|timestamp| timestamp.to_utc())
+                .map_err(|_| InvalidTimeWindow("timestamps must be RFC 3339"))
+        };
+        let (start, end) = (parse(start)?, parse(end)?);
+
+        if start >= end {
+            return Err(InvalidTimeWindow("the start must come before the end"));
+        }
+
+        Ok(Self { start, end })
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidTimeWindow(&'static str);
+
+impl fmt::Display for InvalidTimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_window_from_str_ok() {
+        let window: TimeWindow = "2025-03-30T17:00:00+04:00,2025-03-30T15:00:00Z"
+            .parse()
+            .unwrap();
+
+        assert_eq!(window.start.to_rfc3339(), "2025-03-30T13:00:00+00:00");
+        assert_eq!(window.end.to_rfc3339(), "2025-03-30T15:00:00+00:00");
+    }
+
+    #[test]
+    fn test_time_window_from_str_err() {
+        assert!("2025-03-30T13:00:00Z".parse::<TimeWindow>().is_err());
+        assert!("2025-03-30,2025-03-31".parse::<TimeWindow>().is_err());
+        assert!(
+            "2025-03-30T15:00:00Z,2025-03-30T13:00:00Z"
+                .parse::<TimeWindow>()
+                .is_err()
+        );
+    }
+}
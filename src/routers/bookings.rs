@@ -0,0 +1,33 @@
+use crate::{
+    controllers::BookingController,
+    errors::ServerError,
+    extractors::AuthToken,
+    middlewares,
+    models::{
+        ModelManager,
+        api_schemas::{requests::CreateBookingPayload, responses::CreateBookingResponse},
+    },
+};
+use axum::{Json, Router, extract::State, middleware, routing::post};
+
+pub fn get_router() -> Router<ModelManager> {
+    Router::new().route(
+        "/",
+        post(create_booking).route_layer(middleware::from_fn(middlewares::authenticate_player)),
+    )
+}
+
+async fn create_booking(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<CreateBookingPayload>,
+) -> Result<CreateBookingResponse, ServerError> {
+    let booking =
+        BookingController::book_timeslot(&model_manager, auth_token.user_id, payload.timeslot_id)
+            .await?;
+
+    Ok(CreateBookingResponse {
+        booking_id: booking.id,
+        price: booking.price,
+    })
+}
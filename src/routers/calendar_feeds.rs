@@ -0,0 +1,39 @@
+use crate::{
+    controllers::CalendarFeedController,
+    errors::ServerError,
+    models::{
+        ModelManager,
+        api_schemas::responses::{CalendarFeedResponse, GetCalendarResponse},
+    },
+};
+use axum::{
+    Router,
+    extract::{Path, State},
+    routing::get,
+};
+use chrono::Utc;
+
+/// Calendar apps subscribe without credentials, so the token in the path is the only secret
+pub fn get_router() -> Router<ModelManager> {
+    Router::new().route("/{file}", get(get_calendar))
+}
+
+pub(super) fn into_calendar_feed_response(token: String) -> CalendarFeedResponse {
+    CalendarFeedResponse {
+        path: format!("/calendar-feeds/{token}.ics"),
+        token,
+    }
+}
+
+async fn get_calendar(
+    Path(file): Path<String>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetCalendarResponse, ServerError> {
+    let token = file.strip_suffix(".ics").unwrap_or(&file);
+
+    let calendar = CalendarFeedController::get_calendar(&model_manager, token).await?;
+
+    Ok(GetCalendarResponse {
+        ics: calendar.to_ics(Utc::now()),
+    })
+}
@@ -0,0 +1,123 @@
+use crate::{
+    controllers::{FacilityController, PitchActor},
+    errors::ServerError,
+    extractors::AuthToken,
+    models::{
+        ModelManager,
+        api_schemas::{
+            requests::{CreateFacilityPayload, SetFacilityUnitPayload},
+            responses::{
+                CreateFacilityResponse, DeleteFacilityResponse, FacilityUnit, GetFacilityResponse,
+                UpdateFacilityResponse,
+            },
+        },
+        tables::PitchStatus,
+    },
+    routers::pitches::get_pitch_actor,
+};
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::{get, post, put},
+};
+use uuid::Uuid;
+
+pub fn get_router() -> Router<ModelManager> {
+    Router::new()
+        .route("/", post(create_facility))
+        .route("/{facility_id}", get(get_facility).delete(delete_facility))
+        .route(
+            "/{facility_id}/units/{pitch_id}",
+            put(set_facility_unit).delete(remove_facility_unit),
+        )
+}
+
+async fn create_facility(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<CreateFacilityPayload>,
+) -> Result<CreateFacilityResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+    let owner_id = match actor {
+        PitchActor::Owner(owner_id) => payload.owner_id.unwrap_or(owner_id),
+        PitchActor::Admin(_) => payload.owner_id.ok_or(ServerError::PitchOwnerIsRequired)?,
+    };
+
+    let facility_id =
+        FacilityController::create_facility(&model_manager, actor, owner_id, payload.name).await?;
+
+    Ok(CreateFacilityResponse { facility_id })
+}
+
+async fn get_facility(
+    auth_token: Option<AuthToken>,
+    Path(facility_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetFacilityResponse, ServerError> {
+    let viewer = auth_token.and_then(|auth_token| get_pitch_actor(&auth_token).ok());
+
+    let facility =
+        FacilityController::get_facility_by_id(&model_manager, facility_id, viewer).await?;
+
+    Ok(GetFacilityResponse {
+        id: facility.id,
+        owner_id: facility.owner_id,
+        name: facility.display_name,
+        units: facility
+            .units
+            .into_iter()
+            .map(|unit| FacilityUnit {
+                id: unit.id,
+                name: unit.display_name,
+                sport: unit.sport,
+                other_sports: unit.other_sports,
+                status: (unit.status != PitchStatus::Published).then_some(unit.status),
+                component_ids: unit.component_ids,
+            })
+            .collect(),
+    })
+}
+
+async fn delete_facility(
+    auth_token: AuthToken,
+    Path(facility_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<DeleteFacilityResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    FacilityController::delete_facility_by_id(&model_manager, facility_id, actor).await?;
+
+    Ok(DeleteFacilityResponse)
+}
+
+async fn set_facility_unit(
+    auth_token: AuthToken,
+    Path((facility_id, pitch_id)): Path<(Uuid, Uuid)>,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<SetFacilityUnitPayload>,
+) -> Result<UpdateFacilityResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    FacilityController::set_unit(
+        &model_manager,
+        facility_id,
+        pitch_id,
+        actor,
+        payload.component_ids,
+    )
+    .await?;
+
+    Ok(UpdateFacilityResponse)
+}
+
+async fn remove_facility_unit(
+    auth_token: AuthToken,
+    Path((facility_id, pitch_id)): Path<(Uuid, Uuid)>,
+    State(model_manager): State<ModelManager>,
+) -> Result<UpdateFacilityResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    FacilityController::remove_unit(&model_manager, facility_id, pitch_id, actor).await?;
+
+    Ok(UpdateFacilityResponse)
+}
@@ -1,3 +1,9 @@
 pub mod auth;
+pub mod bookings;
+pub mod calendar_feeds;
+pub mod facilities;
 pub mod pitches;
+pub mod players;
+pub mod search;
+pub mod timeslots;
 pub mod users;
@@ -1,69 +1,1053 @@
 use crate::{
-    controllers::PitchController,
+    controllers::{
+        AvailabilityController, CalendarFeedController, ClosureController, FavoriteController,
+        FeedScope, InsertClosurePayload, InsertPitchPayload, InsertPricingRulePayload,
+        InsertTimeslotRulePayload, LocationPayload, OpeningHours, OpeningHoursController,
+        OpeningHoursException, PitchActor, PitchController, PitchFilters, PitchSort,
+        PriceAdjustment, PricingRuleController, TimeslotController, TimeslotFilters,
+        TimeslotRuleController, UpdatePitchPayload as ControllerUpdatePitchPayload,
+        WeeklyOpeningHours,
+    },
     errors::ServerError,
-    extractors::AuthToken,
+    extractors::{AuthToken, Paginated},
+    middlewares,
     models::{
         ModelManager,
-        api_schemas::responses::{GetPitchesResponse, Pitch},
-        tables::UserRole,
+        api_schemas::{
+            query_params::{
+                GetAvailabilityQuery, GetEffectiveOpeningHoursQuery, GetPitchTimeslotsQuery,
+                GetPitchesQuery, GetPriceQuoteQuery, PitchSortQuery,
+            },
+            requests::{
+                CreateClosurePayload, CreatePitchPayload, CreatePricingRulePayload,
+                CreateTimeslotRulePayload, PitchLocationPayload, SetOpeningHoursPayload,
+                SetPitchStatusPayload, UpdatePitchPayload,
+            },
+            responses::{
+                AffectedBooking, AvailabilityDay, AvailabilityInterval, CalendarFeedResponse,
+                CreateClosureResponse, CreatePitchResponse, CreatePricingRuleResponse,
+                CreateTimeslotRuleResponse, DeleteCalendarFeedResponse, DeleteClosureResponse,
+                DeletePitchResponse, DeletePricingRuleResponse, DeleteTimeslotRuleResponse,
+                EffectiveOpeningDay, GetAmenitiesResponse, GetAvailabilityResponse,
+                GetEffectiveOpeningHoursResponse, GetOpeningHoursResponse,
+                GetPitchClosuresResponse, GetPitchFavoritesCountResponse, GetPitchesResponse,
+                GetPriceQuoteResponse, GetPricingRulesResponse, GetTimeslotRulesResponse,
+                GetTimeslotsResponse, OpeningInterval, Pitch, PitchAvailability, PitchClosure,
+                PitchClosureNotice, PitchOpeningHoursException, PitchRelevance, PitchWeeklyHours,
+                PriceQuoteSegment, PricingRule, SetOpeningHoursResponse, TimeslotRule,
+                UpdateClosureResponse, UpdatePitchResponse,
+            },
+        },
+        local_time::with_local_offset,
+        price::Price,
+        tables::{PitchStatus, UserRole},
     },
+    routers::{calendar_feeds::into_calendar_feed_response, timeslots::into_timeslot_response},
+};
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State, rejection::QueryRejection},
+    http::{HeaderMap, header},
+    middleware,
+    routing::{delete, get, patch, put},
 };
-use axum::{Router, extract::State, routing::get};
+use chrono::{Duration, Utc};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+const MAX_CLOSURE_REASON_CHARS: usize = 256;
+const MAX_OPENING_HOURS_REASON_CHARS: usize = 256;
+const MAX_EFFECTIVE_OPENING_HOURS_DAYS: i64 = 92;
+const MAX_AVAILABILITY_DAYS: i64 = 92;
+const MAX_PRICING_RULE_NAME_CHARS: usize = 64;
+const MAX_ADJUSTMENT_PERCENT: i32 = 1000;
+const MAX_PRICE_QUOTE_DAYS: i64 = 7;
 
 pub fn get_router() -> Router<ModelManager> {
-    Router::new().route("/", get(get_pitches))
+    Router::new()
+        .route("/", get(get_pitches).post(create_pitch))
+        .route("/amenities", get(get_amenities))
+        .route("/{pitch_id}", patch(update_pitch).delete(delete_pitch))
+        .route(
+            "/{pitch_id}/hidden",
+            put(hide_pitch)
+                .delete(unhide_pitch)
+                .route_layer(middleware::from_fn(middlewares::authenticate_admin)),
+        )
+        .route("/{pitch_id}/status", put(set_pitch_status))
+        .route(
+            "/{pitch_id}/closures",
+            get(get_closures).post(create_closure),
+        )
+        .route("/{pitch_id}/closures/{closure_id}", delete(delete_closure))
+        .route(
+            "/{pitch_id}/closures/{closure_id}/confirmed",
+            put(confirm_closure),
+        )
+        .route(
+            "/{pitch_id}/opening-hours",
+            get(get_opening_hours).put(set_opening_hours),
+        )
+        .route(
+            "/{pitch_id}/opening-hours/effective",
+            get(get_effective_opening_hours),
+        )
+        .route("/{pitch_id}/availability", get(get_availability))
+        .route("/{pitch_id}/timeslots", get(get_pitch_timeslots))
+        .route(
+            "/{pitch_id}/timeslot-rules",
+            get(get_timeslot_rules).post(create_timeslot_rule),
+        )
+        .route(
+            "/{pitch_id}/timeslot-rules/{rule_id}",
+            delete(delete_timeslot_rule),
+        )
+        .route(
+            "/{pitch_id}/pricing-rules",
+            get(get_pricing_rules).post(create_pricing_rule),
+        )
+        .route(
+            "/{pitch_id}/pricing-rules/{rule_id}",
+            delete(delete_pricing_rule),
+        )
+        .route("/{pitch_id}/price-quote", get(get_price_quote))
+        .route("/{pitch_id}/favorites/count", get(get_favorites_count))
+        .route(
+            "/{pitch_id}/calendar-feed",
+            get(get_calendar_feed)
+                .put(regenerate_calendar_feed)
+                .delete(delete_calendar_feed),
+        )
+}
+
+pub(super) fn get_pitch_actor(auth_token: &AuthToken) -> Result<PitchActor, ServerError> {
+    match auth_token.user_role {
+        UserRole::Business => Ok(PitchActor::Owner(auth_token.user_id)),
+        UserRole::Admin => Ok(PitchActor::Admin(auth_token.user_id)),
+        UserRole::Player => Err(ServerError::UserIsNotBusiness),
+    }
 }
 
 async fn get_pitches(
     auth_token: Option<AuthToken>,
+    Paginated { pagination, query }: Paginated<GetPitchesQuery>,
     State(model_manager): State<ModelManager>,
 ) -> Result<GetPitchesResponse, ServerError> {
-    // let pitches = match auth_token {
-    //     Some(AuthToken {
-    //         user_id,
-    //         user_role: UserRole::Business,
-    //         ..
-    //     }) => PitchController::get_pitches_by_business_id(user_id, &model_manager).await?,
-    //     _ => PitchController::get_all_pitches(&model_manager).await?,
-    // };
-    //
-
-    let pitches = vec![
-        Pitch {
-            id: String::from("pitch-1"),
-            name: String::from("Al Forsan"),
-            sport: String::from("football"),
-            location: String::from("Sharjah, UAE"),
-            address: String::from("pitch_one_address"),
-            price_per_hour: 249.99,
-            rating: 3.1,
-            review_count: 42,
-            image_url: String::from(
-                "https://unsplash.com/photos/group-of-people-playing-soccer-on-soccer-field-8-s5QuUBtyM",
-            ),
-            amenities: vec![String::from("water"), String::from("lights")],
-            description: String::from("pitch-1 description"),
-            owner_name: String::from("Alsaleh"),
-            owner_avatar: String::from(
-                "https://unsplash.com/photos/group-of-people-playing-soccer-on-soccer-field-8-s5QuUBtyM",
-            ),
-        },
-        Pitch {
-            id: todo!(),
-            name: todo!(),
-            sport: todo!(),
-            location: todo!(),
-            address: todo!(),
-            price_per_hour: todo!(),
-            rating: todo!(),
-            review_count: todo!(),
-            image_url: todo!(),
-            amenities: todo!(),
-            description: todo!(),
-            owner_name: todo!(),
-            owner_avatar: todo!(),
+    let page = pagination.page_request::<(PitchSort, Option<f64>, Uuid)>()?;
+
+    if let Some(radius_km) = query.radius_km
+        && (query.near.is_none() || !radius_km.is_finite() || radius_km <= 0.0)
+    {
+        return Err(ServerError::InvalidGeoFilter);
+    }
+
+    // the bounds have to fit the `price_per_hour_cents` column
+    for price in [query.min_price, query.max_price].into_iter().flatten() {
+        if price.is_negative() || i32::try_from(price.amount_cents()).is_err() {
+            return Err(ServerError::InvalidPriceRange);
+        }
+    }
+    if let (Some(min_price), Some(max_price)) = (query.min_price, query.max_price)
+        && !max_price
+            .checked_sub(min_price)
+            .is_ok_and(|difference| !difference.is_negative())
+    {
+        return Err(ServerError::InvalidPriceRange);
+    }
+
+    // businesses and admins manage pitches rather than look for one, so they keep a stable order
+    let is_browsing = !matches!(
+        auth_token,
+        Some(AuthToken {
+            user_role: UserRole::Business | UserRole::Admin,
+            ..
+        })
+    );
+    let sort = match query.sort {
+        None if is_browsing && query.relevance.unwrap_or(true) => PitchSort::Relevance,
+        None => PitchSort::Distance,
+        Some(PitchSortQuery::Distance) if query.near.is_none() && query.bbox.is_none() => {
+            return Err(ServerError::SortByDistanceWithoutOrigin);
+        }
+        Some(PitchSortQuery::Distance) => PitchSort::Distance,
+        Some(PitchSortQuery::Price) => PitchSort::Price,
+        Some(PitchSortQuery::PriceDesc) => PitchSort::PriceDesc,
+        Some(PitchSortQuery::Rating) => PitchSort::Rating,
+        Some(PitchSortQuery::RatingDesc) => PitchSort::RatingDesc,
+    };
+    // a cursor only continues the list it comes from
+    if let Some((cursor_sort, ..)) = page.after
+        && cursor_sort != sort
+    {
+        return Err(ServerError::InvalidQueryParams(
+            "cursor comes from a list with another sort".to_string(),
+        ));
+    }
+
+    let filters = PitchFilters {
+        sports: query.sport,
+        min_price_per_hour: query.min_price,
+        max_price_per_hour: query.max_price,
+        surface: query.surface,
+        environment: query.environment,
+        amenities: query.amenities,
+        min_capacity: query.min_capacity,
+        near: query.near,
+        radius_km: query.radius_km,
+        bbox: query.bbox,
+        available_between: query.available_between,
+        sort,
+    };
+
+    let pitches = match (auth_token, query.business_id) {
+        (
+            Some(AuthToken {
+                user_role: UserRole::Admin,
+                ..
+            }),
+            Some(business_id),
+        ) => {
+            PitchController::get_pitches_by_business_id(
+                business_id,
+                &model_manager,
+                &filters,
+                &page,
+            )
+            .await?
+        }
+        (_, Some(_)) => return Err(ServerError::UserIsNotAdmin),
+        (
+            Some(AuthToken {
+                user_role: UserRole::Admin,
+                ..
+            }),
+            None,
+        ) => {
+            PitchController::get_all_pitches_including_unpublished(&model_manager, &filters, &page)
+                .await?
+        }
+        (
+            Some(AuthToken {
+                user_id,
+                user_role: UserRole::Business,
+                ..
+            }),
+            None,
+        ) => {
+            PitchController::get_pitches_by_business_id(user_id, &model_manager, &filters, &page)
+                .await?
+        }
+        (auth_token, None) => {
+            let viewer_id = auth_token.map(|auth_token| auth_token.user_id);
+            PitchController::get_all_pitches(&model_manager, viewer_id, &filters, &page).await?
+        }
+    };
+
+    let pitches = pitches.map(|item| Pitch {
+        id: item.pitch.id,
+        name: item.pitch.display_name,
+        sport: item.pitch.sport,
+        other_sports: (!item.pitch.other_sports.is_empty()).then_some(item.pitch.other_sports),
+        facility_id: item.pitch.facility_id,
+        price_per_hour: Price::new(item.pitch.price_per_hour_cents.into(), item.pitch.currency),
+        rating: item.rating,
+        review_count: item.review_count,
+        image_url: item.pitch.image_url,
+        surface: item.pitch.surface,
+        environment: item.pitch.environment,
+        description: item.pitch.description,
+        max_players: item.pitch.max_players,
+        amenities: item.amenities,
+        timezone: item.pitch.timezone,
+        location: item
+            .city
+            .zip(item.country)
+            .map(|(city, country)| format!("{city}, {country}")),
+        address: item
+            .address_line_1
+            .map(|address_line_1| match item.address_line_2 {
+                Some(address_line_2) => format!("{address_line_1}, {address_line_2}"),
+                None => address_line_1,
+            }),
+        latitude: item.latitude,
+        longitude: item.longitude,
+        distance_km: item.distance_km,
+        owner_name: item.owner_name,
+        owner_avatar: item.owner_avatar,
+        is_favorite: item.is_favorite,
+        status: (item.pitch.status != PitchStatus::Published).then_some(item.pitch.status),
+        closure: item
+            .closure_reason
+            .zip(item.closure_starts_at.zip(item.closure_ends_at))
+            .map(|(reason, (starts_at, ends_at))| PitchClosureNotice {
+                starts_at: match item.closure_local_starts_at {
+                    Some(local_starts_at) => with_local_offset(starts_at, local_starts_at),
+                    None => starts_at.fixed_offset(),
+                },
+                ends_at: match item.closure_local_ends_at {
+                    Some(local_ends_at) => with_local_offset(ends_at, local_ends_at),
+                    None => ends_at.fixed_offset(),
+                },
+                reason,
+            }),
+        relevance: item
+            .relevance
+            .relevance_score
+            .filter(|_| query.debug)
+            .map(|score| PitchRelevance {
+                score,
+                sport: item.relevance.sport_score,
+                distance: item.relevance.distance_score,
+                price: item.relevance.price_score,
+                rating: item.relevance.rating_score,
+                availability: item.relevance.availability_score,
+                popularity: item.relevance.popularity_score,
+            }),
+    });
+
+    Ok(GetPitchesResponse::from(pitches))
+}
+
+fn into_location_payload(location: PitchLocationPayload) -> LocationPayload {
+    LocationPayload {
+        address_line_1: location.address_line_1,
+        address_line_2: location.address_line_2,
+        city: location.city,
+        postal_code: location.postal_code,
+        country: location.country,
+        latitude: location.latitude,
+        longitude: location.longitude,
+    }
+}
+
+async fn get_amenities(
+    State(model_manager): State<ModelManager>,
+) -> Result<GetAmenitiesResponse, ServerError> {
+    let amenities = PitchController::get_amenities(&model_manager).await?;
+
+    Ok(GetAmenitiesResponse { amenities })
+}
+
+async fn create_pitch(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<CreatePitchPayload>,
+) -> Result<CreatePitchResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+    let owner_id = match actor {
+        PitchActor::Owner(owner_id) => payload.owner_id.unwrap_or(owner_id),
+        PitchActor::Admin(_) => payload.owner_id.ok_or(ServerError::PitchOwnerIsRequired)?,
+    };
+
+    let pitch = InsertPitchPayload {
+        display_name: payload.name,
+        sport: payload.sport,
+        other_sports: payload.other_sports,
+        price_per_hour: payload.price_per_hour,
+        image_url: payload.image_url,
+        surface: payload.surface,
+        environment: payload.environment,
+        description: payload.description,
+        max_players: payload.max_players,
+        amenities: payload.amenities,
+        location: payload.location.map(into_location_payload),
+        status: if payload.is_draft {
+            PitchStatus::Draft
+        } else {
+            PitchStatus::Published
         },
-    ];
+        timezone: payload
+            .timezone
+            .map_or_else(|| "UTC".to_string(), |timezone| timezone.trim().to_string()),
+    };
+    let pitch_id = PitchController::create_pitch(&model_manager, actor, owner_id, pitch).await?;
+
+    Ok(CreatePitchResponse { pitch_id })
+}
+
+async fn update_pitch(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<UpdatePitchPayload>,
+) -> Result<UpdatePitchResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    let new_pitch = ControllerUpdatePitchPayload {
+        display_name: payload.name,
+        sport: payload.sport,
+        other_sports: payload.other_sports,
+        price_per_hour: payload.price_per_hour,
+        image_url: payload.image_url,
+        surface: payload.surface,
+        environment: payload.environment,
+        description: payload.description,
+        max_players: payload.max_players,
+        amenities: payload.amenities,
+        location: payload.location.map(into_location_payload),
+        timezone: payload.timezone.map(|timezone| timezone.trim().to_string()),
+    };
+    PitchController::update_pitch_by_id(&model_manager, pitch_id, actor, new_pitch).await?;
+
+    Ok(UpdatePitchResponse)
+}
+
+async fn hide_pitch(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<UpdatePitchResponse, ServerError> {
+    PitchController::set_pitch_status_by_id(
+        &model_manager,
+        pitch_id,
+        PitchActor::Admin(auth_token.user_id),
+        PitchStatus::Hidden,
+    )
+    .await?;
+
+    Ok(UpdatePitchResponse)
+}
+
+async fn unhide_pitch(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<UpdatePitchResponse, ServerError> {
+    PitchController::set_pitch_status_by_id(
+        &model_manager,
+        pitch_id,
+        PitchActor::Admin(auth_token.user_id),
+        PitchStatus::Published,
+    )
+    .await?;
+
+    Ok(UpdatePitchResponse)
+}
+
+async fn set_pitch_status(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<SetPitchStatusPayload>,
+) -> Result<UpdatePitchResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    PitchController::set_pitch_status_by_id(&model_manager, pitch_id, actor, payload.status)
+        .await?;
+
+    Ok(UpdatePitchResponse)
+}
+
+async fn get_closures(
+    auth_token: Option<AuthToken>,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetPitchClosuresResponse, ServerError> {
+    // players see the same closures as guests
+    let viewer = auth_token.and_then(|auth_token| get_pitch_actor(&auth_token).ok());
+
+    let closures = ClosureController::get_closures(&model_manager, pitch_id, viewer).await?;
+
+    Ok(GetPitchClosuresResponse {
+        closures: closures
+            .into_iter()
+            .map(|closure| PitchClosure {
+                id: closure.id,
+                starts_at: with_local_offset(closure.starts_at, closure.local_starts_at),
+                ends_at: with_local_offset(closure.ends_at, closure.local_ends_at),
+                reason: closure.reason,
+                is_confirmed: closure.is_confirmed,
+            })
+            .collect(),
+    })
+}
+
+async fn create_closure(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<CreateClosurePayload>,
+) -> Result<CreateClosureResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    if payload.starts_at >= payload.ends_at || payload.ends_at <= Utc::now() {
+        return Err(ServerError::InvalidClosurePeriod);
+    }
+    let reason = payload.reason.trim();
+    if reason.is_empty() || reason.chars().count() > MAX_CLOSURE_REASON_CHARS {
+        return Err(ServerError::InvalidClosureReason);
+    }
+
+    let closure = InsertClosurePayload {
+        starts_at: payload.starts_at,
+        ends_at: payload.ends_at,
+        reason: reason.to_string(),
+    };
+    let created_closure =
+        ClosureController::create_closure(&model_manager, pitch_id, actor, closure).await?;
+
+    Ok(CreateClosureResponse {
+        closure_id: created_closure.id,
+        is_confirmed: created_closure.is_confirmed,
+        affected_bookings: created_closure
+            .affected_bookings
+            .into_iter()
+            .map(|booking| AffectedBooking {
+                booking_id: booking.booking_id,
+                timeslot_id: booking.timeslot_id,
+                booked_by: booking.booked_by,
+                starts_at: with_local_offset(booking.starts_at, booking.local_starts_at),
+                ends_at: with_local_offset(booking.ends_at, booking.local_ends_at),
+            })
+            .collect(),
+    })
+}
+
+async fn confirm_closure(
+    auth_token: AuthToken,
+    Path((pitch_id, closure_id)): Path<(Uuid, Uuid)>,
+    State(model_manager): State<ModelManager>,
+) -> Result<UpdateClosureResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    ClosureController::confirm_closure_by_id(&model_manager, pitch_id, closure_id, actor).await?;
+
+    Ok(UpdateClosureResponse)
+}
+
+async fn delete_closure(
+    auth_token: AuthToken,
+    Path((pitch_id, closure_id)): Path<(Uuid, Uuid)>,
+    State(model_manager): State<ModelManager>,
+) -> Result<DeleteClosureResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    ClosureController::delete_closure_by_id(&model_manager, pitch_id, closure_id, actor).await?;
+
+    Ok(DeleteClosureResponse)
+}
+
+async fn get_opening_hours(
+    auth_token: Option<AuthToken>,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetOpeningHoursResponse, ServerError> {
+    let viewer = auth_token.and_then(|auth_token| get_pitch_actor(&auth_token).ok());
+
+    let opening_hours =
+        OpeningHoursController::get_opening_hours(&model_manager, pitch_id, viewer).await?;
+
+    Ok(GetOpeningHoursResponse {
+        timezone: opening_hours.timezone,
+        weekly: opening_hours
+            .weekly
+            .into_iter()
+            .map(|hours| PitchWeeklyHours {
+                weekday: hours.weekday,
+                opens_at: hours.opens_at,
+                closes_at: hours.closes_at,
+            })
+            .collect(),
+        exceptions: opening_hours
+            .exceptions
+            .into_iter()
+            .map(|exception| PitchOpeningHoursException {
+                date: exception.date,
+                opens_at: exception.opens_at,
+                closes_at: exception.closes_at,
+                reason: exception.reason,
+            })
+            .collect(),
+    })
+}
+
+async fn set_opening_hours(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<SetOpeningHoursPayload>,
+) -> Result<SetOpeningHoursResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    let mut weekly: Vec<_> = payload
+        .weekly
+        .into_iter()
+        .map(|hours| WeeklyOpeningHours {
+            weekday: hours.weekday,
+            opens_at: hours.opens_at,
+            closes_at: hours.closes_at,
+        })
+        .collect();
+    weekly.sort_by_key(|hours| (hours.weekday as u8, hours.opens_at));
+    let overlaps = weekly
+        .windows(2)
+        .any(|pair| pair[0].weekday == pair[1].weekday && pair[0].closes_at > pair[1].opens_at);
+    if overlaps || weekly.iter().any(|hours| hours.opens_at >= hours.closes_at) {
+        return Err(ServerError::InvalidOpeningHours);
+    }
+
+    let mut dates = HashSet::new();
+    let mut exceptions = Vec::with_capacity(payload.exceptions.len());
+    for exception in payload.exceptions {
+        let has_valid_hours = match (exception.opens_at, exception.closes_at) {
+            (Some(opens_at), Some(closes_at)) => opens_at < closes_at,
+            (None, None) => true,
+            _ => false,
+        };
+        let reason = exception
+            .reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+        let has_valid_reason = reason
+            .as_ref()
+            .is_none_or(|reason| reason.chars().count() <= MAX_OPENING_HOURS_REASON_CHARS);
+        if !has_valid_hours || !has_valid_reason || !dates.insert(exception.date) {
+            return Err(ServerError::InvalidOpeningHours);
+        }
+
+        exceptions.push(OpeningHoursException {
+            date: exception.date,
+            opens_at: exception.opens_at,
+            closes_at: exception.closes_at,
+            reason,
+        });
+    }
+
+    let opening_hours = OpeningHours {
+        timezone: payload.timezone.trim().to_string(),
+        weekly,
+        exceptions,
+    };
+    OpeningHoursController::set_opening_hours(&model_manager, pitch_id, actor, opening_hours)
+        .await?;
+
+    Ok(SetOpeningHoursResponse)
+}
+
+async fn get_effective_opening_hours(
+    auth_token: Option<AuthToken>,
+    Path(pitch_id): Path<Uuid>,
+    query: Result<Query<GetEffectiveOpeningHoursQuery>, QueryRejection>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetEffectiveOpeningHoursResponse, ServerError> {
+    let Query(query) =
+        query.map_err(|rejection| ServerError::InvalidQueryParams(rejection.body_text()))?;
+
+    let days = (query.to - query.from).num_days() + 1;
+    if !(1..=MAX_EFFECTIVE_OPENING_HOURS_DAYS).contains(&days) {
+        return Err(ServerError::InvalidDateRange);
+    }
+    let viewer = auth_token.and_then(|auth_token| get_pitch_actor(&auth_token).ok());
+
+    let effective_hours = OpeningHoursController::get_effective_opening_hours(
+        &model_manager,
+        pitch_id,
+        viewer,
+        query.from,
+        query.to,
+    )
+    .await?;
+
+    Ok(GetEffectiveOpeningHoursResponse {
+        timezone: effective_hours.timezone,
+        days: effective_hours
+            .days
+            .into_iter()
+            .map(|day| EffectiveOpeningDay {
+                date: day.date,
+                is_open: !day.intervals.is_empty(),
+                intervals: day
+                    .intervals
+                    .into_iter()
+                    .map(|interval| OpeningInterval {
+                        opens_at: interval.opens_at,
+                        closes_at: interval.closes_at,
+                    })
+                    .collect(),
+                reason: day.reason,
+            })
+            .collect(),
+    })
+}
+
+async fn get_availability(
+    auth_token: Option<AuthToken>,
+    Path(pitch_id): Path<Uuid>,
+    query: Result<Query<GetAvailabilityQuery>, QueryRejection>,
+    headers: HeaderMap,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetAvailabilityResponse, ServerError> {
+    let Query(query) =
+        query.map_err(|rejection| ServerError::InvalidQueryParams(rejection.body_text()))?;
+
+    let days = (query.to - query.from).num_days() + 1;
+    if !(1..=MAX_AVAILABILITY_DAYS).contains(&days) {
+        return Err(ServerError::InvalidDateRange);
+    }
+    let viewer = auth_token.and_then(|auth_token| get_pitch_actor(&auth_token).ok());
+
+    let availability = AvailabilityController::get_availability(
+        &model_manager,
+        pitch_id,
+        viewer,
+        query.from,
+        query.to,
+        query.tz,
+    )
+    .await?;
+
+    if has_etag(&headers, &availability.etag) {
+        return Ok(GetAvailabilityResponse {
+            etag: availability.etag,
+            availability: None,
+        });
+    }
+
+    Ok(GetAvailabilityResponse {
+        etag: availability.etag,
+        availability: Some(PitchAvailability {
+            timezone: availability.timezone,
+            days: availability
+                .days
+                .into_iter()
+                .map(|day| AvailabilityDay {
+                    date: day.date,
+                    intervals: day
+                        .intervals
+                        .into_iter()
+                        .map(|interval| AvailabilityInterval {
+                            kind: interval.kind,
+                            starts_at: interval.starts_at,
+                            ends_at: interval.ends_at,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }),
+    })
+}
+
+/// Whether `If-None-Match` lists the etag, weak or not
+fn has_etag(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/").trim_matches('"') == etag)
+}
+
+async fn get_pitch_timeslots(
+    auth_token: Option<AuthToken>,
+    Path(pitch_id): Path<Uuid>,
+    Paginated { pagination, query }: Paginated<GetPitchTimeslotsQuery>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetTimeslotsResponse, ServerError> {
+    let viewer = auth_token.and_then(|auth_token| get_pitch_actor(&auth_token).ok());
+    let filters = TimeslotFilters {
+        from: query.from,
+        to: query.to,
+        ..Default::default()
+    };
+
+    let timeslots = TimeslotController::get_pitch_timeslots(
+        &model_manager,
+        pitch_id,
+        viewer,
+        filters,
+        &pagination.page_request()?,
+    )
+    .await?
+    .map(into_timeslot_response);
+
+    Ok(GetTimeslotsResponse::from(timeslots))
+}
+
+async fn get_timeslot_rules(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetTimeslotRulesResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    let rules = TimeslotRuleController::get_rules(&model_manager, pitch_id, actor).await?;
+
+    Ok(GetTimeslotRulesResponse {
+        rules: rules
+            .into_iter()
+            .map(|rule| TimeslotRule {
+                id: rule.id,
+                weekdays: rule.weekdays,
+                starts_at: rule.starts_at,
+                ends_at: rule.ends_at,
+                slot_minutes: rule.slot_minutes,
+                starts_on: rule.starts_on,
+                until: rule.until,
+            })
+            .collect(),
+    })
+}
+
+async fn create_timeslot_rule(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<CreateTimeslotRulePayload>,
+) -> Result<CreateTimeslotRuleResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    let window_minutes =
+        i32::from(payload.ends_at.minutes()) - i32::from(payload.starts_at.minutes());
+    let ends_before_it_starts = payload.until.is_some_and(|until| until < payload.starts_on);
+    if payload.weekdays.is_empty()
+        || payload.slot_minutes <= 0
+        || payload.slot_minutes > window_minutes
+        || ends_before_it_starts
+    {
+        return Err(ServerError::InvalidTimeslotRule);
+    }
+    let mut weekdays = HashSet::new();
+    let rule = InsertTimeslotRulePayload {
+        weekdays: payload
+            .weekdays
+            .into_iter()
+            .filter(|weekday| weekdays.insert(*weekday))
+            .collect(),
+        starts_at: payload.starts_at,
+        ends_at: payload.ends_at,
+        slot_minutes: payload.slot_minutes,
+        starts_on: payload.starts_on,
+        until: payload.until,
+    };
+    let created_rule =
+        TimeslotRuleController::create_rule(&model_manager, pitch_id, actor, rule).await?;
+
+    Ok(CreateTimeslotRuleResponse {
+        rule_id: created_rule.id,
+        created_timeslots: created_rule.created_timeslots,
+    })
+}
+
+async fn delete_timeslot_rule(
+    auth_token: AuthToken,
+    Path((pitch_id, rule_id)): Path<(Uuid, Uuid)>,
+    State(model_manager): State<ModelManager>,
+) -> Result<DeleteTimeslotRuleResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    TimeslotRuleController::delete_rule_by_id(&model_manager, pitch_id, rule_id, actor).await?;
+
+    Ok(DeleteTimeslotRuleResponse)
+}
+
+async fn get_pricing_rules(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetPricingRulesResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    let rules = PricingRuleController::get_rules(&model_manager, pitch_id, actor).await?;
+
+    Ok(GetPricingRulesResponse {
+        rules: rules
+            .into_iter()
+            .map(|rule| PricingRule {
+                id: rule.id,
+                name: rule.name,
+                priority: rule.priority,
+                weekdays: rule.weekdays,
+                starts_at: rule.starts_at,
+                ends_at: rule.ends_at,
+                starts_on: rule.starts_on,
+                until: rule.until,
+                min_lead_minutes: rule.min_lead_minutes,
+                max_lead_minutes: rule.max_lead_minutes,
+                price_per_hour_cents: rule.price_per_hour_cents,
+                adjustment_percent: rule.adjustment_percent,
+            })
+            .collect(),
+    })
+}
+
+async fn create_pricing_rule(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<CreatePricingRulePayload>,
+) -> Result<CreatePricingRuleResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    let name_chars = payload.name.chars().count();
+    let dates_are_ordered = match (payload.starts_on, payload.until) {
+        (Some(starts_on), Some(until)) => starts_on <= until,
+        _ => true,
+    };
+    let leads_are_ordered = match (payload.min_lead_minutes, payload.max_lead_minutes) {
+        (Some(min_lead_minutes), Some(max_lead_minutes)) => min_lead_minutes <= max_lead_minutes,
+        _ => true,
+    };
+    let has_negative_lead = [payload.min_lead_minutes, payload.max_lead_minutes]
+        .into_iter()
+        .flatten()
+        .any(|lead_minutes| lead_minutes < 0);
+    let adjustment = match (payload.price_per_hour_cents, payload.adjustment_percent) {
+        (Some(cents), None) if cents >= 0 => PriceAdjustment::PricePerHourCents(cents),
+        (None, Some(percent)) if (-100..=MAX_ADJUSTMENT_PERCENT).contains(&percent) => {
+            PriceAdjustment::Percent(percent)
+        }
+        _ => return Err(ServerError::InvalidPricingRule),
+    };
+    if !(1..=MAX_PRICING_RULE_NAME_CHARS).contains(&name_chars)
+        || payload.weekdays.is_empty()
+        || payload.starts_at >= payload.ends_at
+        || !dates_are_ordered
+        || !leads_are_ordered
+        || has_negative_lead
+    {
+        return Err(ServerError::InvalidPricingRule);
+    }
+    let mut weekdays = HashSet::new();
+    let rule = InsertPricingRulePayload {
+        name: payload.name,
+        priority: payload.priority,
+        weekdays: payload
+            .weekdays
+            .into_iter()
+            .filter(|weekday| weekdays.insert(*weekday))
+            .collect(),
+        starts_at: payload.starts_at,
+        ends_at: payload.ends_at,
+        starts_on: payload.starts_on,
+        until: payload.until,
+        min_lead_minutes: payload.min_lead_minutes,
+        max_lead_minutes: payload.max_lead_minutes,
+        adjustment,
+    };
+    let rule_id = PricingRuleController::create_rule(&model_manager, pitch_id, actor, rule).await?;
+
+    Ok(CreatePricingRuleResponse { rule_id })
+}
+
+async fn delete_pricing_rule(
+    auth_token: AuthToken,
+    Path((pitch_id, rule_id)): Path<(Uuid, Uuid)>,
+    State(model_manager): State<ModelManager>,
+) -> Result<DeletePricingRuleResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    PricingRuleController::delete_rule_by_id(&model_manager, pitch_id, rule_id, actor).await?;
+
+    Ok(DeletePricingRuleResponse)
+}
+
+async fn get_calendar_feed(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<CalendarFeedResponse, ServerError> {
+    let scope = FeedScope::Pitch(pitch_id, get_pitch_actor(&auth_token)?);
+
+    let feed = CalendarFeedController::get_feed(&model_manager, scope).await?;
+
+    Ok(into_calendar_feed_response(feed.token))
+}
+
+async fn regenerate_calendar_feed(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<CalendarFeedResponse, ServerError> {
+    let scope = FeedScope::Pitch(pitch_id, get_pitch_actor(&auth_token)?);
+
+    let feed = CalendarFeedController::regenerate_feed(&model_manager, scope).await?;
+
+    Ok(into_calendar_feed_response(feed.token))
+}
+
+async fn delete_calendar_feed(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<DeleteCalendarFeedResponse, ServerError> {
+    let scope = FeedScope::Pitch(pitch_id, get_pitch_actor(&auth_token)?);
+
+    CalendarFeedController::delete_feed(&model_manager, scope).await?;
+
+    Ok(DeleteCalendarFeedResponse)
+}
+
+async fn get_price_quote(
+    auth_token: Option<AuthToken>,
+    Path(pitch_id): Path<Uuid>,
+    query: Result<Query<GetPriceQuoteQuery>, QueryRejection>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetPriceQuoteResponse, ServerError> {
+    let Query(query) =
+        query.map_err(|rejection| ServerError::InvalidQueryParams(rejection.body_text()))?;
+
+    if query.start >= query.end || query.end - query.start > Duration::days(MAX_PRICE_QUOTE_DAYS) {
+        return Err(ServerError::InvalidPriceQuotePeriod);
+    }
+    let viewer = auth_token.and_then(|auth_token| get_pitch_actor(&auth_token).ok());
+
+    let quote = PricingRuleController::get_price_quote(
+        &model_manager,
+        pitch_id,
+        viewer,
+        query.start,
+        query.end,
+    )
+    .await?;
+
+    Ok(GetPriceQuoteResponse {
+        starts_at: quote.starts_at,
+        ends_at: quote.ends_at,
+        price: quote.price,
+        segments: quote
+            .segments
+            .into_iter()
+            .map(|segment| PriceQuoteSegment {
+                starts_at: segment.starts_at,
+                ends_at: segment.ends_at,
+                rule_id: segment.rule.as_ref().map(|rule| rule.id),
+                rule_name: segment.rule.map(|rule| rule.name),
+                price_per_hour: segment.price_per_hour,
+                price: segment.price,
+            })
+            .collect(),
+    })
+}
+
+async fn delete_pitch(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<DeletePitchResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    PitchController::delete_pitch_by_id(&model_manager, pitch_id, actor).await?;
+
+    Ok(DeletePitchResponse)
+}
+
+async fn get_favorites_count(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetPitchFavoritesCountResponse, ServerError> {
+    let owner_id = match auth_token.user_role {
+        UserRole::Business => Some(auth_token.user_id),
+        UserRole::Admin => None,
+        UserRole::Player => return Err(ServerError::UserIsNotBusiness),
+    };
+
+    let favorites_count =
+        FavoriteController::count_pitch_favorites(&model_manager, pitch_id, owner_id).await?;
 
-    Ok(GetPitchesResponse(pitches))
+    Ok(GetPitchFavoritesCountResponse {
+        pitch_id,
+        favorites_count,
+    })
 }
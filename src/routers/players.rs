@@ -0,0 +1,45 @@
+use crate::{
+    controllers::{PlayerController, ProfileViewer},
+    errors::ServerError,
+    extractors::AuthToken,
+    models::{
+        ModelManager, api_schemas::responses::GetPlayerPublicProfileResponse, tables::UserRole,
+    },
+};
+use axum::{
+    Router,
+    extract::{Path, State},
+    routing::get,
+};
+use uuid::Uuid;
+
+pub fn get_router() -> Router<ModelManager> {
+    Router::new().route("/{player_id}/public", get(get_public_profile))
+}
+
+async fn get_public_profile(
+    auth_token: Option<AuthToken>,
+    Path(player_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetPlayerPublicProfileResponse, ServerError> {
+    let viewer = match auth_token {
+        Some(AuthToken {
+            user_role: UserRole::Admin,
+            ..
+        }) => ProfileViewer::Owner,
+        Some(AuthToken { user_id, .. }) if user_id == player_id => ProfileViewer::Owner,
+        _ => ProfileViewer::Stranger,
+    };
+
+    let profile = PlayerController::get_public_profile_by_id(&model_manager, player_id)
+        .await?
+        .visible_to(viewer);
+
+    Ok(GetPlayerPublicProfileResponse {
+        id: profile.id,
+        name: profile.name,
+        member_since: profile.member_since,
+        total_bookings: profile.total_bookings,
+        favorite_sport: profile.favorite_sport,
+    })
+}
@@ -0,0 +1,80 @@
+use crate::{
+    controllers::SearchController,
+    errors::ServerError,
+    models::{
+        ModelManager,
+        api_schemas::{
+            query_params::SearchQuery,
+            responses::{AreaSearchHit, BusinessSearchHit, PitchSearchHit, SearchResponse},
+        },
+    },
+};
+use axum::{
+    Router,
+    extract::{Query, State, rejection::QueryRejection},
+    routing::get,
+};
+
+const DEFAULT_SEARCH_LIMIT: u32 = 5;
+const MAX_SEARCH_LIMIT: u32 = 20;
+const MAX_SEARCH_QUERY_CHARS: usize = 100;
+
+pub fn get_router() -> Router<ModelManager> {
+    Router::new().route("/", get(search))
+}
+
+async fn search(
+    query: Result<Query<SearchQuery>, QueryRejection>,
+    State(model_manager): State<ModelManager>,
+) -> Result<SearchResponse, ServerError> {
+    let Query(query) =
+        query.map_err(|rejection| ServerError::InvalidQueryParams(rejection.body_text()))?;
+
+    let text = query.q.trim();
+    if text.is_empty() || text.chars().count() > MAX_SEARCH_QUERY_CHARS {
+        return Err(ServerError::InvalidSearchQuery);
+    }
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+
+    let results = SearchController::search(&model_manager, text, limit.into()).await?;
+
+    Ok(SearchResponse {
+        pitches: results
+            .pitches
+            .into_iter()
+            .map(|hit| PitchSearchHit {
+                id: hit.id,
+                display_name: hit.display_name,
+                sport: hit.sport,
+                city: hit.city,
+                snippet: hit.snippet,
+                rank: hit.rank,
+            })
+            .collect(),
+        businesses: results
+            .businesses
+            .into_iter()
+            .map(|hit| BusinessSearchHit {
+                id: hit.id,
+                display_name: hit.display_name,
+                pitch_count: hit.pitch_count,
+                snippet: hit.snippet,
+                rank: hit.rank,
+            })
+            .collect(),
+        areas: results
+            .areas
+            .into_iter()
+            .map(|hit| AreaSearchHit {
+                city: hit.city,
+                country: hit.country,
+                pitch_count: hit.pitch_count,
+                snippet: hit.snippet,
+                rank: hit.rank,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,365 @@
+use crate::{
+    controllers::{
+        ImportAction, ImportFormat, ImportRowError as ControllerImportRowError,
+        InsertTimeslotPayload, PricedTimeslot, TimeslotController, TimeslotFilters,
+        TimeslotOperation, TimeslotOperationOutcome,
+        UpdateTimeslotPayload as ControllerUpdateTimeslotPayload,
+    },
+    errors::{ClientError, ServerError},
+    extractors::{AuthToken, Paginated},
+    models::{
+        ModelManager,
+        api_schemas::{
+            query_params::{GetTimeslotsQuery, ImportTimeslotsQuery},
+            requests::{
+                BulkMode, BulkTimeslotOperation, BulkTimeslotsPayload, CreateTimeslotPayload,
+                UpdateTimeslotPayload,
+            },
+            responses::{
+                BulkTimeslotResult, BulkTimeslotStatus, BulkTimeslotsResponse,
+                CreateTimeslotResponse, DeleteTimeslotResponse, GetTimeslotsResponse,
+                ImportRowError, ImportRowMessage, ImportTimeslotsResponse, ImportedTimeslot,
+                Timeslot, UpdateTimeslotResponse,
+            },
+        },
+        local_time::with_local_offset,
+    },
+    routers::pitches::get_pitch_actor,
+};
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::{Path, Query, State, rejection::QueryRejection},
+    http::{HeaderMap, header},
+    routing::{get, patch, post},
+};
+use uuid::Uuid;
+
+const MAX_BULK_OPERATIONS: usize = 500;
+const MAX_IMPORT_ROWS: usize = 500;
+
+pub fn get_router() -> Router<ModelManager> {
+    Router::new()
+        .route("/", get(get_timeslots).post(create_timeslot))
+        .route("/bulk", post(apply_bulk_operations))
+        .route("/import", post(import_timeslots))
+        .route(
+            "/{timeslot_id}",
+            patch(update_timeslot).delete(delete_timeslot),
+        )
+}
+
+async fn get_timeslots(
+    auth_token: Option<AuthToken>,
+    Paginated { pagination, query }: Paginated<GetTimeslotsQuery>,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetTimeslotsResponse, ServerError> {
+    // players see the same timeslots as guests
+    let viewer = auth_token.and_then(|auth_token| get_pitch_actor(&auth_token).ok());
+    let filters = TimeslotFilters {
+        pitch_id: query.pitch_id,
+        business_id: query.business_id,
+        from: query.from,
+        to: query.to,
+    };
+
+    let timeslots = TimeslotController::get_timeslots(
+        &model_manager,
+        viewer,
+        filters,
+        &pagination.page_request()?,
+    )
+    .await?
+    .map(into_timeslot_response);
+
+    Ok(GetTimeslotsResponse::from(timeslots))
+}
+
+pub(super) fn into_timeslot_response(
+    PricedTimeslot { timeslot, price }: PricedTimeslot,
+) -> Timeslot {
+    Timeslot {
+        id: timeslot.id,
+        pitch_id: timeslot.pitch_id,
+        starts_at: with_local_offset(timeslot.starts_at, timeslot.local_starts_at),
+        ends_at: with_local_offset(timeslot.ends_at, timeslot.local_ends_at),
+        timezone: timeslot.timezone,
+        is_booked: timeslot.is_booked,
+        rule_id: timeslot.rule_id,
+        price,
+        has_custom_price: timeslot.custom_price.is_some(),
+    }
+}
+
+async fn create_timeslot(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<CreateTimeslotPayload>,
+) -> Result<CreateTimeslotResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    let timeslot = InsertTimeslotPayload {
+        pitch_id: payload.pitch_id,
+        starts_at: payload.starts_at,
+        ends_at: payload.ends_at,
+        price: payload.price,
+    };
+    let timeslot_id = TimeslotController::create_timeslot(&model_manager, actor, timeslot).await?;
+
+    Ok(CreateTimeslotResponse { timeslot_id })
+}
+
+async fn update_timeslot(
+    auth_token: AuthToken,
+    Path(timeslot_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<UpdateTimeslotPayload>,
+) -> Result<UpdateTimeslotResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    let new_timeslot = ControllerUpdateTimeslotPayload {
+        starts_at: payload.starts_at,
+        ends_at: payload.ends_at,
+        price: payload.price,
+    };
+    TimeslotController::update_timeslot_by_id(&model_manager, timeslot_id, actor, new_timeslot)
+        .await?;
+
+    Ok(UpdateTimeslotResponse)
+}
+
+async fn delete_timeslot(
+    auth_token: AuthToken,
+    Path(timeslot_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<DeleteTimeslotResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    TimeslotController::delete_timeslot_by_id(&model_manager, timeslot_id, actor).await?;
+
+    Ok(DeleteTimeslotResponse)
+}
+
+async fn apply_bulk_operations(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+    Json(payload): Json<BulkTimeslotsPayload>,
+) -> Result<BulkTimeslotsResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+
+    if !(1..=MAX_BULK_OPERATIONS).contains(&payload.operations.len()) {
+        return Err(ServerError::InvalidBulkOperations);
+    }
+    let mut timeslot_ids = Vec::with_capacity(payload.operations.len());
+    let operations = payload
+        .operations
+        .into_iter()
+        .map(|operation| match operation {
+            BulkTimeslotOperation::Create {
+                pitch_id,
+                starts_at,
+                ends_at,
+                price,
+            } => {
+                timeslot_ids.push(None);
+                TimeslotOperation::Create(InsertTimeslotPayload {
+                    pitch_id,
+                    starts_at,
+                    ends_at,
+                    price,
+                })
+            }
+            BulkTimeslotOperation::Update {
+                timeslot_id,
+                starts_at,
+                ends_at,
+                price,
+            } => {
+                timeslot_ids.push(Some(timeslot_id));
+                TimeslotOperation::Update(
+                    timeslot_id,
+                    ControllerUpdateTimeslotPayload {
+                        starts_at,
+                        ends_at,
+                        price,
+                    },
+                )
+            }
+            BulkTimeslotOperation::Delete { timeslot_id } => {
+                timeslot_ids.push(Some(timeslot_id));
+                TimeslotOperation::Delete(timeslot_id)
+            }
+        })
+        .collect();
+
+    let applied = TimeslotController::apply_operations(
+        &model_manager,
+        actor,
+        operations,
+        payload.mode == BulkMode::AllOrNothing,
+    )
+    .await?;
+
+    let is_committed = applied.is_committed;
+    Ok(BulkTimeslotsResponse {
+        is_committed,
+        results: applied
+            .results
+            .into_iter()
+            .zip(timeslot_ids)
+            .map(|(result, timeslot_id)| match result {
+                Ok(TimeslotOperationOutcome::Created(id)) if is_committed => BulkTimeslotResult {
+                    status: BulkTimeslotStatus::Created,
+                    timeslot_id: Some(id),
+                    error: None,
+                    conflicting_timeslot_id: None,
+                },
+                Ok(TimeslotOperationOutcome::Updated) if is_committed => BulkTimeslotResult {
+                    status: BulkTimeslotStatus::Updated,
+                    timeslot_id,
+                    error: None,
+                    conflicting_timeslot_id: None,
+                },
+                Ok(TimeslotOperationOutcome::Deleted) if is_committed => BulkTimeslotResult {
+                    status: BulkTimeslotStatus::Deleted,
+                    timeslot_id,
+                    error: None,
+                    conflicting_timeslot_id: None,
+                },
+                Ok(_) => BulkTimeslotResult {
+                    status: BulkTimeslotStatus::RolledBack,
+                    timeslot_id,
+                    error: None,
+                    conflicting_timeslot_id: None,
+                },
+                Err(err) => {
+                    let server_error = ServerError::from(err);
+                    let conflicting_timeslot_id = match server_error {
+                        ServerError::TimeslotOverlaps(conflicting_timeslot_id) => {
+                            conflicting_timeslot_id
+                        }
+                        _ => None,
+                    };
+                    BulkTimeslotResult {
+                        status: BulkTimeslotStatus::Failed,
+                        timeslot_id,
+                        error: Some(ClientError::from(&server_error)),
+                        conflicting_timeslot_id,
+                    }
+                }
+            })
+            .collect(),
+    })
+}
+
+/// Takes the file as the body, `text/csv` or `text/calendar`
+async fn import_timeslots(
+    auth_token: AuthToken,
+    query: Result<Query<ImportTimeslotsQuery>, QueryRejection>,
+    State(model_manager): State<ModelManager>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<ImportTimeslotsResponse, ServerError> {
+    let actor = get_pitch_actor(&auth_token)?;
+    let Query(query) =
+        query.map_err(|rejection| ServerError::InvalidQueryParams(rejection.body_text()))?;
+
+    let media_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|media_type| media_type.trim().to_ascii_lowercase());
+    let format = match media_type.as_deref() {
+        Some("text/csv") => ImportFormat::Csv,
+        Some("text/calendar") => ImportFormat::ICalendar,
+        _ => return Err(ServerError::UnsupportedImportFormat),
+    };
+    let text = std::str::from_utf8(&body).map_err(|_| ServerError::InvalidImportFile)?;
+    let rows = format.parse(text)?;
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(ServerError::TooManyImportRows);
+    }
+
+    let imported_rows = TimeslotController::import_timeslots(
+        &model_manager,
+        actor,
+        query.pitch_id,
+        rows,
+        query.apply,
+    )
+    .await?;
+
+    let mut response = ImportTimeslotsResponse {
+        is_applied: query.apply,
+        creates: Vec::new(),
+        conflicts: Vec::new(),
+        skips: Vec::new(),
+        errors: Vec::new(),
+    };
+    for row in imported_rows {
+        let timeslot = match row.timeslot {
+            Ok(timeslot) => timeslot,
+            Err(err) => {
+                response.errors.push(ImportRowError {
+                    line: row.line,
+                    message: into_import_row_message(err),
+                });
+                continue;
+            }
+        };
+        let mut imported_timeslot = ImportedTimeslot {
+            line: row.line,
+            pitch_id: timeslot.pitch_id,
+            starts_at: with_local_offset(timeslot.starts_at, timeslot.local_starts_at),
+            ends_at: with_local_offset(timeslot.ends_at, timeslot.local_ends_at),
+            price: timeslot.price,
+            timeslot_id: None,
+            error: None,
+            conflicting_timeslot_id: None,
+        };
+        match timeslot.action {
+            ImportAction::Create(timeslot_id) => {
+                // the timeslot was rolled back with the rest of a dry run
+                imported_timeslot.timeslot_id = Some(timeslot_id).filter(|_| query.apply);
+                response.creates.push(imported_timeslot);
+            }
+            ImportAction::SkipExisting(timeslot_id) => {
+                imported_timeslot.timeslot_id = Some(timeslot_id);
+                response.skips.push(imported_timeslot);
+            }
+            ImportAction::SkipPast => {
+                imported_timeslot.error = Some(ClientError::from(&ServerError::TimeslotInThePast));
+                response.skips.push(imported_timeslot);
+            }
+            ImportAction::Conflict(err) => {
+                let server_error = ServerError::from(err);
+                if let ServerError::TimeslotOverlaps(conflicting_timeslot_id) = server_error {
+                    imported_timeslot.conflicting_timeslot_id = conflicting_timeslot_id;
+                }
+                imported_timeslot.error = Some(ClientError::from(&server_error));
+                response.conflicts.push(imported_timeslot);
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+fn into_import_row_message(err: ControllerImportRowError) -> ImportRowMessage {
+    match err {
+        ControllerImportRowError::MissingColumns => {
+            ImportRowMessage::RowMustHaveAValueForEveryColumn
+        }
+        ControllerImportRowError::InvalidStart => ImportRowMessage::StartMustBeADateTime,
+        ControllerImportRowError::InvalidEnd => ImportRowMessage::EndMustBeADateTime,
+        ControllerImportRowError::InvalidPrice => ImportRowMessage::PriceMustBeAnAmountAndACurrency,
+        ControllerImportRowError::AllDayEvent => ImportRowMessage::AllDayEventsCannotBeImported,
+        ControllerImportRowError::RecurringEvent => {
+            ImportRowMessage::RecurringEventsCannotBeImported
+        }
+        ControllerImportRowError::MissingPitch => ImportRowMessage::PitchIsRequired,
+        ControllerImportRowError::UnknownPitch => ImportRowMessage::PitchNotFound,
+        ControllerImportRowError::UnknownTimezone => ImportRowMessage::UnknownTimezone,
+        ControllerImportRowError::InvalidPeriod => ImportRowMessage::EndMustBeAfterStart,
+    }
+}
@@ -1,29 +1,64 @@
 use crate::{
-    controllers::UserController,
+    controllers::{
+        CalendarFeedController, FavoriteController, FeedScope, NotificationController,
+        PlayerController, UserController,
+    },
     errors::ServerError,
-    extractors::AuthToken,
+    extractors::{AuthToken, Paginated},
     middlewares,
     models::{
         ModelManager,
         api_schemas::{
             common_schemas::UserProfile,
             query_params::GetUserInfoQuery,
-            requests::{UpdateUserInfoPayload, UpdateUserProfilePayload},
-            responses::{GetUserPersonalInfoResponse, UpdateUserInfoResponse},
+            requests::{
+                UpdatePrivacySettingsPayload, UpdateUserInfoPayload, UpdateUserProfilePayload,
+            },
+            responses::{
+                CalendarFeedResponse, DeleteCalendarFeedResponse, FavoritePitch,
+                GetFavoritesResponse, GetNotificationsResponse, GetPrivacySettingsResponse,
+                GetUserPersonalInfoResponse, Notification, UpdateFavoritesResponse,
+                UpdatePrivacySettingsResponse, UpdateUserInfoResponse,
+            },
         },
+        tables::UserRole,
     },
+    routers::calendar_feeds::into_calendar_feed_response,
 };
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
     middleware,
-    routing::get,
+    routing::{get, put},
 };
 use uuid::Uuid;
 
 pub fn get_router() -> Router<ModelManager> {
     Router::new()
         .route("/me", get(get_personal_info).patch(update_personal_info))
+        .route(
+            "/me/privacy",
+            get(get_privacy_settings)
+                .patch(update_privacy_settings)
+                .route_layer(middleware::from_fn(middlewares::authenticate_player)),
+        )
+        .route(
+            "/me/favorites",
+            get(get_favorites).route_layer(middleware::from_fn(middlewares::authenticate_player)),
+        )
+        .route(
+            "/me/favorites/pitches/{pitch_id}",
+            put(add_favorite_pitch)
+                .delete(remove_favorite_pitch)
+                .route_layer(middleware::from_fn(middlewares::authenticate_player)),
+        )
+        .route("/me/notifications", get(get_notifications))
+        .route(
+            "/me/calendar-feed",
+            get(get_calendar_feed)
+                .put(regenerate_calendar_feed)
+                .delete(delete_calendar_feed),
+        )
         .route(
             "/{user_id}",
             get(get_user_info).route_layer(middleware::from_fn(middlewares::authenticate_admin)),
@@ -92,6 +127,7 @@ async fn update_personal_info(
                     first_name: profile.first_name,
                     last_name: profile.last_name,
                     preferred_sports: profile.preferred_sports,
+                    self_declared_levels: profile.self_declared_levels,
                 },
             )
         }
@@ -120,3 +156,134 @@ async fn update_personal_info(
 
     Ok(UpdateUserInfoResponse)
 }
+
+async fn get_privacy_settings(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetPrivacySettingsResponse, ServerError> {
+    let privacy_settings =
+        PlayerController::get_privacy_settings_by_id(&model_manager, auth_token.user_id).await?;
+
+    Ok(GetPrivacySettingsResponse(privacy_settings))
+}
+
+async fn update_privacy_settings(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+    Json(new_settings): Json<UpdatePrivacySettingsPayload>,
+) -> Result<UpdatePrivacySettingsResponse, ServerError> {
+    let new_settings = crate::controllers::UpdatePrivacySettingsPayload {
+        name_visibility: new_settings.name_visibility,
+        member_since_visibility: new_settings.member_since_visibility,
+        total_bookings_visibility: new_settings.total_bookings_visibility,
+        favorite_sport_visibility: new_settings.favorite_sport_visibility,
+    };
+
+    PlayerController::update_privacy_settings_by_id(
+        &model_manager,
+        auth_token.user_id,
+        new_settings,
+    )
+    .await?;
+
+    Ok(UpdatePrivacySettingsResponse)
+}
+
+async fn get_favorites(
+    auth_token: AuthToken,
+    Paginated { pagination, .. }: Paginated,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetFavoritesResponse, ServerError> {
+    let pitches = FavoriteController::get_favorite_pitches(
+        &model_manager,
+        auth_token.user_id,
+        &pagination.page_request()?,
+    )
+    .await?
+    .map(|favorite| FavoritePitch {
+        pitch_id: favorite.pitch_id,
+        display_name: favorite.display_name,
+        sport: favorite.sport,
+        favorited_at: favorite.favorited_at,
+    });
+
+    Ok(GetFavoritesResponse::from(pitches))
+}
+
+async fn get_notifications(
+    auth_token: AuthToken,
+    Paginated { pagination, .. }: Paginated,
+    State(model_manager): State<ModelManager>,
+) -> Result<GetNotificationsResponse, ServerError> {
+    let notifications = NotificationController::get_notifications(
+        &model_manager,
+        auth_token.user_id,
+        &pagination.page_request()?,
+    )
+    .await?
+    .map(|notification| Notification {
+        id: notification.id,
+        message: notification.message,
+        created_at: notification.created_at,
+    });
+
+    Ok(GetNotificationsResponse::from(notifications))
+}
+
+async fn add_favorite_pitch(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<UpdateFavoritesResponse, ServerError> {
+    FavoriteController::add_pitch(&model_manager, auth_token.user_id, pitch_id).await?;
+
+    Ok(UpdateFavoritesResponse)
+}
+
+async fn remove_favorite_pitch(
+    auth_token: AuthToken,
+    Path(pitch_id): Path<Uuid>,
+    State(model_manager): State<ModelManager>,
+) -> Result<UpdateFavoritesResponse, ServerError> {
+    FavoriteController::remove_pitch(&model_manager, auth_token.user_id, pitch_id).await?;
+
+    Ok(UpdateFavoritesResponse)
+}
+
+/// Businesses subscribe to the bookings of their pitches, players to their own bookings
+fn get_feed_scope(auth_token: &AuthToken) -> Result<FeedScope, ServerError> {
+    match auth_token.user_role {
+        UserRole::Business | UserRole::Player => Ok(FeedScope::User(auth_token.user_id)),
+        UserRole::Admin => Err(ServerError::UserIsNotPlayer),
+    }
+}
+
+async fn get_calendar_feed(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+) -> Result<CalendarFeedResponse, ServerError> {
+    let feed =
+        CalendarFeedController::get_feed(&model_manager, get_feed_scope(&auth_token)?).await?;
+
+    Ok(into_calendar_feed_response(feed.token))
+}
+
+async fn regenerate_calendar_feed(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+) -> Result<CalendarFeedResponse, ServerError> {
+    let feed =
+        CalendarFeedController::regenerate_feed(&model_manager, get_feed_scope(&auth_token)?)
+            .await?;
+
+    Ok(into_calendar_feed_response(feed.token))
+}
+
+async fn delete_calendar_feed(
+    auth_token: AuthToken,
+    State(model_manager): State<ModelManager>,
+) -> Result<DeleteCalendarFeedResponse, ServerError> {
+    CalendarFeedController::delete_feed(&model_manager, get_feed_scope(&auth_token)?).await?;
+
+    Ok(DeleteCalendarFeedResponse)
+}
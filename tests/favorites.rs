@@ -0,0 +1,210 @@
+mod utils;
+
+use crate::utils::{login, test_get_err, test_get_ok, test_login_needed_error};
+use rand::distr::{Alphanumeric, SampleString};
+use serde_json::json;
+use uuid::Uuid;
+
+const DEV_BASE_URL: &str = "http://localhost:1948/api/v1";
+
+test_get_ok!(
+    test_name = get_favorites_ok_player,
+    user = player_1,
+    path = "/users/me/favorites",
+    status = 200,
+    response = json!({
+        "items": [
+            {
+                "pitch_id": "00000000-0000-0000-0000-000000000006",
+                "display_name": "football_pitch_1",
+                "sport": "football",
+                "favorited_at": "2025-03-02T08:00:00Z"
+            },
+            {
+                "pitch_id": "00000000-0000-0000-0000-000000000007",
+                "display_name": "football_pitch_2",
+                "sport": "football",
+                "favorited_at": "2025-03-01T08:00:00Z"
+            }
+        ],
+        "next_cursor": null,
+        "has_more": false
+    })
+);
+
+#[tokio::test]
+async fn get_favorites_ok_paginated() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = player_1);
+    let response = client
+        .do_get("/users/me/favorites?limit=1&include_total=true")
+        .await?;
+    let first_page = response.json_body()?;
+    assert_eq!(first_page["has_more"], true);
+    assert_eq!(first_page["total"], 2);
+
+    // exec
+    let cursor = first_page["next_cursor"].as_str().unwrap();
+    let response = client
+        .do_get(&format!("/users/me/favorites?limit=1&cursor={cursor}"))
+        .await?;
+
+    // check
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json_body()?,
+        json!({
+            "items": [
+                {
+                    "pitch_id": "00000000-0000-0000-0000-000000000007",
+                    "display_name": "football_pitch_2",
+                    "sport": "football",
+                    "favorited_at": "2025-03-01T08:00:00Z"
+                }
+            ],
+            "next_cursor": null,
+            "has_more": false
+        })
+    );
+
+    Ok(())
+}
+
+test_get_err!(
+    test_name = get_favorites_err_business,
+    user = business_1,
+    path = "/users/me/favorites",
+    status = 403,
+    error_message = "this_operation_is_for_players_only"
+);
+
+test_login_needed_error!(get_favorites_err_login_needed, "/users/me/favorites");
+
+// PUT/DELETE /users/me/favorites/pitches/{pitch_id} 204
+#[tokio::test]
+async fn add_and_remove_favorite_pitch_ok() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    let username = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let password = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let request_body = json!({
+        "username": username,
+        "password": password,
+        "confirm_password": password,
+        "account_type": "player",
+        "profile": {
+            "first_name": "first",
+            "last_name": "last",
+            "preferred_sports": [],
+        }
+    });
+    let response = client.do_post("/auth/signup", request_body).await?;
+    assert_eq!(response.status(), 201);
+
+    login!(client, username = username, password = password);
+
+    // exec
+    let path = "/users/me/favorites/pitches/00000000-0000-0000-0000-000000000009";
+    let response = client.do_put(path, json!(null)).await?;
+    assert_eq!(response.status(), 204);
+
+    // adding the same pitch twice is a no-op
+    let response = client.do_put(path, json!(null)).await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = client.do_get("/users/me/favorites").await?;
+    let response_body = response.json_body()?;
+    let pitch_ids: Vec<_> = response_body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|pitch| pitch["pitch_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(pitch_ids, ["00000000-0000-0000-0000-000000000009"]);
+
+    // exec
+    let response = client.do_delete(path).await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = client.do_get("/users/me/favorites").await?;
+    assert_eq!(
+        response.json_body()?,
+        json!({ "items": [], "next_cursor": null, "has_more": false })
+    );
+
+    Ok(())
+}
+
+// PUT /users/me/favorites/pitches/{pitch_id} 400
+#[tokio::test]
+async fn add_favorite_pitch_err_pitch_not_found() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = player_2);
+
+    // exec
+    let response = client
+        .do_put(
+            &format!("/users/me/favorites/pitches/{}", Uuid::new_v4()),
+            json!(null),
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check status code
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+
+    // check response body
+    let expected_body = json!({
+        "message": "pitch_not_found",
+        "request_id": serde_json::from_value::<Uuid>(response_body.get("request_id").unwrap().clone()).unwrap(),
+        "status": 400
+    });
+    assert_eq!(response_body, expected_body);
+
+    Ok(())
+}
+
+test_get_ok!(
+    test_name = get_favorites_count_ok_owner,
+    user = business_1,
+    path = "/pitches/00000000-0000-0000-0000-000000000006/favorites/count",
+    status = 200,
+    response = json!({
+        "pitch_id": "00000000-0000-0000-0000-000000000006",
+        "favorites_count": 2
+    })
+);
+
+test_get_ok!(
+    test_name = get_favorites_count_ok_admin,
+    user = admin,
+    path = "/pitches/00000000-0000-0000-0000-000000000006/favorites/count",
+    status = 200,
+    response = json!({
+        "pitch_id": "00000000-0000-0000-0000-000000000006",
+        "favorites_count": 2
+    })
+);
+
+test_get_err!(
+    test_name = get_favorites_count_err_not_owner,
+    user = business_2,
+    path = "/pitches/00000000-0000-0000-0000-000000000006/favorites/count",
+    status = 400,
+    error_message = "pitch_not_found"
+);
+
+test_get_err!(
+    test_name = get_favorites_count_err_player,
+    user = player_1,
+    path = "/pitches/00000000-0000-0000-0000-000000000006/favorites/count",
+    status = 403,
+    error_message = "this_operation_is_for_businesses_only"
+);
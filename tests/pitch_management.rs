@@ -0,0 +1,1808 @@
+// Kept apart from tests/pitches.rs: pitches created here would show up in the listings checked
+// there, and tests within one binary run concurrently.
+
+mod utils;
+
+use crate::utils::login;
+use chrono::{DateTime, Duration, DurationRound, NaiveTime, SecondsFormat, Utc};
+use rand::distr::{Alphanumeric, SampleString};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+const DEV_BASE_URL: &str = "http://localhost:1948/api/v1";
+
+fn check_error_body(response_body: &Value, status: u16, message: &str) {
+    let expected_body = json!({
+        "message": message,
+        "request_id": serde_json::from_value::<Uuid>(response_body.get("request_id").unwrap().clone()).unwrap(),
+        "status": status
+    });
+    assert_eq!(response_body, &expected_body);
+}
+
+// POST /pitches 201, PATCH /pitches/{pitch_id} 204, DELETE /pitches/{pitch_id} 204
+#[tokio::test]
+async fn create_update_and_delete_pitch_ok() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    let (_, display_name) = signup_business(&client).await?;
+
+    // exec
+    let request_body = json!({
+        "name": "new_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 15000, "currency": "AED" },
+        "surface": "artificial_turf",
+        "environment": "indoor",
+        "max_players": 4,
+        "amenities": ["lights", "showers"],
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check status code
+    assert_eq!(response.status(), 201, "response body:\n{response_body:#}");
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+
+    // check correct excution
+    let response = client.do_get("/pitches").await?;
+    let expected_body = json!([{
+        "id": pitch_id,
+        "name": "new_pitch",
+        "sport": "padel",
+        "pricePerHour": { "amountCents": 15000, "currency": "AED" },
+        "reviewCount": 0,
+        "surface": "artificial_turf",
+        "environment": "indoor",
+        "maxPlayers": 4,
+        "amenities": ["lights", "showers"],
+        "timezone": "UTC",
+        "ownerName": display_name,
+        "isFavorite": false
+    }]);
+    assert_eq!(response.json_body()?["items"], expected_body);
+
+    // exec
+    let request_body = json!({
+        "name": "renamed_pitch",
+        "image_url": "https://example.com/pitches/renamed_pitch.png",
+        "description": "covered court",
+        "amenities": ["water"],
+        "location": {
+            "address_line_1": "Street 1",
+            "city": "Ajman",
+            "country": "UAE",
+            "latitude": 25.4052,
+            "longitude": 55.5136,
+        },
+        "timezone": "Asia/Dubai",
+    });
+    let response = client
+        .do_patch(&format!("/pitches/{pitch_id}"), request_body)
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = client.do_get("/pitches").await?;
+    let expected_body = json!([{
+        "id": pitch_id,
+        "name": "renamed_pitch",
+        "sport": "padel",
+        "pricePerHour": { "amountCents": 15000, "currency": "AED" },
+        "reviewCount": 0,
+        "imageUrl": "https://example.com/pitches/renamed_pitch.png",
+        "surface": "artificial_turf",
+        "environment": "indoor",
+        "description": "covered court",
+        "maxPlayers": 4,
+        "amenities": ["water"],
+        "timezone": "Asia/Dubai",
+        "location": "Ajman, UAE",
+        "address": "Street 1",
+        "latitude": 25.4052,
+        "longitude": 55.5136,
+        "ownerName": display_name,
+        "isFavorite": false
+    }]);
+    assert_eq!(response.json_body()?["items"], expected_body);
+
+    // exec
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = client.do_get("/pitches").await?;
+    assert_eq!(response.json_body()?["items"], json!([]));
+
+    Ok(())
+}
+
+async fn signup_business(client: &httpc_test::Client) -> anyhow::Result<(Uuid, String)> {
+    let username = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let password = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let display_name = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let request_body = json!({
+        "username": username,
+        "password": password,
+        "confirm_password": password,
+        "account_type": "business",
+        "profile": {
+            "display_name": display_name,
+        }
+    });
+    let response = client.do_post("/auth/signup", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let business_id = response.json_value::<Uuid>("/user_id")?;
+
+    login!(client, username = username, password = password);
+
+    Ok((business_id, display_name))
+}
+
+// POST /pitches 201, PUT/DELETE /pitches/{pitch_id}/hidden 204, DELETE /pitches/{pitch_id} 204
+#[tokio::test]
+async fn admin_manages_pitch_of_business_ok() -> anyhow::Result<()> {
+    let business_client = httpc_test::new_client(DEV_BASE_URL)?;
+    let admin_client = httpc_test::new_client(DEV_BASE_URL)?;
+    let guest_client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    let (business_id, display_name) = signup_business(&business_client).await?;
+    login!(admin_client, user = admin);
+
+    // exec
+    let request_body = json!({
+        "owner_id": business_id,
+        "name": "admin_pitch",
+        "sport": "football",
+        "price_per_hour": { "amountCents": 9000, "currency": "USD" },
+    });
+    let response = admin_client.do_post("/pitches", request_body).await?;
+    let response_body = response.json_body()?;
+    assert_eq!(response.status(), 201, "response body:\n{response_body:#}");
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+
+    let response = admin_client
+        .do_put(&format!("/pitches/{pitch_id}/hidden"), json!(null))
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let expected_pitch = json!({
+        "id": pitch_id,
+        "name": "admin_pitch",
+        "sport": "football",
+        "pricePerHour": { "amountCents": 9000, "currency": "USD" },
+        "reviewCount": 0,
+        "amenities": [],
+        "timezone": "UTC",
+        "ownerName": display_name,
+        "isFavorite": false,
+        "status": "hidden"
+    });
+    let response = admin_client
+        .do_get(&format!("/pitches?business_id={business_id}"))
+        .await?;
+    assert_eq!(response.json_body()?["items"], json!([expected_pitch]));
+
+    let response = business_client.do_get("/pitches").await?;
+    assert_eq!(response.json_body()?["items"], json!([expected_pitch]));
+
+    let response = guest_client.do_get("/pitches").await?;
+    let response_body = response.json_body()?;
+    assert!(
+        response_body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|pitch| pitch["id"] != json!(pitch_id)),
+        "response body:\n{response_body:#}"
+    );
+
+    // exec
+    let response = admin_client
+        .do_delete(&format!("/pitches/{pitch_id}/hidden"))
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = guest_client.do_get("/pitches").await?;
+    let response_body = response.json_body()?;
+    assert!(
+        response_body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|pitch| pitch["id"] == json!(pitch_id)),
+        "response body:\n{response_body:#}"
+    );
+
+    // exec
+    let response = admin_client
+        .do_delete(&format!("/pitches/{pitch_id}"))
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = business_client.do_get("/users/me/notifications").await?;
+    let response_body = response.json_body()?;
+    let messages: Vec<_> = response_body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|notification| notification["message"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            r#"an admin deleted your pitch "admin_pitch""#,
+            r#"an admin unhid your pitch "admin_pitch""#,
+            r#"an admin hid your pitch "admin_pitch""#,
+            r#"an admin created your pitch "admin_pitch""#,
+        ]
+    );
+
+    Ok(())
+}
+
+// POST /pitches 400
+#[tokio::test]
+async fn create_pitch_err_admin_without_owner() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = admin);
+
+    // exec
+    let request_body = json!({
+        "name": "ownerless_pitch",
+        "sport": "football",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "pitch_owner_is_required");
+
+    Ok(())
+}
+
+// POST /pitches 403
+#[tokio::test]
+async fn create_pitch_err_business_for_another_owner() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let request_body = json!({
+        "owner_id": "00000000-0000-0000-0000-000000000004",
+        "name": "someone_elses_pitch",
+        "sport": "football",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 403, "response body:\n{response_body:#}");
+    check_error_body(
+        &response_body,
+        403,
+        "this_operation_is_for_the_pitch_owner_only",
+    );
+
+    Ok(())
+}
+
+// PUT /pitches/{pitch_id}/hidden 403
+#[tokio::test]
+async fn hide_pitch_err_business() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let response = client
+        .do_put(
+            "/pitches/00000000-0000-0000-0000-000000000007/hidden",
+            json!(null),
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 403, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 403, "this_operation_is_for_admins_only");
+
+    Ok(())
+}
+
+// POST /pitches 409
+#[tokio::test]
+async fn create_pitch_err_display_name_already_exists() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let request_body = json!({
+        "name": "football_pitch_1",
+        "sport": "football",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 409, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 409, "pitch_display_name_already_exists");
+
+    Ok(())
+}
+
+// POST /pitches 403
+#[tokio::test]
+async fn create_pitch_err_player() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = player_1);
+
+    // exec
+    let request_body = json!({
+        "name": "players_pitch",
+        "sport": "football",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 403, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 403, "this_operation_is_for_businesses_only");
+
+    Ok(())
+}
+
+// PATCH /pitches/{pitch_id} 409
+#[tokio::test]
+async fn update_pitch_err_display_name_already_exists() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let response = client
+        .do_patch(
+            "/pitches/00000000-0000-0000-0000-000000000007",
+            json!({ "name": "football_pitch_1" }),
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 409, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 409, "pitch_display_name_already_exists");
+
+    Ok(())
+}
+
+// PATCH /pitches/{pitch_id} 400
+#[tokio::test]
+async fn update_pitch_err_not_owner() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_2);
+
+    // exec
+    let response = client
+        .do_patch(
+            "/pitches/00000000-0000-0000-0000-000000000006",
+            json!({ "name": "stolen_pitch" }),
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "pitch_not_found");
+
+    Ok(())
+}
+
+// PATCH /pitches/{pitch_id} 400
+#[tokio::test]
+async fn update_pitch_err_price_in_another_currency() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let response = client
+        .do_patch(
+            "/pitches/00000000-0000-0000-0000-000000000007",
+            json!({ "price_per_hour": { "amountCents": 10000, "currency": "USD" } }),
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "price_currency_must_match_the_pitch");
+
+    Ok(())
+}
+
+// POST /pitches 400
+#[tokio::test]
+async fn create_pitch_err_price_out_of_range() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let request_body = json!({
+        "name": Alphanumeric.sample_string(&mut rand::rng(), 16),
+        "sport": "football",
+        "price_per_hour": { "amountCents": 1_000_000_000_000_i64, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "price_is_out_of_range");
+
+    Ok(())
+}
+
+// PATCH /pitches/{pitch_id} 400
+#[tokio::test]
+async fn update_pitch_err_negative_price() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let response = client
+        .do_patch(
+            "/pitches/00000000-0000-0000-0000-000000000007",
+            json!({ "price_per_hour": { "amountCents": -1, "currency": "AED" } }),
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "price_must_not_be_negative");
+
+    Ok(())
+}
+
+// POST /pitches 400
+#[tokio::test]
+async fn create_pitch_err_unknown_amenity() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let request_body = json!({
+        "name": Alphanumeric.sample_string(&mut rand::rng(), 16),
+        "sport": "football",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+        "amenities": ["jacuzzi"],
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "unknown_amenity");
+
+    Ok(())
+}
+
+// PATCH /pitches/{pitch_id} 400
+#[tokio::test]
+async fn update_pitch_err_invalid_max_players() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let response = client
+        .do_patch(
+            "/pitches/00000000-0000-0000-0000-000000000007",
+            json!({ "max_players": 0 }),
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "max_players_must_be_positive");
+
+    Ok(())
+}
+
+// PATCH /pitches/{pitch_id} 400
+#[tokio::test]
+async fn update_pitch_err_invalid_coordinates() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let request_body = json!({
+        "location": {
+            "address_line_1": "nowhere",
+            "city": "nowhere",
+            "country": "nowhere",
+            "latitude": 91.0,
+            "longitude": 0.0,
+        },
+    });
+    let response = client
+        .do_patch(
+            "/pitches/00000000-0000-0000-0000-000000000007",
+            request_body,
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "invalid_coordinates");
+
+    Ok(())
+}
+
+// DELETE /pitches/{pitch_id} 400
+#[tokio::test]
+async fn delete_pitch_err_not_owner() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_2);
+
+    // exec
+    let response = client
+        .do_delete("/pitches/00000000-0000-0000-0000-000000000007")
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "pitch_not_found");
+
+    Ok(())
+}
+
+// DELETE /pitches/{pitch_id} 409
+#[tokio::test]
+async fn delete_pitch_err_pitch_has_bookings() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let response = client
+        .do_delete("/pitches/00000000-0000-0000-0000-000000000006")
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 409, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 409, "pitch_has_bookings");
+
+    Ok(())
+}
+
+// POST /pitches 201, PUT /pitches/{pitch_id}/status 204 and 409
+#[tokio::test]
+async fn pitch_lifecycle_ok() -> anyhow::Result<()> {
+    let business_client = httpc_test::new_client(DEV_BASE_URL)?;
+    let guest_client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&business_client).await?;
+
+    // exec
+    let request_body = json!({
+        "name": "draft_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 9000, "currency": "AED" },
+        "is_draft": true,
+    });
+    let response = business_client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+
+    // check correct excution
+    let response = business_client.do_get("/pitches").await?;
+    assert_eq!(response.json_body()?["items"][0]["status"], "draft");
+
+    let response = guest_client
+        .do_get(&format!("/pitches/{pitch_id}/closures"))
+        .await?;
+    let response_body = response.json_body()?;
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "pitch_not_found");
+
+    // exec
+    let response = business_client
+        .do_put(
+            &format!("/pitches/{pitch_id}/status"),
+            json!({ "status": "published" }),
+        )
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = business_client.do_get("/pitches").await?;
+    assert_eq!(response.json_body()?["items"][0].get("status"), None);
+
+    let response = guest_client
+        .do_get(&format!("/pitches/{pitch_id}/closures"))
+        .await?;
+    assert_eq!(response.json_body()?, json!({ "closures": [] }));
+
+    // exec
+    let response = business_client
+        .do_put(
+            &format!("/pitches/{pitch_id}/status"),
+            json!({ "status": "draft" }),
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 409, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 409, "pitch_status_transition_not_allowed");
+
+    // clean
+    let response = business_client
+        .do_delete(&format!("/pitches/{pitch_id}"))
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// POST /pitches/{pitch_id}/closures 201 and 409, GET /pitches/{pitch_id}/closures 200,
+// DELETE /pitches/{pitch_id}/closures/{closure_id} 204
+#[tokio::test]
+async fn close_pitch_ok() -> anyhow::Result<()> {
+    let business_client = httpc_test::new_client(DEV_BASE_URL)?;
+    let guest_client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&business_client).await?;
+    let request_body = json!({
+        "name": "closed_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 9000, "currency": "AED" },
+        "timezone": "Asia/Dubai",
+    });
+    let response = business_client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+
+    // exec
+    let closure = json!({
+        "starts_at": "2030-01-01T00:00:00Z",
+        "ends_at": "2030-01-08T00:00:00Z",
+        "reason": "  new turf  ",
+    });
+    let response = business_client
+        .do_post(&format!("/pitches/{pitch_id}/closures"), closure)
+        .await?;
+    let response_body = response.json_body()?;
+    assert_eq!(response.status(), 201, "response body:\n{response_body:#}");
+    let closure_id = response.json_value::<Uuid>("/closure_id")?;
+
+    // check correct excution
+    assert_eq!(
+        response_body,
+        json!({
+            "closure_id": closure_id,
+            "is_confirmed": true,
+            "affected_bookings": []
+        })
+    );
+
+    let response = business_client.do_get("/pitches").await?;
+    assert_eq!(
+        response.json_body()?["items"][0]["closure"],
+        json!({
+            "startsAt": "2030-01-01T04:00:00+04:00",
+            "endsAt": "2030-01-08T04:00:00+04:00",
+            "reason": "new turf"
+        })
+    );
+
+    let response = guest_client
+        .do_get(&format!("/pitches/{pitch_id}/closures"))
+        .await?;
+    assert_eq!(
+        response.json_body()?,
+        json!({
+            "closures": [
+                {
+                    "id": closure_id,
+                    "starts_at": "2030-01-01T04:00:00+04:00",
+                    "ends_at": "2030-01-08T04:00:00+04:00",
+                    "reason": "new turf",
+                    "is_confirmed": true
+                }
+            ]
+        })
+    );
+
+    // exec
+    let closure = json!({
+        "starts_at": "2030-01-07T00:00:00Z",
+        "ends_at": "2030-01-09T00:00:00Z",
+        "reason": "painting",
+    });
+    let response = business_client
+        .do_post(&format!("/pitches/{pitch_id}/closures"), closure)
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 409, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 409, "closure_overlaps_another_closure");
+
+    // exec
+    let response = business_client
+        .do_delete(&format!("/pitches/{pitch_id}/closures/{closure_id}"))
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = guest_client
+        .do_get(&format!("/pitches/{pitch_id}/closures"))
+        .await?;
+    assert_eq!(response.json_body()?, json!({ "closures": [] }));
+
+    // clean
+    let response = business_client
+        .do_delete(&format!("/pitches/{pitch_id}"))
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// POST /pitches/{pitch_id}/closures 400
+#[tokio::test]
+async fn close_pitch_err_period_in_the_past() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let closure = json!({
+        "starts_at": "2025-03-20T00:00:00Z",
+        "ends_at": "2025-03-21T00:00:00Z",
+        "reason": "maintenance",
+    });
+    let response = client
+        .do_post(
+            "/pitches/00000000-0000-0000-0000-000000000006/closures",
+            closure,
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(
+        &response_body,
+        400,
+        "closure_must_end_after_it_starts_and_in_the_future",
+    );
+
+    Ok(())
+}
+
+// PUT /pitches/{pitch_id}/opening-hours 204
+// GET /pitches/{pitch_id}/opening-hours/effective 200
+#[tokio::test]
+async fn set_opening_hours_ok() -> anyhow::Result<()> {
+    let business_client = httpc_test::new_client(DEV_BASE_URL)?;
+    let guest_client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&business_client).await?;
+    let request_body = json!({
+        "name": "opening_hours_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 9000, "currency": "AED" },
+    });
+    let response = business_client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+
+    // exec
+    let opening_hours = json!({
+        "timezone": "Asia/Dubai",
+        "weekly": [
+            { "weekday": "tuesday", "opens_at": "16:00", "closes_at": "24:00" },
+            { "weekday": "tuesday", "opens_at": "06:00", "closes_at": "12:00" },
+        ],
+        "exceptions": [
+            { "date": "2030-01-08", "reason": " holiday " },
+        ],
+    });
+    let response = business_client
+        .do_put(&format!("/pitches/{pitch_id}/opening-hours"), opening_hours)
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = guest_client
+        .do_get(&format!("/pitches/{pitch_id}/opening-hours"))
+        .await?;
+    assert_eq!(
+        response.json_body()?,
+        json!({
+            "timezone": "Asia/Dubai",
+            "weekly": [
+                { "weekday": "tuesday", "opens_at": "06:00", "closes_at": "12:00" },
+                { "weekday": "tuesday", "opens_at": "16:00", "closes_at": "24:00" },
+            ],
+            "exceptions": [
+                { "date": "2030-01-08", "opens_at": null, "closes_at": null, "reason": "holiday" },
+            ]
+        })
+    );
+
+    let response = guest_client
+        .do_get(&format!(
+            "/pitches/{pitch_id}/opening-hours/effective?from=2030-01-07&to=2030-01-15"
+        ))
+        .await?;
+    let response_body = response.json_body()?;
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+    assert_eq!(response_body["timezone"], "Asia/Dubai");
+    let days = response_body["days"].as_array().unwrap();
+    assert_eq!(days.len(), 9);
+    assert_eq!(
+        days[1],
+        json!({ "date": "2030-01-08", "is_open": false, "intervals": [], "reason": "holiday" })
+    );
+    assert_eq!(
+        days[8],
+        json!({
+            "date": "2030-01-15",
+            "is_open": true,
+            "intervals": [
+                { "opens_at": "2030-01-15T06:00:00+04:00", "closes_at": "2030-01-15T12:00:00+04:00" },
+                { "opens_at": "2030-01-15T16:00:00+04:00", "closes_at": "2030-01-16T00:00:00+04:00" },
+            ],
+            "reason": null
+        })
+    );
+
+    // clean
+    let response = business_client
+        .do_delete(&format!("/pitches/{pitch_id}"))
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// PUT /pitches/{pitch_id}/opening-hours 400
+#[tokio::test]
+async fn set_opening_hours_err_overlapping_intervals() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let opening_hours = json!({
+        "timezone": "Asia/Dubai",
+        "weekly": [
+            { "weekday": "friday", "opens_at": "08:00", "closes_at": "14:00" },
+            { "weekday": "friday", "opens_at": "13:00", "closes_at": "20:00" },
+        ],
+    });
+    let response = client
+        .do_put(
+            "/pitches/00000000-0000-0000-0000-000000000006/opening-hours",
+            opening_hours,
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(
+        &response_body,
+        400,
+        "opening_hours_must_close_after_they_open_and_not_overlap",
+    );
+
+    Ok(())
+}
+
+// POST /facilities 201, PUT /facilities/{facility_id}/units/{pitch_id} 204,
+// GET /facilities/{facility_id} 200, DELETE /facilities/{facility_id} 204
+#[tokio::test]
+async fn manage_facility_ok() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    let (business_id, _) = signup_business(&client).await?;
+    let mut pitch_ids = Vec::new();
+    for name in ["half_a", "half_b", "full"] {
+        let request_body = json!({
+            "name": name,
+            "sport": "football",
+            "other_sports": ["basketball", "football"],
+            "price_per_hour": { "amountCents": 9000, "currency": "AED" },
+            "is_draft": true,
+        });
+        let response = client.do_post("/pitches", request_body).await?;
+        assert_eq!(response.status(), 201);
+        pitch_ids.push(response.json_value::<Uuid>("/pitch_id")?);
+    }
+    let (half_a, half_b, full) = (pitch_ids[0], pitch_ids[1], pitch_ids[2]);
+
+    // exec
+    let response = client
+        .do_post("/facilities", json!({ "name": "sports_park" }))
+        .await?;
+    assert_eq!(response.status(), 201);
+    let facility_id = response.json_value::<Uuid>("/facility_id")?;
+    for (pitch_id, component_ids) in [
+        (half_a, json!([])),
+        (half_b, json!([])),
+        (full, json!([half_a, half_b])),
+    ] {
+        let response = client
+            .do_put(
+                &format!("/facilities/{facility_id}/units/{pitch_id}"),
+                json!({ "component_ids": component_ids }),
+            )
+            .await?;
+        assert_eq!(response.status(), 204);
+    }
+
+    // check correct excution
+    let response = client.do_get(&format!("/facilities/{facility_id}")).await?;
+    let mut component_ids = vec![half_a, half_b];
+    component_ids.sort_unstable();
+    assert_eq!(
+        response.json_body()?,
+        json!({
+            "id": facility_id,
+            "owner_id": business_id,
+            "name": "sports_park",
+            "units": [
+                {
+                    "id": full,
+                    "name": "full",
+                    "sport": "football",
+                    "other_sports": ["basketball"],
+                    "status": "draft",
+                    "component_ids": component_ids,
+                },
+                {
+                    "id": half_a,
+                    "name": "half_a",
+                    "sport": "football",
+                    "other_sports": ["basketball"],
+                    "status": "draft",
+                    "component_ids": [],
+                },
+                {
+                    "id": half_b,
+                    "name": "half_b",
+                    "sport": "football",
+                    "other_sports": ["basketball"],
+                    "status": "draft",
+                    "component_ids": [],
+                },
+            ]
+        })
+    );
+
+    // exec
+    let response = client
+        .do_put(
+            &format!("/facilities/{facility_id}/units/{half_a}"),
+            json!({ "component_ids": [full] }),
+        )
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(
+        &response_body,
+        400,
+        "components_must_be_other_units_of_the_facility_without_cycles",
+    );
+
+    // clean
+    let response = client
+        .do_delete(&format!("/facilities/{facility_id}"))
+        .await?;
+    assert_eq!(response.status(), 204);
+    for pitch_id in pitch_ids {
+        let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+        assert_eq!(response.status(), 204);
+    }
+
+    Ok(())
+}
+
+// POST /timeslots 201, GET /pitches/{pitch_id}/timeslots 200, PATCH /timeslots/{timeslot_id} 204,
+// DELETE /timeslots/{timeslot_id} 204
+#[tokio::test]
+async fn manage_timeslots_ok() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "timeslots_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 9000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+    let starts_at = (Utc::now() + Duration::days(1)).duration_trunc(Duration::hours(1))?;
+
+    // exec
+    let request_body = json!({
+        "pitch_id": pitch_id,
+        "starts_at": starts_at,
+        "ends_at": starts_at + Duration::hours(1),
+    });
+    let response = client.do_post("/timeslots", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let timeslot_id = response.json_value::<Uuid>("/timeslot_id")?;
+    let response = client
+        .do_patch(
+            &format!("/timeslots/{timeslot_id}"),
+            json!({ "ends_at": starts_at + Duration::minutes(90) }),
+        )
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = client
+        .do_get(&format!("/pitches/{pitch_id}/timeslots"))
+        .await?;
+    assert_eq!(response.status(), 200);
+    let timeslots = response.json_value::<Vec<Value>>("/items")?;
+    assert_eq!(timeslots.len(), 1);
+    assert_eq!(timeslots[0]["id"], json!(timeslot_id));
+    assert_eq!(
+        serde_json::from_value::<DateTime<Utc>>(timeslots[0]["ends_at"].clone())?,
+        starts_at + Duration::minutes(90)
+    );
+    assert_eq!(timeslots[0]["is_booked"], json!(false));
+    assert_eq!(
+        timeslots[0]["price"],
+        json!({ "amountCents": 13500, "currency": "AED" })
+    );
+    assert_eq!(timeslots[0]["has_custom_price"], json!(false));
+
+    // exec
+    let request_body = json!({
+        "pitch_id": pitch_id,
+        "starts_at": starts_at + Duration::hours(1),
+        "ends_at": starts_at + Duration::hours(2),
+    });
+    let response = client.do_post("/timeslots", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 409, "response body:\n{response_body:#}");
+    assert_eq!(
+        response_body["message"],
+        json!("timeslot_overlaps_another_timeslot")
+    );
+    assert_eq!(
+        response_body["details"],
+        json!({ "conflicting_timeslot_id": timeslot_id })
+    );
+
+    // clean
+    let response = client
+        .do_delete(&format!("/timeslots/{timeslot_id}"))
+        .await?;
+    assert_eq!(response.status(), 204);
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// POST /timeslots 400
+#[tokio::test]
+async fn create_timeslot_err_in_the_past() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "timeslots_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 9000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+    let starts_at = Utc::now() - Duration::hours(3);
+
+    // exec
+    let request_body = json!({
+        "pitch_id": pitch_id,
+        "starts_at": starts_at,
+        "ends_at": starts_at + Duration::hours(1),
+    });
+    let response = client.do_post("/timeslots", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(&response_body, 400, "timeslot_is_in_the_past");
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// POST /pitches/{pitch_id}/timeslot-rules 201, GET /pitches/{pitch_id}/timeslot-rules 200,
+// DELETE /pitches/{pitch_id}/timeslot-rules/{rule_id} 204
+#[tokio::test]
+async fn manage_timeslot_rules_ok() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "recurring_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 9000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+    let tomorrow = Utc::now().date_naive() + Duration::days(1);
+
+    // exec
+    let request_body = json!({
+        "weekdays": ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"],
+        "starts_at": "17:00",
+        "ends_at": "23:00",
+        "slot_minutes": 90,
+        "starts_on": tomorrow,
+        "until": tomorrow + Duration::days(1),
+    });
+    let response = client
+        .do_post(&format!("/pitches/{pitch_id}/timeslot-rules"), request_body)
+        .await?;
+    assert_eq!(response.status(), 201);
+    let rule_id = response.json_value::<Uuid>("/rule_id")?;
+
+    // check correct excution
+    assert_eq!(response.json_value::<i64>("/created_timeslots")?, 8);
+    let response = client
+        .do_get(&format!("/pitches/{pitch_id}/timeslot-rules"))
+        .await?;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json_value::<Uuid>("/rules/0/id")?, rule_id);
+    assert_eq!(response.json_value::<String>("/rules/0/ends_at")?, "23:00");
+    let response = client
+        .do_get(&format!("/pitches/{pitch_id}/timeslots"))
+        .await?;
+    let timeslots = response.json_value::<Vec<Value>>("/items")?;
+    assert_eq!(timeslots.len(), 8);
+    assert!(
+        timeslots
+            .iter()
+            .all(|timeslot| timeslot["rule_id"] == json!(rule_id))
+    );
+
+    // exec
+    let response = client
+        .do_delete(&format!("/pitches/{pitch_id}/timeslot-rules/{rule_id}"))
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = client
+        .do_get(&format!("/pitches/{pitch_id}/timeslots"))
+        .await?;
+    assert_eq!(response.json_value::<Vec<Value>>("/items")?.len(), 0);
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// POST /timeslots/bulk 200
+#[tokio::test]
+async fn bulk_timeslots_ok_best_effort() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "bulk_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 9000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+    let starts_at = (Utc::now() + Duration::days(1)).duration_trunc(Duration::hours(1))?;
+
+    // exec
+    let request_body = json!({
+        "mode": "best_effort",
+        "operations": [
+            {
+                "op": "create",
+                "pitch_id": pitch_id,
+                "starts_at": starts_at,
+                "ends_at": starts_at + Duration::hours(1),
+            },
+            {
+                "op": "create",
+                "pitch_id": pitch_id,
+                "starts_at": starts_at + Duration::minutes(30),
+                "ends_at": starts_at + Duration::minutes(90),
+            },
+            { "op": "delete", "timeslot_id": Uuid::nil() },
+        ],
+    });
+    let response = client.do_post("/timeslots/bulk", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+    let timeslot_id = response.json_value::<Uuid>("/results/0/timeslot_id")?;
+    assert_eq!(
+        response_body,
+        json!({
+            "is_committed": true,
+            "results": [
+                { "status": "created", "timeslot_id": timeslot_id },
+                {
+                    "status": "failed",
+                    "error": "timeslot_overlaps_another_timeslot",
+                    "conflicting_timeslot_id": timeslot_id,
+                },
+                { "status": "failed", "timeslot_id": Uuid::nil(), "error": "timeslot_not_found" },
+            ],
+        })
+    );
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// POST /pitches/{pitch_id}/pricing-rules 201, GET /pitches/{pitch_id}/pricing-rules 200,
+// GET /pitches/{pitch_id}/price-quote 200, DELETE /pitches/{pitch_id}/pricing-rules/{rule_id} 204
+#[tokio::test]
+async fn manage_pricing_rules_ok() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "priced_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+    let tomorrow = (Utc::now().date_naive() + Duration::days(1))
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+
+    // exec
+    let request_body = json!({
+        "name": "peak",
+        "priority": 1,
+        "weekdays": ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"],
+        "starts_at": "18:00",
+        "ends_at": "22:00",
+        "adjustment_percent": 50,
+    });
+    let response = client
+        .do_post(&format!("/pitches/{pitch_id}/pricing-rules"), request_body)
+        .await?;
+    assert_eq!(response.status(), 201);
+    let rule_id = response.json_value::<Uuid>("/rule_id")?;
+    let request_body = json!({
+        "pitch_id": pitch_id,
+        "starts_at": tomorrow + Duration::hours(18),
+        "ends_at": tomorrow + Duration::hours(19),
+    });
+    let response = client.do_post("/timeslots", request_body).await?;
+    assert_eq!(response.status(), 201);
+
+    // check correct excution
+    let response = client
+        .do_get(&format!("/pitches/{pitch_id}/pricing-rules"))
+        .await?;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json_value::<Uuid>("/rules/0/id")?, rule_id);
+    assert_eq!(
+        response.json_value::<String>("/rules/0/starts_at")?,
+        "18:00"
+    );
+    let response = client
+        .do_get(&format!("/pitches/{pitch_id}/timeslots"))
+        .await?;
+    assert_eq!(
+        response.json_value::<Value>("/items/0/price")?,
+        json!({ "amountCents": 15000, "currency": "AED" })
+    );
+    let start = (tomorrow + Duration::hours(17)).to_rfc3339_opts(SecondsFormat::Secs, true);
+    let end = (tomorrow + Duration::hours(19)).to_rfc3339_opts(SecondsFormat::Secs, true);
+    let response = client
+        .do_get(&format!(
+            "/pitches/{pitch_id}/price-quote?start={start}&end={end}"
+        ))
+        .await?;
+    let response_body = response.json_body()?;
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+    assert_eq!(
+        response_body["price"],
+        json!({ "amountCents": 25000, "currency": "AED" })
+    );
+    assert_eq!(response_body["segments"][0]["rule_id"], Value::Null);
+    assert_eq!(response_body["segments"][1]["rule_id"], json!(rule_id));
+    assert_eq!(response_body["segments"][1]["rule_name"], json!("peak"));
+
+    // exec
+    let response = client
+        .do_delete(&format!("/pitches/{pitch_id}/pricing-rules/{rule_id}"))
+        .await?;
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = client
+        .do_get(&format!("/pitches/{pitch_id}/timeslots"))
+        .await?;
+    assert_eq!(
+        response.json_value::<Value>("/items/0/price")?,
+        json!({ "amountCents": 10000, "currency": "AED" })
+    );
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// GET /pitches/{pitch_id}/price-quote 200
+#[tokio::test]
+async fn get_price_quote_ok_with_offsets_of_pitch_timezone() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "dubai_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+        "timezone": "Asia/Dubai",
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+
+    // exec
+    let response = client
+        .do_get(&format!(
+            "/pitches/{pitch_id}/price-quote?start=2030-01-07T14:00:00Z&end=2030-01-07T16:00:00Z"
+        ))
+        .await?;
+
+    // check
+    let response_body = response.json_body()?;
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+    assert_eq!(
+        response_body["starts_at"],
+        json!("2030-01-07T18:00:00+04:00")
+    );
+    assert_eq!(response_body["ends_at"], json!("2030-01-07T20:00:00+04:00"));
+    assert_eq!(
+        response_body["segments"][0]["starts_at"],
+        json!("2030-01-07T18:00:00+04:00")
+    );
+    assert_eq!(
+        response_body["segments"][0]["ends_at"],
+        json!("2030-01-07T20:00:00+04:00")
+    );
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// POST /pitches/{pitch_id}/pricing-rules 400
+#[tokio::test]
+async fn create_pricing_rule_err_two_price_changes() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "priced_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+
+    // exec
+    let request_body = json!({
+        "name": "peak",
+        "priority": 1,
+        "weekdays": ["friday"],
+        "starts_at": "18:00",
+        "ends_at": "22:00",
+        "price_per_hour_cents": 15000,
+        "adjustment_percent": 50,
+    });
+    let response = client
+        .do_post(&format!("/pitches/{pitch_id}/pricing-rules"), request_body)
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    check_error_body(
+        &response_body,
+        400,
+        "pricing_rule_must_have_weekdays_ordered_ranges_and_one_price_change",
+    );
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// GET /pitches/{pitch_id}/availability 200, 304
+#[tokio::test]
+async fn get_availability_ok_not_modified() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "available_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+    let tomorrow = Utc::now().date_naive() + Duration::days(1);
+    let starts_at = tomorrow.and_time(NaiveTime::MIN).and_utc() + Duration::hours(18);
+    let request_body = json!({
+        "pitch_id": pitch_id,
+        "starts_at": starts_at,
+        "ends_at": starts_at + Duration::hours(1),
+    });
+    let response = client.do_post("/timeslots", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let url = format!("/pitches/{pitch_id}/availability?from={tomorrow}&to={tomorrow}&tz=UTC");
+
+    // exec
+    let response = client.do_get(&url).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+    assert_eq!(
+        response_body,
+        json!({
+            "timezone": "UTC",
+            "days": [{
+                "date": tomorrow,
+                "intervals": [{
+                    "kind": "free",
+                    "starts_at": starts_at,
+                    "ends_at": starts_at + Duration::hours(1),
+                }],
+            }],
+        })
+    );
+    let etag = response.header("etag").expect("etag header is missing");
+
+    // exec
+    let response = client
+        .reqwest_client()
+        .get(format!("{DEV_BASE_URL}{url}"))
+        .header("If-None-Match", &etag)
+        .send()
+        .await?;
+
+    // check
+    assert_eq!(response.status(), 304);
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// PUT /pitches/{pitch_id}/calendar-feed 200, GET /calendar-feeds/{token}.ics 200, 400
+#[tokio::test]
+async fn regenerate_calendar_feed_ok_revokes_previous_link() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+    let subscriber = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "subscribed_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+    let url = format!("/pitches/{pitch_id}/calendar-feed");
+    let response = client.do_put(&url, json!(null)).await?;
+    assert_eq!(response.status(), 200);
+    let leaked_path = response.json_value::<String>("/path")?;
+
+    // exec
+    let response = client.do_put(&url, json!(null)).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+    let token = response.json_value::<String>("/token")?;
+    assert_eq!(
+        response_body["path"],
+        format!("/calendar-feeds/{token}.ics")
+    );
+
+    // exec
+    let response = subscriber
+        .do_get(&format!("/calendar-feeds/{token}.ics"))
+        .await?;
+
+    // check
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.header("content-type").as_deref(),
+        Some("text/calendar; charset=utf-8")
+    );
+    let ics = response.text_body()?;
+    assert!(
+        ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"),
+        "ics:\n{ics}"
+    );
+    assert!(
+        ics.contains("\r\nX-WR-CALNAME:subscribed_pitch\r\n"),
+        "ics:\n{ics}"
+    );
+    assert!(ics.ends_with("END:VCALENDAR\r\n"), "ics:\n{ics}");
+
+    // exec
+    let response = subscriber.do_get(&leaked_path).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    check_error_body(&response_body, 400, "calendar_feed_not_found");
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// POST /timeslots/import 200, 415
+#[tokio::test]
+async fn import_timeslots_ok_dry_run_then_apply() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+    let request_body = json!({
+        "name": "imported_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+    let starts_at = (Utc::now().date_naive() + Duration::days(1))
+        .and_time(NaiveTime::MIN)
+        .and_utc()
+        + Duration::hours(18);
+    let ends_at = starts_at + Duration::hours(1);
+    let ics = [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "BEGIN:VEVENT",
+        &format!("DTSTART:{}", starts_at.format("%Y%m%dT%H%M%SZ")),
+        &format!("DTEND:{}", ends_at.format("%Y%m%dT%H%M%SZ")),
+        "END:VEVENT",
+        "BEGIN:VEVENT",
+        "DTSTART;VALUE=DATE:20300101",
+        "END:VEVENT",
+        "END:VCALENDAR",
+    ]
+    .join("\r\n");
+    let import = async |apply: bool, content_type: &str| {
+        client
+            .reqwest_client()
+            .post(format!(
+                "{DEV_BASE_URL}/timeslots/import?pitch_id={pitch_id}&apply={apply}"
+            ))
+            .header("Content-Type", content_type)
+            .body(ics.clone())
+            .send()
+            .await
+    };
+
+    // exec
+    let response = import(false, "application/json").await?;
+
+    // check
+    assert_eq!(response.status(), 415);
+
+    // exec
+    let response = import(false, "text/calendar").await?;
+
+    // check
+    assert_eq!(response.status(), 200);
+    let expected_timeslot = json!({
+        "line": 3,
+        "pitch_id": pitch_id,
+        "starts_at": starts_at,
+        "ends_at": ends_at,
+    });
+    let expected_error = json!({ "line": 7, "message": "all_day_events_cannot_be_imported" });
+    assert_eq!(
+        response.json::<Value>().await?,
+        json!({
+            "is_applied": false,
+            "creates": [expected_timeslot],
+            "conflicts": [],
+            "skips": [],
+            "errors": [expected_error],
+        })
+    );
+
+    // exec
+    let response = import(true, "text/calendar; charset=utf-8").await?;
+
+    // check
+    assert_eq!(response.status(), 200);
+    let response_body = response.json::<Value>().await?;
+    assert_eq!(response_body["is_applied"], true);
+    let timeslot_id = &response_body["creates"][0]["timeslot_id"];
+    assert!(timeslot_id.is_string(), "response body:\n{response_body:#}");
+
+    // exec
+    let response = import(false, "text/calendar").await?;
+
+    // check
+    let response_body = response.json::<Value>().await?;
+    assert_eq!(response_body["creates"], json!([]));
+    assert_eq!(&response_body["skips"][0]["timeslot_id"], timeslot_id);
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
+
+// POST /pitches 400 201, GET /pitches/{pitch_id}/timeslots 200
+#[tokio::test]
+async fn get_pitch_timeslots_ok_with_offsets_of_pitch_timezone() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    signup_business(&client).await?;
+
+    // exec
+    let request_body = json!({
+        "name": "berlin_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+        "timezone": "Europe/Atlantis",
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    check_error_body(&response_body, 400, "unknown_timezone");
+
+    // prepare
+    let request_body = json!({
+        "name": "berlin_pitch",
+        "sport": "padel",
+        "price_per_hour": { "amountCents": 10000, "currency": "AED" },
+        "timezone": "Europe/Berlin",
+    });
+    let response = client.do_post("/pitches", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let pitch_id = response.json_value::<Uuid>("/pitch_id")?;
+    // the clocks go from 02:00 to 03:00 during the timeslot
+    let request_body = json!({
+        "pitch_id": pitch_id,
+        "starts_at": "2030-03-31T00:30:00Z",
+        "ends_at": "2030-03-31T01:30:00Z",
+    });
+    let response = client.do_post("/timeslots", request_body).await?;
+    assert_eq!(response.status(), 201);
+
+    // exec
+    let response = client
+        .do_get(&format!("/pitches/{pitch_id}/timeslots"))
+        .await?;
+
+    // check
+    assert_eq!(response.status(), 200);
+    let timeslots = response.json_value::<Vec<Value>>("/items")?;
+    assert_eq!(timeslots.len(), 1);
+    assert_eq!(
+        timeslots[0]["starts_at"],
+        json!("2030-03-31T01:30:00+01:00")
+    );
+    assert_eq!(timeslots[0]["ends_at"], json!("2030-03-31T03:30:00+02:00"));
+    assert_eq!(timeslots[0]["timezone"], json!("Europe/Berlin"));
+
+    // clean
+    let response = client.do_delete(&format!("/pitches/{pitch_id}")).await?;
+    assert_eq!(response.status(), 204);
+
+    Ok(())
+}
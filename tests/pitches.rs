@@ -1,73 +1,172 @@
 mod utils;
 
-use crate::utils::{login, test_get_ok};
-use serde_json::json;
+use crate::utils::{login, test_get_err, test_get_ok};
+use serde_json::{Value, json};
+use uuid::Uuid;
 
 const DEV_BASE_URL: &str = "http://localhost:1948/api/v1";
 
+fn football_pitch_1(is_favorite: bool) -> Value {
+    json!({
+        "id": "00000000-0000-0000-0000-000000000006",
+        "name": "football_pitch_1",
+        "sport": "football",
+        "pricePerHour": { "amountCents": 25000, "currency": "AED" },
+        "rating": 4.5,
+        "reviewCount": 2,
+        "imageUrl": "https://example.com/pitches/football_pitch_1.png",
+        "surface": "artificial_turf",
+        "environment": "outdoor",
+        "description": "five-a-side pitch next to the lake",
+        "maxPlayers": 10,
+        "amenities": ["lights", "water"],
+        "timezone": "Asia/Dubai",
+        "location": "Sharjah, UAE",
+        "address": "Al Majaz Waterfront",
+        "latitude": 25.3275,
+        "longitude": 55.3862,
+        "ownerName": "business_1_display",
+        "ownerAvatar": "https://example.com/avatars/business_1.png",
+        "isFavorite": is_favorite
+    })
+}
+
+fn football_pitch_2(is_favorite: bool) -> Value {
+    json!({
+        "id": "00000000-0000-0000-0000-000000000007",
+        "name": "football_pitch_2",
+        "sport": "football",
+        "pricePerHour": { "amountCents": 20000, "currency": "AED" },
+        "reviewCount": 0,
+        "surface": "grass",
+        "environment": "outdoor",
+        "maxPlayers": 22,
+        "amenities": [],
+        "timezone": "Asia/Dubai",
+        "location": "Sharjah, UAE",
+        "address": "University City Road, Gate 3",
+        "latitude": 25.2866,
+        "longitude": 55.4799,
+        "ownerName": "business_1_display",
+        "ownerAvatar": "https://example.com/avatars/business_1.png",
+        "isFavorite": is_favorite
+    })
+}
+
+fn basketball_pitch_1(is_favorite: bool) -> Value {
+    json!({
+        "id": "00000000-0000-0000-0000-000000000008",
+        "name": "basketball_pitch_1",
+        "sport": "basketball",
+        "pricePerHour": { "amountCents": 12050, "currency": "AED" },
+        "rating": 2.0,
+        "reviewCount": 1,
+        "surface": "wood",
+        "environment": "indoor",
+        "maxPlayers": 10,
+        "amenities": ["parking"],
+        "timezone": "Asia/Dubai",
+        "location": "Dubai, UAE",
+        "address": "Al Wasl Road",
+        "latitude": 25.1972,
+        "longitude": 55.2744,
+        "ownerName": "business_2_display",
+        "isFavorite": is_favorite
+    })
+}
+
+fn padel_pitch_1(is_favorite: bool) -> Value {
+    json!({
+        "id": "00000000-0000-0000-0000-000000000009",
+        "name": "padel_pitch_1",
+        "sport": "padel",
+        "pricePerHour": { "amountCents": 18000, "currency": "AED" },
+        "reviewCount": 0,
+        "amenities": [],
+        "timezone": "Asia/Dubai",
+        "location": "Abu Dhabi, UAE",
+        "address": "Corniche Road",
+        "latitude": 24.4764,
+        "longitude": 54.3705,
+        "ownerName": "business_2_display",
+        "isFavorite": is_favorite
+    })
+}
+
 test_get_ok!(
     test_name = get_pitches_ok_no_login,
     path = "/pitches",
     status = 200,
-    response = json!([
-        {
-            "id": "00000000-0000-0000-0000-000000000006",
-            "owner_id": "00000000-0000-0000-0000-000000000003",
-            "display_name": "football_pitch_1",
-            "sport": "football"
-        },
-        {
-            "id": "00000000-0000-0000-0000-000000000007",
-            "owner_id": "00000000-0000-0000-0000-000000000003",
-            "display_name": "football_pitch_2",
-            "sport": "football"
-        },
-        {
-            "id": "00000000-0000-0000-0000-000000000008",
-            "owner_id": "00000000-0000-0000-0000-000000000004",
-            "display_name": "basketball_pitch_1",
-            "sport": "basketball"
-        },
-        {
-            "id": "00000000-0000-0000-0000-000000000009",
-            "owner_id": "00000000-0000-0000-0000-000000000004",
-            "display_name": "padel_pitch_1",
-            "sport": "padel"
-        }
-    ])
+    response = last_page(json!([
+        football_pitch_1(false),
+        basketball_pitch_1(false),
+        football_pitch_2(false),
+        padel_pitch_1(false),
+    ]))
 );
 
 test_get_ok!(
-    test_name = get_pitches_ok_player,
+    test_name = get_pitches_ok_no_login_without_relevance,
+    path = "/pitches?relevance=false",
+    status = 200,
+    response = last_page(json!([
+        football_pitch_1(false),
+        football_pitch_2(false),
+        basketball_pitch_1(false),
+        padel_pitch_1(false),
+    ]))
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_player_2_personalized,
     user = player_2,
     path = "/pitches",
     status = 200,
-    response = json!([
-        {
-            "id": "00000000-0000-0000-0000-000000000006",
-            "owner_id": "00000000-0000-0000-0000-000000000003",
-            "display_name": "football_pitch_1",
-            "sport": "football"
-        },
-        {
-            "id": "00000000-0000-0000-0000-000000000007",
-            "owner_id": "00000000-0000-0000-0000-000000000003",
-            "display_name": "football_pitch_2",
-            "sport": "football"
-        },
-        {
-            "id": "00000000-0000-0000-0000-000000000008",
-            "owner_id": "00000000-0000-0000-0000-000000000004",
-            "display_name": "basketball_pitch_1",
-            "sport": "basketball"
-        },
-        {
-            "id": "00000000-0000-0000-0000-000000000009",
-            "owner_id": "00000000-0000-0000-0000-000000000004",
-            "display_name": "padel_pitch_1",
-            "sport": "padel"
-        }
-    ])
+    response = last_page(json!([
+        basketball_pitch_1(false),
+        padel_pitch_1(false),
+        football_pitch_1(true),
+        football_pitch_2(false),
+    ]))
+);
+
+#[tokio::test]
+async fn get_pitches_ok_relevance_explanation() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // exec
+    let response = client.do_get("/pitches?debug=true&limit=1").await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+    assert_eq!(
+        response_body["items"][0]["relevance"],
+        json!({
+            "score": 2.133,
+            "sport": null,
+            "distance": null,
+            "price": null,
+            "rating": 0.9,
+            "availability": 0.0,
+            "popularity": 0.333
+        })
+    );
+
+    Ok(())
+}
+
+test_get_ok!(
+    test_name = get_pitches_ok_player,
+    user = player_1,
+    path = "/pitches",
+    status = 200,
+    response = last_page(json!([
+        football_pitch_1(true),
+        football_pitch_2(true),
+        basketball_pitch_1(false),
+        padel_pitch_1(false),
+    ]))
 );
 
 test_get_ok!(
@@ -75,20 +174,7 @@ test_get_ok!(
     user = business_2,
     path = "/pitches",
     status = 200,
-    response = json!([
-        {
-            "id": "00000000-0000-0000-0000-000000000008",
-            "owner_id": "00000000-0000-0000-0000-000000000004",
-            "display_name": "basketball_pitch_1",
-            "sport": "basketball"
-        },
-        {
-            "id": "00000000-0000-0000-0000-000000000009",
-            "owner_id": "00000000-0000-0000-0000-000000000004",
-            "display_name": "padel_pitch_1",
-            "sport": "padel"
-        }
-    ])
+    response = last_page(json!([basketball_pitch_1(false), padel_pitch_1(false)]))
 );
 
 test_get_ok!(
@@ -96,30 +182,305 @@ test_get_ok!(
     user = admin,
     path = "/pitches",
     status = 200,
-    response = json!([
-            {
-                "id": "00000000-0000-0000-0000-000000000006",
-                "owner_id": "00000000-0000-0000-0000-000000000003",
-                "display_name": "football_pitch_1",
-                "sport": "football"
-            },
-            {
-                "id": "00000000-0000-0000-0000-000000000007",
-                "owner_id": "00000000-0000-0000-0000-000000000003",
-                "display_name": "football_pitch_2",
-                "sport": "football"
-            },
-            {
-                "id": "00000000-0000-0000-0000-000000000008",
-                "owner_id": "00000000-0000-0000-0000-000000000004",
-                "display_name": "basketball_pitch_1",
-                "sport": "basketball"
-            },
-            {
-                "id": "00000000-0000-0000-0000-000000000009",
-                "owner_id": "00000000-0000-0000-0000-000000000004",
-                "display_name": "padel_pitch_1",
-                "sport": "padel"
-            }
-    ])
+    response = last_page(json!([
+        football_pitch_1(false),
+        football_pitch_2(false),
+        basketball_pitch_1(false),
+        padel_pitch_1(false),
+    ]))
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_admin_by_business_id,
+    user = admin,
+    path = "/pitches?business_id=00000000-0000-0000-0000-000000000004",
+    status = 200,
+    response = last_page(json!([basketball_pitch_1(false), padel_pitch_1(false)]))
+);
+
+test_get_err!(
+    test_name = get_pitches_err_business_id_not_admin,
+    user = business_1,
+    path = "/pitches?business_id=00000000-0000-0000-0000-000000000004",
+    status = 403,
+    error_message = "this_operation_is_for_admins_only"
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_filter_environment,
+    path = "/pitches?environment=indoor",
+    status = 200,
+    response = last_page(json!([basketball_pitch_1(false)]))
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_filter_amenities_and_capacity,
+    path = "/pitches?amenities=water,lights&min_capacity=10",
+    status = 200,
+    response = last_page(json!([football_pitch_1(false)]))
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_filter_surface,
+    user = business_1,
+    path = "/pitches?surface=grass",
+    status = 200,
+    response = last_page(json!([football_pitch_2(false)]))
+);
+
+fn last_page(items: Value) -> Value {
+    json!({
+        "items": items,
+        "next_cursor": null,
+        "has_more": false
+    })
+}
+
+fn with_distance(mut pitch: Value, distance_km: f64) -> Value {
+    pitch["distanceKm"] = json!(distance_km);
+    pitch
+}
+
+test_get_ok!(
+    test_name = get_pitches_ok_near_within_radius,
+    user = player_1,
+    path = "/pitches?near=25.3463,55.4209&radius_km=25&sort=distance",
+    status = 200,
+    response = last_page(json!([
+        with_distance(football_pitch_1(true), 4.07),
+        with_distance(football_pitch_2(true), 8.91),
+        with_distance(basketball_pitch_1(false), 22.2),
+    ]))
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_bbox_sorted_from_center,
+    path = "/pitches?bbox=55.2,25.1,55.5,25.4&sort=distance",
+    status = 200,
+    response = last_page(json!([
+        with_distance(football_pitch_1(false), 9.37),
+        with_distance(basketball_pitch_1(false), 9.62),
+        with_distance(football_pitch_2(false), 13.7),
+    ]))
+);
+
+test_get_err!(
+    test_name = get_pitches_err_radius_without_near,
+    user = player_1,
+    path = "/pitches?radius_km=10",
+    status = 400,
+    error_message = "radius_must_be_positive_and_requires_near"
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_sports_sorted_by_price_desc,
+    path = "/pitches?sport=football,padel&sort=-price",
+    status = 200,
+    response = last_page(json!([
+        football_pitch_1(false),
+        football_pitch_2(false),
+        padel_pitch_1(false),
+    ]))
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_price_range_sorted_by_price,
+    path = "/pitches?min_price=150+AED&max_price=200.00+AED&sort=price",
+    status = 200,
+    response = last_page(json!([padel_pitch_1(false), football_pitch_2(false)]))
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_sorted_by_rating_desc,
+    path = "/pitches?sort=-rating",
+    status = 200,
+    response = last_page(json!([
+        football_pitch_1(false),
+        basketball_pitch_1(false),
+        football_pitch_2(false),
+        padel_pitch_1(false),
+    ]))
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_available_between,
+    path = "/pitches?available_between=2025-03-30T13:00:00Z,2025-03-30T15:00:00Z",
+    status = 200,
+    response = last_page(json!([football_pitch_1(false)]))
+);
+
+test_get_ok!(
+    test_name = get_pitches_ok_available_between_only_booked,
+    path = "/pitches?available_between=2025-03-20T13:00:00Z,2025-03-20T14:00:00Z",
+    status = 200,
+    response = last_page(json!([]))
+);
+
+test_get_err!(
+    test_name = get_pitches_err_min_price_above_max_price,
+    user = player_1,
+    path = "/pitches?min_price=300+AED&max_price=100+AED",
+    status = 400,
+    error_message = "price_range_must_be_non_negative_and_ordered"
+);
+
+test_get_err!(
+    test_name = get_pitches_err_price_range_of_two_currencies,
+    user = player_1,
+    path = "/pitches?min_price=100+AED&max_price=300+USD",
+    status = 400,
+    error_message = "price_range_must_be_non_negative_and_ordered"
+);
+
+test_get_err!(
+    test_name = get_pitches_err_min_price_out_of_range,
+    user = player_1,
+    path = "/pitches?min_price=100000000000+AED",
+    status = 400,
+    error_message = "price_range_must_be_non_negative_and_ordered"
+);
+
+#[tokio::test]
+async fn get_pitches_err_min_price_without_currency() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // exec
+    let response = client.do_get("/pitches?min_price=1e12").await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    assert_eq!(response_body["message"], json!("invalid_query_parameters"));
+
+    Ok(())
+}
+
+test_get_err!(
+    test_name = get_pitches_err_sort_by_distance_without_origin,
+    user = player_1,
+    path = "/pitches?sort=distance",
+    status = 400,
+    error_message = "sort_by_distance_requires_near_or_bbox"
+);
+
+/// Invalid query params get a `details` field explaining what is wrong
+async fn check_invalid_query_params(path: &str, expected_details: &str) -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // exec
+    let response = client.do_get(path).await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    assert_eq!(response_body["message"], "invalid_query_parameters");
+    let details = response_body["details"].as_str().unwrap();
+    assert!(details.contains(expected_details), "details: {details}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_pitches_err_unknown_query_param() -> anyhow::Result<()> {
+    check_invalid_query_params("/pitches?offset=1", "unknown field `offset`").await
+}
+
+#[tokio::test]
+async fn get_pitches_err_unknown_sort() -> anyhow::Result<()> {
+    check_invalid_query_params("/pitches?sort=name", "unknown variant `name`").await
+}
+
+#[tokio::test]
+async fn get_pitches_err_unknown_sport() -> anyhow::Result<()> {
+    check_invalid_query_params("/pitches?sport=football,tennis", "unknown variant `tennis`").await
+}
+
+#[tokio::test]
+async fn get_pitches_err_invalid_available_between() -> anyhow::Result<()> {
+    check_invalid_query_params(
+        "/pitches?available_between=2025-03-30T15:00:00Z,2025-03-30T13:00:00Z",
+        "the start must come before the end",
+    )
+    .await
+}
+
+test_get_ok!(
+    test_name = get_amenities_ok,
+    path = "/pitches/amenities",
+    status = 200,
+    response = json!({
+        "amenities": [
+            "cafe",
+            "changing_rooms",
+            "equipment_rental",
+            "lights",
+            "parking",
+            "showers",
+            "water"
+        ]
+    })
+);
+
+#[tokio::test]
+async fn get_pitches_ok_next_page() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    let response = client.do_get("/pitches?limit=3&include_total=true").await?;
+    let first_page = response.json_body()?;
+    assert_eq!(response.status(), 200, "response body:\n{first_page:#}");
+
+    // exec
+    let cursor = first_page["next_cursor"].as_str().unwrap();
+    let response = client
+        .do_get(&format!("/pitches?limit=3&cursor={cursor}"))
+        .await?;
+    let second_page = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 200, "response body:\n{second_page:#}");
+    assert_eq!(
+        first_page["items"],
+        json!([
+            football_pitch_1(false),
+            basketball_pitch_1(false),
+            football_pitch_2(false),
+        ])
+    );
+    assert_eq!(first_page["has_more"], true);
+    assert_eq!(first_page["total"], 4);
+    assert_eq!(second_page, last_page(json!([padel_pitch_1(false)])));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_pitches_err_cursor_of_another_sort() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    let response = client.do_get("/pitches?limit=1&sort=price").await?;
+    let first_page = response.json_body()?;
+    assert_eq!(response.status(), 200, "response body:\n{first_page:#}");
+    let cursor = first_page["next_cursor"].as_str().unwrap();
+
+    // exec
+    let response = client
+        .do_get(&format!("/pitches?limit=1&sort=-rating&cursor={cursor}"))
+        .await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+    assert_eq!(response_body["message"], json!("invalid_query_parameters"));
+
+    Ok(())
+}
+
+test_get_err!(
+    test_name = get_pitches_err_invalid_cursor,
+    user = player_1,
+    path = "/pitches?cursor=not-a-cursor",
+    status = 400,
+    error_message = "invalid_cursor"
 );
@@ -0,0 +1,206 @@
+mod utils;
+
+use crate::utils::{login, test_get_err, test_get_ok, test_login_needed_error};
+use rand::distr::{Alphanumeric, SampleString};
+use serde_json::json;
+use uuid::Uuid;
+
+const DEV_BASE_URL: &str = "http://localhost:1948/api/v1";
+
+// GET /players/{player_id}/public 200
+#[tokio::test]
+async fn get_public_profile_ok_no_login() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // exec
+    let response = client
+        .do_get("/players/00000000-0000-0000-0000-000000000001/public")
+        .await?;
+    let mut response_body = response.json_body()?;
+
+    // check status code
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+
+    // check response body
+    let member_since = response_body
+        .as_object_mut()
+        .unwrap()
+        .remove("memberSince")
+        .unwrap();
+    assert!(member_since.is_string());
+    let expected_body = json!({
+        "id": "00000000-0000-0000-0000-000000000001",
+        "name": "player_1_first player_1_last",
+        "totalBookings": 1,
+        "favoriteSport": "football"
+    });
+    assert_eq!(response_body, expected_body);
+
+    Ok(())
+}
+
+// GET /players/{player_id}/public 200
+#[tokio::test]
+async fn get_public_profile_ok_hidden_fields_omitted() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = player_1);
+
+    // exec
+    let response = client
+        .do_get("/players/00000000-0000-0000-0000-000000000002/public")
+        .await?;
+    let mut response_body = response.json_body()?;
+
+    // check status code
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+
+    // check response body
+    let member_since = response_body
+        .as_object_mut()
+        .unwrap()
+        .remove("memberSince")
+        .unwrap();
+    assert!(member_since.is_string());
+    let expected_body = json!({
+        "id": "00000000-0000-0000-0000-000000000002",
+        "totalBookings": 1
+    });
+    assert_eq!(response_body, expected_body);
+
+    Ok(())
+}
+
+// GET /players/{player_id}/public 200
+#[tokio::test]
+async fn get_public_profile_ok_owner_sees_all_fields() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    login!(client, user = player_2);
+
+    // exec
+    let response = client
+        .do_get("/players/00000000-0000-0000-0000-000000000002/public")
+        .await?;
+    let mut response_body = response.json_body()?;
+
+    // check status code
+    assert_eq!(response.status(), 200, "response body:\n{response_body:#}");
+
+    // check response body
+    let member_since = response_body
+        .as_object_mut()
+        .unwrap()
+        .remove("memberSince")
+        .unwrap();
+    assert!(member_since.is_string());
+    let expected_body = json!({
+        "id": "00000000-0000-0000-0000-000000000002",
+        "name": "player_2_first player_2_last",
+        "totalBookings": 1,
+        "favoriteSport": "basketball"
+    });
+    assert_eq!(response_body, expected_body);
+
+    Ok(())
+}
+
+test_get_err!(
+    test_name = get_public_profile_err_user_is_not_a_player,
+    user = player_1,
+    path = "/players/00000000-0000-0000-0000-000000000003/public",
+    status = 400,
+    error_message = "user_not_found"
+);
+
+test_get_ok!(
+    test_name = get_privacy_settings_ok_player,
+    user = player_2,
+    path = "/users/me/privacy",
+    status = 200,
+    response = json!({
+        "name_visibility": "friends_only",
+        "member_since_visibility": "public",
+        "total_bookings_visibility": "public",
+        "favorite_sport_visibility": "hidden"
+    })
+);
+
+test_get_err!(
+    test_name = get_privacy_settings_err_business,
+    user = business_1,
+    path = "/users/me/privacy",
+    status = 403,
+    error_message = "this_operation_is_for_players_only"
+);
+
+test_login_needed_error!(get_privacy_settings_err_login_needed, "/users/me/privacy");
+
+// PATCH /users/me/privacy 204
+#[tokio::test]
+async fn update_privacy_settings_ok() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // prepare
+    let username = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let password = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let request_body = json!({
+        "username": username,
+        "password": password,
+        "confirm_password": password,
+        "account_type": "player",
+        "profile": {
+            "first_name": "first",
+            "last_name": "last",
+            "preferred_sports": ["padel"],
+        }
+    });
+    let response = client.do_post("/auth/signup", request_body).await?;
+    assert_eq!(response.status(), 201);
+    let user_id = response.json_value::<Uuid>("/user_id")?;
+
+    login!(client, username = username, password = password);
+
+    // exec
+    let payload = json!({
+        "name_visibility": "hidden",
+        "total_bookings_visibility": "friends_only",
+    });
+    let response = client.do_patch("/users/me/privacy", payload).await?;
+
+    // check status code
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = client.do_get("/users/me/privacy").await?;
+    let response_body = response.json_body()?;
+    let expected_body = json!({
+        "name_visibility": "hidden",
+        "member_since_visibility": "public",
+        "total_bookings_visibility": "friends_only",
+        "favorite_sport_visibility": "public"
+    });
+    assert_eq!(response_body, expected_body);
+
+    let guest_client = httpc_test::new_client(DEV_BASE_URL)?;
+    let response = guest_client
+        .do_get(&format!("/players/{user_id}/public"))
+        .await?;
+    let mut response_body = response.json_body()?;
+    assert!(
+        response_body
+            .as_object_mut()
+            .unwrap()
+            .remove("memberSince")
+            .is_some()
+    );
+    let expected_body = json!({
+        "id": user_id,
+        "favoriteSport": "padel"
+    });
+    assert_eq!(response_body, expected_body);
+
+    Ok(())
+}
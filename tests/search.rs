@@ -0,0 +1,109 @@
+mod utils;
+
+use crate::utils::{login, test_get_err, test_get_ok};
+use serde_json::json;
+use uuid::Uuid;
+
+const DEV_BASE_URL: &str = "http://localhost:1948/api/v1";
+
+test_get_ok!(
+    test_name = search_ok_pitch_description,
+    path = "/search?q=lake",
+    status = 200,
+    response = json!({
+        "pitches": [
+            {
+                "id": "00000000-0000-0000-0000-000000000006",
+                "display_name": "football_pitch_1",
+                "sport": "football",
+                "city": "Sharjah",
+                "snippet": "football_pitch_1: five-a-side pitch next to the <mark>lake</mark>",
+                "rank": 0.243
+            }
+        ],
+        "businesses": [],
+        "areas": []
+    })
+);
+
+test_get_ok!(
+    test_name = search_ok_businesses_ranked,
+    user = player_1,
+    path = "/search?q=business_2",
+    status = 200,
+    response = json!({
+        "pitches": [],
+        "businesses": [
+            {
+                "id": "00000000-0000-0000-0000-000000000004",
+                "display_name": "business_2_display",
+                "pitch_count": 2,
+                "snippet": "<mark>business</mark>_<mark>2</mark>_display",
+                "rank": 1.0
+            },
+            {
+                "id": "00000000-0000-0000-0000-000000000003",
+                "display_name": "business_1_display",
+                "pitch_count": 2,
+                "snippet": "<mark>business</mark>_1_display",
+                "rank": 0.818
+            }
+        ],
+        "areas": []
+    })
+);
+
+test_get_ok!(
+    test_name = search_ok_area,
+    path = "/search?q=Sharjah&limit=1",
+    status = 200,
+    response = json!({
+        "pitches": [],
+        "businesses": [],
+        "areas": [
+            {
+                "city": "Sharjah",
+                "country": "UAE",
+                "pitch_count": 2,
+                "snippet": "<mark>Sharjah</mark>",
+                "rank": 1.0
+            }
+        ]
+    })
+);
+
+test_get_err!(
+    test_name = search_err_empty_query,
+    user = player_1,
+    path = "/search?q=%20%20",
+    status = 400,
+    error_message = "search_query_must_have_between_one_and_hundred_characters"
+);
+
+test_get_err!(
+    test_name = search_err_missing_query,
+    user = player_1,
+    path = "/search",
+    status = 400,
+    error_message = "search_query_must_have_between_one_and_hundred_characters"
+);
+
+#[tokio::test]
+async fn search_err_unknown_param() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL)?;
+
+    // exec
+    let response = client.do_get("/search?q=lake&sport=football").await?;
+    let response_body = response.json_body()?;
+
+    // check
+    assert_eq!(response.status(), 400, "response_body:\n{response_body:#}");
+    assert_eq!(response_body["message"], "invalid_query_parameters");
+    let details = response_body["details"].as_str().unwrap();
+    assert!(
+        details.contains("unknown field `sport`"),
+        "details: {details}"
+    );
+
+    Ok(())
+}
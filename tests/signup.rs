@@ -58,6 +58,7 @@ async fn signup_ok_player() -> anyhow::Result<()> {
             "first_name": first_name,
             "last_name": last_name,
             "preferred_sports": preferred_sports,
+            "sport_ratings": [],
         }
     });
     assert_eq!(response_body, expected_body);
@@ -20,7 +20,15 @@ test_get_ok!(
         "profile": {
             "first_name": "player_1_first",
             "last_name": "player_1_last",
-            "preferred_sports": ["football"]
+            "preferred_sports": ["football"],
+            "sport_ratings": [
+                {
+                    "sport": "football",
+                    "self_declared_level": 6,
+                    "system_rating": 5.5,
+                    "confidence": 0.25
+                }
+            ]
         }
     })
 );
@@ -113,6 +121,7 @@ async fn update_personal_info_ok_player() -> anyhow::Result<()> {
             "first_name": first_name,
             "last_name": new_last_name,
             "preferred_sports": preferred_sports,
+            "sport_ratings": [],
         }
     });
     assert_eq!(response_body, expected_response);
@@ -304,6 +313,7 @@ async fn update_personal_info_ok_no_update() -> anyhow::Result<()> {
             "first_name": "player_2_first",
             "last_name": "player_2_last",
             "preferred_sports": ["basketball", "padel"],
+            "sport_ratings": [],
         }
     });
     assert_eq!(response_body, expected_response);
@@ -315,6 +325,190 @@ async fn update_personal_info_ok_no_update() -> anyhow::Result<()> {
     Ok(())
 }
 
+// PATCH /users/me 204
+#[tokio::test]
+async fn update_personal_info_ok_self_declared_levels() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL).unwrap();
+
+    // prepare
+    let username = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let password = Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let request_body = json!({
+        "username": username,
+        "password": password,
+        "confirm_password": password,
+        "account_type": "player",
+        "profile": {
+            "first_name": "first",
+            "last_name": "last",
+            "preferred_sports": ["football", "padel"],
+        }
+    });
+    let response = client.do_post("/auth/signup", request_body).await?;
+    assert_eq!(response.status(), 201);
+
+    login!(client, username = username, password = password);
+
+    // exec
+    let payload = json!({
+        "player_profile": {
+            "self_declared_levels": {
+                "football": 7,
+                "padel": 3,
+            }
+        }
+    });
+    let response = client.do_patch("/users/me", payload).await?;
+    assert_eq!(response.status(), 204);
+
+    let payload = json!({
+        "player_profile": {
+            "self_declared_levels": {
+                "padel": null,
+            }
+        }
+    });
+    let response = client.do_patch("/users/me", payload).await?;
+
+    // check status code
+    assert_eq!(response.status(), 204);
+
+    // check correct excution
+    let response = client.do_get("/users/me").await?;
+    let response_body = response.json_body()?;
+    let expected_ratings = json!([
+        {
+            "sport": "football",
+            "self_declared_level": 7,
+            "system_rating": null,
+            "confidence": 0.0
+        },
+        {
+            "sport": "padel",
+            "self_declared_level": null,
+            "system_rating": null,
+            "confidence": 0.0
+        }
+    ]);
+    assert_eq!(
+        response_body.pointer("/profile/sport_ratings").unwrap(),
+        &expected_ratings
+    );
+
+    Ok(())
+}
+
+// PATCH /users/me 400
+#[tokio::test]
+async fn update_personal_info_err_self_declared_level_out_of_range() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL).unwrap();
+
+    // prepare
+    login!(client, user = player_1);
+
+    // exec
+    let payload = json!({
+        "username": "player_1",
+        "player_profile": {
+            "self_declared_levels": {
+                "football": 11,
+            }
+        }
+    });
+    let response = client.do_patch("/users/me", payload).await?;
+    let response_body = response.json_body()?;
+
+    // check status code
+    assert_eq!(response.status(), 400, "response body:\n{response_body:#}");
+
+    // check response body
+    let expected_body = json!({
+        "message": "skill_level_must_be_between_one_and_ten",
+        "request_id": serde_json::from_value::<Uuid>(response_body.get("request_id").unwrap().clone()).unwrap(),
+        "status": 400
+    });
+    assert_eq!(response_body, expected_body);
+
+    Ok(())
+}
+
+// PATCH /users/me 422
+#[tokio::test]
+async fn update_personal_info_err_player_sends_business_profile() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL).unwrap();
+
+    // prepare
+    login!(client, user = player_1);
+
+    // exec
+    let payload = json!({
+        "username": "player_1",
+        "business_profile": {
+            "display_name": "player_1_display",
+        }
+    });
+    let response = client.do_patch("/users/me", payload).await?;
+    let response_body = response.json_body()?;
+
+    // check status code
+    assert_eq!(response.status(), 422, "response body:\n{response_body:#}");
+
+    // check response body
+    let expected_body = json!({
+        "message": "profile_does_not_match_account_type",
+        "request_id": serde_json::from_value::<Uuid>(response_body.get("request_id").unwrap().clone()).unwrap(),
+        "status": 422
+    });
+    assert_eq!(response_body, expected_body);
+
+    Ok(())
+}
+
+// PATCH /users/me 422
+#[tokio::test]
+async fn update_personal_info_err_business_sends_player_profile() -> anyhow::Result<()> {
+    let client = httpc_test::new_client(DEV_BASE_URL).unwrap();
+
+    // prepare
+    login!(client, user = business_1);
+
+    // exec
+    let payload = json!({
+        "player_profile": {
+            "first_name": "business_1_first",
+        }
+    });
+    let response = client.do_patch("/users/me", payload).await?;
+    let response_body = response.json_body()?;
+
+    // check status code
+    assert_eq!(response.status(), 422, "response body:\n{response_body:#}");
+
+    // check response body
+    let expected_body = json!({
+        "message": "profile_does_not_match_account_type",
+        "request_id": serde_json::from_value::<Uuid>(response_body.get("request_id").unwrap().clone()).unwrap(),
+        "status": 422
+    });
+    assert_eq!(response_body, expected_body);
+
+    // check nothing was updated
+    let response = client.do_get("/users/me").await?;
+    let response_body = response.json_body().unwrap();
+
+    let expected_response = json!({
+        "id": "00000000-0000-0000-0000-000000000003",
+        "username": "business_1",
+        "account_type": "business",
+        "profile": {
+            "display_name": "business_1_display",
+        }
+    });
+    assert_eq!(response_body, expected_response);
+
+    Ok(())
+}
+
 test_get_ok!(
     test_name = get_user_info_ok_admin_requests_player,
     user = admin,
@@ -327,7 +521,15 @@ test_get_ok!(
         "profile": {
             "first_name": "player_1_first",
             "last_name": "player_1_last",
-            "preferred_sports": ["football"]
+            "preferred_sports": ["football"],
+            "sport_ratings": [
+                {
+                    "sport": "football",
+                    "self_declared_level": 6,
+                    "system_rating": 5.5,
+                    "confidence": 0.25
+                }
+            ]
     }
     })
 );